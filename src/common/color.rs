@@ -9,15 +9,239 @@ pub enum ColorType {
 
     /// RGB8888形式 `[r; u8, g: u8, b: u8, a: u8]`
     Rgba8888,
+
+    /// BGR888形式 `[b: u8, g: u8, r: u8]`
+    Bgr888,
+
+    /// BGRA8888形式 `[b: u8, g: u8, r: u8, a: u8]`
+    Bgra8888,
+
+    /// 8ビットグレースケール形式 `[l: u8]`
+    L8,
+
+    /// アルファ付き8ビットグレースケール形式 `[l: u8, a: u8]`
+    La88,
+
+    /// ARGB8888形式 ネイティブエンディアンの`u32`（`0xAARRGGBB`）
+    Argb8888,
+
+    /// XRGB8888形式 ネイティブエンディアンの`u32`（`0xXXRRGGBB`）
+    /// 
+    /// デコード時のXは 0xFF になり、エンコード時のXは無視されます。
+    Xrgb8888,
+
+    /// RGB555形式 `u16`（`0bXRRRRRGGGGGBBBBB`）
+    ///
+    /// デコード時のXは 0 になり、エンコード時のXは無視されます。
+    Rgb555,
+
+    /// ARGB1555形式 `u16`（`0bARRRRRGGGGGBBBBB`）
+    ///
+    /// デコード時は透明色のピクセルのAが 0、それ以外のピクセルのAが 1 になります。
+    Argb1555,
+
+    /// RGB444形式 `u16`（`0xXRGB`）
+    ///
+    /// デコード時のXは 0 になり、エンコード時のXは無視されます。
+    Rgb444,
+
+    /// RGBA4444形式 `u16`（`0xRGBA`）
+    ///
+    /// デコード時は透明色のピクセルのAが 0、それ以外のピクセルのAが 0xF になります。
+    Rgba4444,
+
+    /// RGB332形式 `u8`（`0bRRRGGGBB`）
+    ///
+    /// デコード時は各チャンネルの上位ビットを切り捨てで取り出し、`DecodeOptions::dither`で順序ディザリングを適用できます。
+    /// エンコード時はビット複製で拡張されます。
+    Rgb332,
+
+    /// BGR565形式 `u16`（`0bBBBBBGGGGGGRRRRR`）
+    ///
+    /// `ColorType::Rgb565`のRとBを入れ替えた形式です。
+    Bgr565,
+
+    /// RGB565形式 ビッグエンディアンの`u16`
+    ///
+    /// CPUのエンディアンにかかわらず上位バイトが先になります。
+    /// SPI接続のディスプレイなど、ビッグエンディアンのピクセルを要求する転送先にそのまま書き込めます。
+    Rgb565Be,
+
+    /// BGR565形式 ビッグエンディアンの`u16`（`0bBBBBBGGGGGGRRRRR`）
+    ///
+    /// `ColorType::Bgr565`を`ColorType::Rgb565Be`と同じバイト順にした形式です。
+    Bgr565Be,
+
+    /// 16ビットRGB形式 ネイティブエンディアンの`[r: u16, g: u16, b: u16]`
+    ///
+    /// エンコード時は各チャンネルを最も近い8ビットの値に丸めます。
+    Rgb16,
+
+    /// 16ビットRGBA形式 ネイティブエンディアンの`[r: u16, g: u16, b: u16, a: u16]`
+    ///
+    /// デコード時は透明色のピクセルのAが 0、それ以外のピクセルのAが 0xFFFF になります。
+    Rgba16,
+
+    /// 浮動小数点数RGB形式 ネイティブエンディアンの`[r: f32, g: f32, b: f32]`（0.0 - 1.0）
+    ///
+    /// 既定では線形値として扱い、sRGBの伝達関数を適用します。
+    /// エンコード時は範囲外の値を 0.0 - 1.0 に、NaN を 0.0 に丸めます。
+    RgbF32,
+
+    /// 浮動小数点数RGBA形式 ネイティブエンディアンの`[r: f32, g: f32, b: f32, a: f32]`（0.0 - 1.0）
+    ///
+    /// RGBは`ColorType::RgbF32`と同じで、Aには伝達関数を適用しません。
+    /// デコード時は透明色のピクセルのAが 0.0、それ以外のピクセルのAが 1.0 になります。
+    RgbaF32,
+
+    /// 1ビットモノクロ形式（1 が白、0 が黒）
+    ///
+    /// 各行は最上位ビットから左のピクセル順に詰められ、バイト境界までパディングされます。
+    /// デコード時のパディングは 0 になり、エンコード時のパディングは無視されます。
+    ///
+    /// 1ピクセルが1バイトに満たないため、ピクセル位置を指定する関数では使用できません。
+    Mono1,
 }
 
 impl ColorType {
     /// ピクセルあたりのバイト数
+    ///
+    /// 1ピクセルが1バイトに満たない色データタイプ（`ColorType::Mono1`）では、1ピクセルを含むのに必要な 1 を返します。
+    /// この値はピクセルのバイト位置の計算には使用できないため、[`is_byte_addressable`](Self::is_byte_addressable)で確認してから使用してください。
+    /// 1行あたりのバイト数は[`bytes_per_row`](Self::bytes_per_row)で取得できます。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use limg_core::ColorType;
+    ///
+    /// assert_eq!(ColorType::Rgb565.bytes_per_pixel(), 2);
+    /// assert_eq!(ColorType::Mono1.bytes_per_pixel(), 1);
+    /// assert_eq!(ColorType::Mono1.bytes_per_row(10), 2);
+    /// ```
     pub const fn bytes_per_pixel(self) -> usize {
         match self {
             ColorType::Rgb888 => 3,
             ColorType::Rgb565 => 2,
             ColorType::Rgba8888 => 4,
+            ColorType::Bgr888 => 3,
+            ColorType::Bgra8888 => 4,
+            ColorType::L8 => 1,
+            ColorType::La88 => 2,
+            ColorType::Argb8888 => 4,
+            ColorType::Xrgb8888 => 4,
+            ColorType::Rgb555 => 2,
+            ColorType::Argb1555 => 2,
+            ColorType::Rgb444 => 2,
+            ColorType::Rgba4444 => 2,
+            ColorType::Rgb332 => 1,
+            ColorType::Bgr565 => 2,
+            ColorType::Rgb565Be => 2,
+            ColorType::Bgr565Be => 2,
+            ColorType::Rgb16 => 6,
+            ColorType::Rgba16 => 8,
+            ColorType::RgbF32 => 12,
+            ColorType::RgbaF32 => 16,
+            ColorType::Mono1 => 1,
+        }
+    }
+
+    /// ピクセルあたりのビット数
+    pub const fn bits_per_pixel(self) -> usize {
+        match self {
+            ColorType::Mono1 => 1,
+            _ => self.bytes_per_pixel() * 8,
+        }
+    }
+
+    /// 1ピクセルが1バイト以上で、ピクセルごとにバイト位置を指定できるかどうか
+    ///
+    /// `false`の色データタイプ（`ColorType::Mono1`）は、ピクセル位置を指定するエンコードおよびデコードでは使用できません。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use limg_core::ColorType;
+    ///
+    /// assert!(ColorType::Rgb888.is_byte_addressable());
+    /// assert!(!ColorType::Mono1.is_byte_addressable());
+    /// ```
+    #[inline]
+    pub const fn is_byte_addressable(self) -> bool {
+        !matches!(self, ColorType::Mono1)
+    }
+
+    /// 幅`width`ピクセルの1行あたりのバイト数
+    ///
+    /// 1ピクセルが1バイトに満たない場合、バイト境界までのパディングを含みます。
+    pub const fn bytes_per_row(self, width: usize) -> usize {
+        (self.bits_per_pixel() * width).div_ceil(8)
+    }
+}
+
+/// 8ビットチャンネルの色データにおけるRとB、アルファの並び順
+///
+/// RGB系とBGR系のカーネルを共通化するために使用します。
+pub trait ChannelOrder {
+    /// RとBを入れ替えるかどうか
+    const SWAP_RB: bool;
+
+    /// 4バイトの色データでアルファを先頭に置くかどうか
+    /// 
+    /// SIMD実装は`false`の並び順のみ対応しています。
+    const ALPHA_FIRST: bool = false;
+
+    /// `first`と`last`を並び順に合わせて入れ替えます。
+    #[inline(always)]
+    fn swap_rb<T>(first: T, last: T) -> (T, T) {
+        if Self::SWAP_RB { (last, first) } else { (first, last) }
+    }
+
+    /// `[R, G, B]`と並び順の配列を相互に変換します。
+    #[inline(always)]
+    fn order(rgb: [u8; 3]) -> [u8; 3] {
+        let (first, last) = Self::swap_rb(rgb[0], rgb[2]);
+        [first, rgb[1], last]
+    }
+
+    /// `[R, G, B]`配列とアルファ値から並び順の4バイトを作成します。
+    #[inline(always)]
+    fn pack_rgba(rgb: [u8; 3], alpha: u8) -> [u8; 4] {
+        let [first, g, last] = Self::order(rgb);
+        if Self::ALPHA_FIRST { [alpha, first, g, last] } else { [first, g, last, alpha] }
+    }
+
+    /// 並び順の4バイトから`[R, G, B]`配列とアルファ値を取り出します。
+    #[inline(always)]
+    fn unpack_rgba(rgba: [u8; 4]) -> ([u8; 3], u8) {
+        if Self::ALPHA_FIRST {
+            (Self::order([rgba[1], rgba[2], rgba[3]]), rgba[0])
+        } else {
+            (Self::order([rgba[0], rgba[1], rgba[2]]), rgba[3])
         }
     }
 }
+
+/// `[r, g, b]`の並び順
+pub struct Rgb;
+
+/// `[b, g, r]`の並び順
+pub struct Bgr;
+
+/// ネイティブエンディアンの`u32`に`0xAARRGGBB`として格納する並び順
+/// 
+/// リトルエンディアンでは`[b, g, r, a]`、ビッグエンディアンでは`[a, r, g, b]`になります。
+pub struct Argb;
+
+impl ChannelOrder for Rgb {
+    const SWAP_RB: bool = false;
+}
+
+impl ChannelOrder for Bgr {
+    const SWAP_RB: bool = true;
+}
+
+impl ChannelOrder for Argb {
+    const SWAP_RB: bool = cfg!(target_endian = "little");
+    const ALPHA_FIRST: bool = cfg!(target_endian = "big");
+}
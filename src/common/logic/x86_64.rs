@@ -25,12 +25,30 @@ impl M128I {
         _mm_storeu_si128(mem_addr.cast::<__m128i>(), self.0);
     }
 
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn loadl_epi64(mem_addr: *const M128I) -> M128I {
+        M128I(_mm_loadl_epi64(mem_addr.cast::<__m128i>()))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn storel_epi64(self, mem_addr: *mut M128I) {
+        _mm_storel_epi64(mem_addr.cast::<__m128i>(), self.0);
+    }
+
     #[inline]
     #[target_feature(enable = "sse2")]
     pub unsafe fn set1_epi16(a: i16) -> M128I {
         M128I(_mm_set1_epi16(a))
     }
 
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn set1_epi32(a: i32) -> M128I {
+        M128I(_mm_set1_epi32(a))
+    }
+
     #[inline]
     #[target_feature(enable = "sse2")]
     pub unsafe fn slli_si128<const IMM8: i32>(self) -> M128I {
@@ -55,12 +73,96 @@ impl M128I {
         M128I(_mm_srli_epi16::<IMM8>(self.0))
     }
 
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn srai_epi16<const IMM8: i32>(self) -> M128I {
+        M128I(_mm_srai_epi16::<IMM8>(self.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn add_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_add_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn adds_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_adds_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn sub_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_sub_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn mullo_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_mullo_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn min_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_min_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn max_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_max_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn srai_epi32<const IMM8: i32>(self) -> M128I {
+        M128I(_mm_srai_epi32::<IMM8>(self.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn add_epi32(self, a: M128I) -> M128I {
+        M128I(_mm_add_epi32(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn madd_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_madd_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn packs_epi32(self, a: M128I) -> M128I {
+        M128I(_mm_packs_epi32(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn unpacklo_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_unpacklo_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn unpackhi_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_unpackhi_epi16(self.0, a.0))
+    }
+
     #[inline]
     #[target_feature(enable = "sse2")]
     pub unsafe fn and_si128(self, a: M128I) -> M128I {
         M128I(_mm_and_si128(self.0, a.0))
     }
 
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn andnot_si128(self, a: M128I) -> M128I {
+        M128I(_mm_andnot_si128(self.0, a.0))
+    }
+
     #[inline]
     #[target_feature(enable = "sse2")]
     pub unsafe fn or_si128(self, a: M128I) -> M128I {
@@ -79,6 +181,18 @@ impl M128I {
         M128I(_mm_cmpeq_epi16(self.0, a.0))
     }
 
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn cmpgt_epi16(self, a: M128I) -> M128I {
+        M128I(_mm_cmpgt_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn movemask_epi8(self) -> i32 {
+        _mm_movemask_epi8(self.0)
+    }
+
     #[inline]
     #[target_feature(enable = "ssse3")]
     pub unsafe fn shuffle_epi8(self, a: M128I) -> M128I {
@@ -92,7 +206,7 @@ impl M128I {
         const E00: i8, const E01: i8, const E02: i8, const E03: i8, const E04: i8, const E05: i8, const E06: i8, const E07: i8,
         const E08: i8, const E09: i8, const E10: i8, const E11: i8, const E12: i8, const E13: i8, const E14: i8, const E15: i8,
     >() -> M128I {
-        M128I(transmute([E00, E01, E02, E03, E04, E05, E06, E07, E08, E09, E10, E11, E12, E13, E14, E15]))
+        M128I(transmute::<[i8; 16], __m128i>([E00, E01, E02, E03, E04, E05, E06, E07, E08, E09, E10, E11, E12, E13, E14, E15]))
     }
 
     #[inline(always)]
@@ -100,12 +214,12 @@ impl M128I {
         const E00: u8, const E01: u8, const E02: u8, const E03: u8, const E04: u8, const E05: u8, const E06: u8, const E07: u8,
         const E08: u8, const E09: u8, const E10: u8, const E11: u8, const E12: u8, const E13: u8, const E14: u8, const E15: u8,
     >() -> M128I {
-        M128I(transmute([E00, E01, E02, E03, E04, E05, E06, E07, E08, E09, E10, E11, E12, E13, E14, E15]))
+        M128I(transmute::<[u8; 16], __m128i>([E00, E01, E02, E03, E04, E05, E06, E07, E08, E09, E10, E11, E12, E13, E14, E15]))
     }
 
     #[inline(always)]
     pub const unsafe fn const1_u16<const A: u16>() -> M128I {
-        M128I(transmute([A; 8]))
+        M128I(transmute::<[u16; 8], __m128i>([A; 8]))
     }
 
     #[inline]
@@ -158,6 +272,15 @@ impl ::core::ops::BitOr for M128I {
     }
 }
 
+impl ::core::ops::BitXor for M128I {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        unsafe { self.xor_si128(rhs) }
+    }
+}
+
 
 #[repr(transparent)]
 #[derive(Clone, Copy)]
@@ -183,6 +306,7 @@ impl M256I {
     }
 
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     #[target_feature(enable = "avx")]
     pub unsafe fn set_epi32(e0: i32, e1: i32, e2: i32, e3: i32, e4: i32, e5: i32, e6: i32, e7: i32) -> M256I {
         M256I(_mm256_set_epi32(e0, e1, e2, e3, e4, e5, e6, e7))
@@ -194,6 +318,12 @@ impl M256I {
         M256I(_mm256_set1_epi16(a))
     }
 
+    #[inline]
+    #[target_feature(enable = "avx")]
+    pub unsafe fn set1_epi32(a: i32) -> M256I {
+        M256I(_mm256_set1_epi32(a))
+    }
+
     #[inline]
     #[target_feature(enable = "avx2")]
     pub unsafe fn slli_si256<const IMM8: i32>(self) -> M256I {
@@ -218,12 +348,96 @@ impl M256I {
         M256I(_mm256_srli_epi16::<IMM8>(self.0))
     }
 
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn srai_epi16<const IMM8: i32>(self) -> M256I {
+        M256I(_mm256_srai_epi16::<IMM8>(self.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn add_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_add_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn adds_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_adds_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn sub_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_sub_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn mullo_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_mullo_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn min_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_min_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn max_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_max_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn srai_epi32<const IMM8: i32>(self) -> M256I {
+        M256I(_mm256_srai_epi32::<IMM8>(self.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn add_epi32(self, a: M256I) -> M256I {
+        M256I(_mm256_add_epi32(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn madd_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_madd_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn packs_epi32(self, a: M256I) -> M256I {
+        M256I(_mm256_packs_epi32(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn unpacklo_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_unpacklo_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn unpackhi_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_unpackhi_epi16(self.0, a.0))
+    }
+
     #[inline]
     #[target_feature(enable = "avx2")]
     pub unsafe fn and_si256(self, a: M256I) -> M256I {
         M256I(_mm256_and_si256(self.0, a.0))
     }
 
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn andnot_si256(self, a: M256I) -> M256I {
+        M256I(_mm256_andnot_si256(self.0, a.0))
+    }
+
     #[inline]
     #[target_feature(enable = "avx2")]
     pub unsafe fn or_si256(self, a: M256I) -> M256I {
@@ -242,6 +456,18 @@ impl M256I {
         M256I(_mm256_cmpeq_epi16(self.0, a.0))
     }
 
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn cmpgt_epi16(self, a: M256I) -> M256I {
+        M256I(_mm256_cmpgt_epi16(self.0, a.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn movemask_epi8(self) -> i32 {
+        _mm256_movemask_epi8(self.0)
+    }
+
     #[inline]
     #[target_feature(enable = "avx2")]
     pub unsafe fn blend_epi32<const IMM8: i32>(self, a: M256I) -> M256I {
@@ -272,13 +498,25 @@ impl M256I {
         M256I(_mm256_permutevar8x32_epi32(self.0, a.0))
     }
 
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn extracti128_si256<const IMM1: i32>(self) -> M128I {
+        M128I(_mm256_extracti128_si256::<IMM1>(self.0))
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn cvtepu8_epi16(a: M128I) -> M256I {
+        M256I(_mm256_cvtepu8_epi16(a.0))
+    }
+
     // ---- 追加関数 ----
 
     #[inline(always)]
     pub const unsafe fn const_i32<
         const E00: i32, const E01: i32, const E02: i32, const E03: i32, const E04: i32, const E05: i32, const E06: i32, const E07: i32,
         >() -> M256I {
-        M256I(transmute([E00, E01, E02, E03, E04, E05, E06, E07]))
+        M256I(transmute::<[i32; 8], __m256i>([E00, E01, E02, E03, E04, E05, E06, E07]))
     }
 
     #[inline(always)]
@@ -288,7 +526,7 @@ impl M256I {
         const E16: i8, const E17: i8, const E18: i8, const E19: i8, const E20: i8, const E21: i8, const E22: i8, const E23: i8,
         const E24: i8, const E25: i8, const E26: i8, const E27: i8, const E28: i8, const E29: i8, const E30: i8, const E31: i8
         >() -> M256I {
-        M256I(transmute([
+        M256I(transmute::<[i8; 32], __m256i>([
             E00, E01, E02, E03, E04, E05, E06, E07,
             E08, E09, E10, E11, E12, E13, E14, E15,
             E16, E17, E18, E19, E20, E21, E22, E23,
@@ -303,7 +541,7 @@ impl M256I {
         const E16: u8, const E17: u8, const E18: u8, const E19: u8, const E20: u8, const E21: u8, const E22: u8, const E23: u8,
         const E24: u8, const E25: u8, const E26: u8, const E27: u8, const E28: u8, const E29: u8, const E30: u8, const E31: u8
         >() -> M256I {
-        M256I(transmute([
+        M256I(transmute::<[u8; 32], __m256i>([
             E00, E01, E02, E03, E04, E05, E06, E07,
             E08, E09, E10, E11, E12, E13, E14, E15,
             E16, E17, E18, E19, E20, E21, E22, E23,
@@ -313,7 +551,7 @@ impl M256I {
 
     #[inline(always)]
     pub const unsafe fn const1_u16<const A: u16>() -> M256I {
-        M256I(transmute([A; 16]))
+        M256I(transmute::<[u16; 16], __m256i>([A; 16]))
     }
 
     #[inline]
@@ -371,3 +609,12 @@ impl ::core::ops::BitOr for M256I {
     }
 }
 
+impl ::core::ops::BitXor for M256I {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        unsafe { self.xor_si256(rhs) }
+    }
+}
+
@@ -2,4 +2,5 @@ pub mod header;
 pub mod logic;
 pub mod color;
 pub mod spec;
-pub mod pixel;
\ No newline at end of file
+pub mod pixel;
+pub mod wide;
@@ -6,10 +6,95 @@ pub const PIXEL_R_MASK: u16 = 0xF800;
 pub const PIXEL_G_MASK: u16 = 0x07E0;
 /// RGB565のB情報マスク
 pub const PIXEL_B_MASK: u16 = 0x001F;
+/// RGB565のGの最下位ビット
+pub(crate) const PIXEL_G_LSB: u16 = 0x0020;
+
+/// RGB555のR情報マスク
+pub(crate) const RGB555_R_MASK: u16 = 0x7C00;
+/// RGB555のG情報マスク
+pub(crate) const RGB555_G_MASK: u16 = 0x03E0;
+/// RGB555の最上位ビット（ARGB1555のアルファ）
+pub(crate) const RGB555_A_BIT: u16 = 0x8000;
+
+/// 減色時の量子化方法
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Quantization {
+    /// 下位ビットを切り捨てる
+    /// 
+    /// [`rgb_to_pixel`]と同じ結果になります。
+    #[default]
+    Truncate,
+
+    /// 下位ビットを四捨五入する（最大値で飽和）
+    Round,
+
+    /// [`pixel_to_rgb`]で復元した値が最も近くなる値を選ぶ
+    /// 
+    /// 等距離の場合は大きい値を選びます。
+    Nearest,
+}
+
+impl Quantization {
+    /// 8ビットの`value`を`bits`ビット（1 - 8）に量子化します。
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use limg_core::Quantization;
+    /// 
+    /// assert_eq!(Quantization::Truncate.quantize(132, 5), 16);
+    /// assert_eq!(Quantization::Round.quantize(132, 5), 17);
+    /// // 16 -> 132, 17 -> 140
+    /// assert_eq!(Quantization::Nearest.quantize(132, 5), 16);
+    /// ```
+    #[inline]
+    pub const fn quantize(self, value: u8, bits: u32) -> u8 {
+        let shift = 8 - bits;
+        let max = ((1u16 << bits) - 1) as u8;
+        let truncated = value >> shift;
+
+        match self {
+            Quantization::Truncate => truncated,
+            Quantization::Round => {
+                let rounded = (value as u16 + ((1 << shift) >> 1)) >> shift;
+                if rounded > max as u16 { max } else { rounded as u8 }
+            },
+            Quantization::Nearest => {
+                // 復元値は切り捨てた値の前後1つ以内に最も近いものがある
+                let mut nearest = if truncated > 0 { truncated - 1 } else { 0 };
+                let last = if truncated < max { truncated + 1 } else { max };
+
+                let mut candidate = nearest + 1;
+                while candidate <= last {
+                    if expand_bits(candidate, bits).abs_diff(value) <= expand_bits(nearest, bits).abs_diff(value) {
+                        nearest = candidate;
+                    }
+                    candidate += 1;
+                }
+
+                nearest
+            },
+        }
+    }
+}
+
+/// `bits`ビット（1 - 8）の`value`をビット複製で8ビットに拡張します。
+#[inline]
+pub(crate) const fn expand_bits(value: u8, bits: u32) -> u8 {
+    let mut result = 0u16;
+    let mut shift = 8 - bits as i32;
+
+    while shift > -(bits as i32) {
+        result |= if shift >= 0 { (value as u16) << shift } else { (value as u16) >> -shift };
+        shift -= bits as i32;
+    }
+
+    result as u8
+}
 
 /// `[R, G, B]`配列からピクセルに変換します
 /// 
-/// 変換時に減色が発生します。
+/// 変換時に減色が発生します。各チャンネルの下位ビットは切り捨てられます。
 /// 
 /// # Examples
 /// 
@@ -43,3 +128,114 @@ pub const fn pixel_to_rgb(pixel: u16) -> [u8; 3] {
     let b =  (pixel & PIXEL_B_MASK)        as u8;
     [(r << 3) | (r >> 2), (g << 2) | (g >> 4), (b << 3) | (b >> 2)]
 }
+
+/// ピクセルからRGB555に変換します
+///
+/// Gの最下位ビットは切り捨てられ、最上位ビットは 0 になります。
+#[inline(always)]
+pub(crate) const fn pixel_to_rgb555(pixel: u16) -> u16 {
+    ((pixel >> 1) & (RGB555_R_MASK | RGB555_G_MASK)) | (pixel & PIXEL_B_MASK)
+}
+
+/// RGB555からピクセルに変換します
+///
+/// 最上位ビットは無視され、Gの最下位ビットはビット複製で補われます。
+#[inline(always)]
+pub(crate) const fn rgb555_to_pixel(value: u16) -> u16 {
+    ((value << 1) & (PIXEL_R_MASK | PIXEL_G_MASK & !PIXEL_G_LSB)) | ((value >> 4) & PIXEL_G_LSB) | (value & PIXEL_B_MASK)
+}
+
+/// ピクセルのRとBを入れ替えます
+///
+/// RGB565とBGR565の相互変換に使用します。
+#[inline(always)]
+pub(crate) const fn swap_rb(pixel: u16) -> u16 {
+    (pixel << 11) | (pixel & PIXEL_G_MASK) | (pixel >> 11)
+}
+
+/// ピクセルから4ビットの`[R, G, B]`配列に変換します
+///
+/// 各チャンネルの上位4ビットを取り出します。
+#[inline(always)]
+pub(crate) const fn pixel_to_rgb444(pixel: u16) -> [u8; 3] {
+    [(pixel >> 12) as u8, ((pixel >> 7) & 0x0F) as u8, ((pixel >> 1) & 0x0F) as u8]
+}
+
+/// 4ビットの`[R, G, B]`配列からピクセルに変換します
+///
+/// 各チャンネルはビット複製で拡張され、8ビットに拡張した値を切り捨てで減色したものと同じになります。
+#[inline(always)]
+pub(crate) const fn rgb444_to_pixel(rgb: [u8; 3]) -> u16 {
+    let [r, g, b] = [rgb[0] as u16, rgb[1] as u16, rgb[2] as u16];
+    (((r << 1) | (r >> 3)) << 11) | (((g << 2) | (g >> 2)) << 5) | ((b << 1) | (b >> 3))
+}
+
+/// RGB332（`0bRRRGGGBB`）からピクセルに変換します
+///
+/// 各チャンネルはビット複製で拡張され、8ビットに拡張した値を切り捨てで減色したものと同じになります。
+#[inline(always)]
+pub(crate) const fn rgb332_to_pixel(rgb332: u8) -> u16 {
+    let [r, g, b] = [(rgb332 >> 5) as u16, ((rgb332 >> 2) & 0x07) as u16, (rgb332 & 0x03) as u16];
+    (((r << 2) | (r >> 1)) << 11) | (((g << 3) | g) << 5) | ((b << 3) | (b << 1) | (b >> 1))
+}
+
+/// `[R, G, B]`配列から`quantization`の量子化方法でピクセルに変換します
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{pixel_to_rgb, rgb_to_pixel_with_quantization, Quantization};
+/// 
+/// let pixel = rgb_to_pixel_with_quantization([0, 128, 255], Quantization::Nearest);
+/// assert_eq!(pixel_to_rgb(pixel), [0, 130, 255]);
+/// 
+/// let pixel = rgb_to_pixel_with_quantization([4, 128, 251], Quantization::Nearest);
+/// assert_eq!(pixel_to_rgb(pixel), [8, 130, 255]);
+/// ```
+#[inline(always)]
+pub const fn rgb_to_pixel_with_quantization(rgb: [u8; 3], quantization: Quantization) -> u16 {
+    ((quantization.quantize(rgb[0], 5) as u16) << 11) |
+    ((quantization.quantize(rgb[1], 6) as u16) <<  5) |
+     (quantization.quantize(rgb[2], 5) as u16)
+}
+
+/// RGBから輝度を求める際の係数
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LumaCoefficients {
+    /// ITU-R BT.601（Y = 0.299R + 0.587G + 0.114B）
+    #[default]
+    Bt601,
+
+    /// ITU-R BT.709（Y = 0.2126R + 0.7152G + 0.0722B）
+    Bt709,
+}
+
+impl LumaCoefficients {
+    /// R, G, Bの重みを合計が256になる整数で返します。
+    #[inline(always)]
+    pub const fn weights(self) -> [u16; 3] {
+        match self {
+            LumaCoefficients::Bt601 => [77, 150, 29],
+            LumaCoefficients::Bt709 => [54, 183, 19],
+        }
+    }
+
+    /// `[R, G, B]`配列から輝度を求めます。
+    /// 
+    /// 整数演算のみで計算し、白は 255 になります。
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use limg_core::LumaCoefficients;
+    /// 
+    /// assert_eq!(LumaCoefficients::Bt601.luma([255, 255, 255]), 255);
+    /// assert_eq!(LumaCoefficients::Bt601.luma([0, 255, 0]), 149);
+    /// assert_eq!(LumaCoefficients::Bt709.luma([0, 255, 0]), 182);
+    /// ```
+    #[inline(always)]
+    pub const fn luma(self, rgb: [u8; 3]) -> u8 {
+        let [r, g, b] = self.weights();
+        ((r * rgb[0] as u16 + g * rgb[1] as u16 + b * rgb[2] as u16 + 128) >> 8) as u8
+    }
+}
@@ -0,0 +1,276 @@
+use crate::common::color::ColorType;
+
+/// チャンネルあたり8ビットを超える色データタイプを変換する際のブロックのピクセル数
+/// 
+/// 順序ディザリングの周期（8ピクセル）の倍数です。
+pub(crate) const WIDE_BLOCK_PIXELS: usize = 64;
+
+/// `color_type`がチャンネルあたり8ビットを超える場合、変換先のチャンネルあたり8ビットの色データタイプを返します。
+#[inline(always)]
+pub(crate) const fn narrow_color_type(color_type: ColorType) -> Option<ColorType> {
+    match color_type {
+        ColorType::Rgb16 | ColorType::RgbF32 => Some(ColorType::Rgb888),
+        ColorType::Rgba16 | ColorType::RgbaF32 => Some(ColorType::Rgba8888),
+        _ => None,
+    }
+}
+
+/// `color_type`の1ピクセル分の`src`をチャンネルあたり8ビットの`[R, G, B, A]`配列に変換します。
+/// 
+/// アルファを持たない色データタイプの場合、アルファ値は 255 になります。
+/// `srgb_transfer`が`true`の場合、浮動小数点数のRGBは線形値としてsRGBの伝達関数を適用します。
+#[inline(always)]
+pub(crate) fn narrow_pixel(src: &[u8], color_type: ColorType, srgb_transfer: bool) -> [u8; 4] {
+    let mut rgba = [u8::MAX; 4];
+
+    match color_type {
+        ColorType::Rgb16 | ColorType::Rgba16 => {
+            for (value, src) in rgba.iter_mut().zip(src.chunks_exact(2)) {
+                *value = u16_to_u8(u16::from_ne_bytes([src[0], src[1]]));
+            }
+        },
+        ColorType::RgbF32 | ColorType::RgbaF32 => {
+            for (channel, (value, src)) in rgba.iter_mut().zip(src.chunks_exact(4)).enumerate() {
+                let src = f32::from_ne_bytes([src[0], src[1], src[2], src[3]]);
+                // アルファは常に線形値として扱う
+                *value = if srgb_transfer && channel < 3 { linear_to_srgb(src) } else { f32_to_u8(src) };
+            }
+        },
+        _ => unreachable!(),
+    }
+
+    rgba
+}
+
+/// チャンネルあたり8ビットの`[R, G, B, A]`配列を`color_type`の1ピクセル分として`dst`に書き込みます。
+/// 
+/// アルファを持たない色データタイプの場合、アルファ値は無視されます。
+#[inline(always)]
+pub(crate) fn widen_pixel(rgba: [u8; 4], dst: &mut [u8], color_type: ColorType, srgb_transfer: bool) {
+    match color_type {
+        ColorType::Rgb16 | ColorType::Rgba16 => {
+            for (value, dst) in rgba.iter().zip(dst.chunks_exact_mut(2)) {
+                dst.copy_from_slice(&(*value as u16 * 257).to_ne_bytes());
+            }
+        },
+        ColorType::RgbF32 | ColorType::RgbaF32 => {
+            for (channel, (value, dst)) in rgba.iter().zip(dst.chunks_exact_mut(4)).enumerate() {
+                let value = if srgb_transfer && channel < 3 { SRGB_TO_LINEAR[*value as usize] } else { *value as f32 / 255.0 };
+                dst.copy_from_slice(&value.to_ne_bytes());
+            }
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// `color_type`の`num_pixels`分のピクセルを[`narrow_color_type`]の色データタイプに変換します。
+#[inline(always)]
+pub(crate) unsafe fn narrow_pixels(data: *const u8, buf: *mut u8, color_type: ColorType, num_pixels: usize, srgb_transfer: bool) {
+    let Some(narrow_type) = narrow_color_type(color_type) else { unreachable!() };
+    let narrow_bytes = narrow_type.bytes_per_pixel();
+
+    let data = unsafe { ::core::slice::from_raw_parts(data, color_type.bytes_per_pixel() * num_pixels) };
+    let buf = unsafe { ::core::slice::from_raw_parts_mut(buf, narrow_bytes * num_pixels) };
+
+    for (src, dst) in data.chunks_exact(color_type.bytes_per_pixel()).zip(buf.chunks_exact_mut(narrow_bytes)) {
+        dst.copy_from_slice(&narrow_pixel(src, color_type, srgb_transfer)[..narrow_bytes]);
+    }
+}
+
+/// [`narrow_color_type`]の色データタイプの`num_pixels`分のピクセルを`color_type`に変換します。
+#[inline(always)]
+pub(crate) unsafe fn widen_pixels(data: *const u8, buf: *mut u8, color_type: ColorType, num_pixels: usize, srgb_transfer: bool) {
+    let Some(narrow_type) = narrow_color_type(color_type) else { unreachable!() };
+    let narrow_bytes = narrow_type.bytes_per_pixel();
+
+    let data = unsafe { ::core::slice::from_raw_parts(data, narrow_bytes * num_pixels) };
+    let buf = unsafe { ::core::slice::from_raw_parts_mut(buf, color_type.bytes_per_pixel() * num_pixels) };
+
+    for (src, dst) in data.chunks_exact(narrow_bytes).zip(buf.chunks_exact_mut(color_type.bytes_per_pixel())) {
+        let mut rgba = [u8::MAX; 4];
+        rgba[..narrow_bytes].copy_from_slice(src);
+        widen_pixel(rgba, dst, color_type, srgb_transfer);
+    }
+}
+
+/// `color_type`の`num_pixels`分のピクセルを[`WIDE_BLOCK_PIXELS`]ずつ[`narrow_color_type`]の色データタイプに変換し、
+/// 変換したブロックと先頭からのピクセル位置、ピクセル数を`f`に渡します。
+#[inline(always)]
+pub(crate) unsafe fn for_each_narrow_block(data: *const u8, color_type: ColorType, num_pixels: usize, srgb_transfer: bool, mut f: impl FnMut(*const u8, usize, usize)) {
+    let mut block = [0u8; WIDE_BLOCK_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+    let mut offset = 0;
+
+    while offset < num_pixels {
+        let block_pixels = (num_pixels - offset).min(WIDE_BLOCK_PIXELS);
+
+        unsafe { narrow_pixels(data.add(color_type.bytes_per_pixel() * offset), block.as_mut_ptr(), color_type, block_pixels, srgb_transfer); }
+        f(block.as_ptr(), offset, block_pixels);
+
+        offset += block_pixels;
+    }
+}
+
+/// `color_type`の`num_pixels`分のピクセルを[`WIDE_BLOCK_PIXELS`]ずつ`buf`に書き込みます。
+/// 
+/// `f`に渡したブロックと先頭からのピクセル位置、ピクセル数で[`narrow_color_type`]の色データタイプのピクセルを書き込ませ、
+/// `color_type`に変換します。
+#[inline(always)]
+pub(crate) unsafe fn for_each_wide_block(buf: *mut u8, color_type: ColorType, num_pixels: usize, srgb_transfer: bool, mut f: impl FnMut(*mut u8, usize, usize)) {
+    let mut block = [0u8; WIDE_BLOCK_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+    let mut offset = 0;
+
+    while offset < num_pixels {
+        let block_pixels = (num_pixels - offset).min(WIDE_BLOCK_PIXELS);
+
+        f(block.as_mut_ptr(), offset, block_pixels);
+        unsafe { widen_pixels(block.as_ptr(), buf.add(color_type.bytes_per_pixel() * offset), color_type, block_pixels, srgb_transfer); }
+
+        offset += block_pixels;
+    }
+}
+
+/// 16ビットの`value`を最も近い8ビットの値に丸めます。
+#[inline(always)]
+const fn u16_to_u8(value: u16) -> u8 {
+    // value / 257 の四捨五入
+    ((value as u32 * 255 + 32895) >> 16) as u8
+}
+
+/// 0.0 - 1.0 の`value`を 0 - 255 に丸めます。
+/// 
+/// 範囲外の値は 0 または 255 に、NaN は 0 になります。
+#[inline(always)]
+fn f32_to_u8(value: f32) -> u8 {
+    // 浮動小数点数から整数への変換は飽和し、NaN は 0 になる
+    (value * 255.0 + 0.5) as u8
+}
+
+/// 線形値の`value`にsRGBの伝達関数を適用し、最も近い8ビットの値に丸めます。
+/// 
+/// 丸めは伝達関数を適用した後のsRGBの値で行われ、[`SRGB_THRESHOLDS`]との比較のみで求めます。
+/// 範囲外の値は 0 または 255 に、NaN は 0 になります。
+#[inline(always)]
+fn linear_to_srgb(value: f32) -> u8 {
+    // NaN はどの境界値とも比較が偽になる
+    SRGB_THRESHOLDS.partition_point(|threshold| *threshold <= value) as u8
+}
+
+/// sRGBの8ビット値から線形値への変換表
+pub(crate) const SRGB_TO_LINEAR: [f32; 256] = [
+    0.0, 0.000303527, 0.000607054, 0.000910581, 0.001214108, 0.001517635, 0.001821162, 0.0021246888,
+    0.002428216, 0.0027317428, 0.00303527, 0.0033465358, 0.0036765074, 0.004024717, 0.004391442, 0.0047769533,
+    0.0051815165, 0.0056053917, 0.006048833, 0.0065120906, 0.00699541, 0.007499032, 0.008023193, 0.008568126,
+    0.009134059, 0.009721218, 0.010329823, 0.010960094, 0.011612245, 0.012286488, 0.0129830325, 0.013702083,
+    0.014443844, 0.015208514, 0.015996294, 0.016807375, 0.017641954, 0.01850022, 0.019382361, 0.020288562,
+    0.02121901, 0.022173885, 0.023153367, 0.024157632, 0.02518686, 0.026241222, 0.027320892, 0.02842604,
+    0.029556835, 0.030713445, 0.031896032, 0.033104766, 0.034339808, 0.035601314, 0.03688945, 0.038204372,
+    0.039546236, 0.0409152, 0.04231141, 0.04373503, 0.045186203, 0.046665087, 0.048171826, 0.049706567,
+    0.051269457, 0.052860647, 0.054480277, 0.05612849, 0.05780543, 0.059511237, 0.061246052, 0.063010015,
+    0.064803265, 0.06662594, 0.06847817, 0.070360094, 0.07227185, 0.07421357, 0.07618538, 0.07818742,
+    0.08021982, 0.08228271, 0.08437621, 0.08650046, 0.08865558, 0.09084171, 0.093058966, 0.09530747,
+    0.09758735, 0.099898726, 0.10224173, 0.104616486, 0.107023105, 0.10946171, 0.11193243, 0.114435375,
+    0.116970666, 0.11953843, 0.122138776, 0.12477182, 0.12743768, 0.13013647, 0.13286832, 0.13563333,
+    0.13843161, 0.14126329, 0.14412847, 0.14702727, 0.14995979, 0.15292615, 0.15592647, 0.15896083,
+    0.16202937, 0.1651322, 0.1682694, 0.17144111, 0.1746474, 0.17788842, 0.18116425, 0.18447499,
+    0.18782078, 0.19120169, 0.19461784, 0.19806932, 0.20155625, 0.20507874, 0.20863687, 0.21223076,
+    0.2158605, 0.2195262, 0.22322796, 0.22696587, 0.23074006, 0.23455058, 0.23839757, 0.24228112,
+    0.24620132, 0.25015828, 0.2541521, 0.25818285, 0.26225066, 0.2663556, 0.2704978, 0.2746773,
+    0.27889428, 0.28314874, 0.28744084, 0.29177064, 0.29613826, 0.30054379, 0.3049873, 0.30946892,
+    0.31398872, 0.31854677, 0.3231432, 0.3277781, 0.33245152, 0.33716363, 0.34191442, 0.34670407,
+    0.3515326, 0.35640013, 0.3613068, 0.3662526, 0.3712377, 0.37626213, 0.38132602, 0.38642943,
+    0.39157248, 0.39675522, 0.40197778, 0.4072402, 0.4125426, 0.41788507, 0.42326766, 0.4286905,
+    0.43415365, 0.43965718, 0.4452012, 0.4507858, 0.45641103, 0.462077, 0.4677838, 0.47353148,
+    0.47932017, 0.48514995, 0.49102086, 0.49693298, 0.5028865, 0.50888133, 0.5149177, 0.52099556,
+    0.5271151, 0.5332764, 0.5394795, 0.54572445, 0.55201143, 0.5583404, 0.5647115, 0.57112485,
+    0.57758045, 0.58407843, 0.59061885, 0.59720176, 0.60382736, 0.61049557, 0.6172066, 0.6239604,
+    0.63075715, 0.63759685, 0.6444797, 0.65140563, 0.65837485, 0.6653873, 0.67244315, 0.6795425,
+    0.6866853, 0.69387174, 0.7011019, 0.70837575, 0.7156935, 0.7230551, 0.73046076, 0.7379104,
+    0.7454042, 0.7529422, 0.7605245, 0.76815116, 0.7758222, 0.7835378, 0.7912979, 0.7991027,
+    0.80695224, 0.8148466, 0.82278574, 0.8307699, 0.838799, 0.8468732, 0.8549926, 0.8631572,
+    0.8713671, 0.8796224, 0.8879231, 0.8962694, 0.9046612, 0.91309863, 0.92158186, 0.9301109,
+    0.9386857, 0.9473065, 0.9559733, 0.9646863, 0.9734453, 0.9822506, 0.9911021, 1.0,
+];
+
+/// 線形値からsRGBの8ビット値への変換に使用する境界値
+///
+/// `i`番目はsRGBの値`(i + 0.5) / 255`（`i`と`i + 1`の中間）に逆伝達関数を適用した線形値で、この値以上の場合は`i + 1`以上になります。
+/// 線形値の中間ではないため、sRGBの値で最も近い8ビットの値に丸められます。
+pub(crate) const SRGB_THRESHOLDS: [f32; 255] = [
+    0.0001517635, 0.0004552905, 0.0007588175, 0.0010623444, 0.0013658714, 0.0016693984, 0.0019729254, 0.0022764525,
+    0.0025799794, 0.0028835062, 0.0031883009, 0.0035092593, 0.003848315, 0.004205748, 0.004581833, 0.0049768374,
+    0.005391024, 0.0058246506, 0.0062779696, 0.0067512277, 0.0072446684, 0.0077585303, 0.0082930485, 0.008848453,
+    0.0094249705, 0.010022826, 0.010642237, 0.011283421, 0.0119465925, 0.01263196, 0.013339732, 0.014070112,
+    0.014823303, 0.015599503, 0.01639891, 0.017221715, 0.018068114, 0.018938294, 0.019832443, 0.020750744,
+    0.021693382, 0.022660539, 0.02365239, 0.024669115, 0.025710888, 0.026777882, 0.02787027, 0.02898822,
+    0.030131903, 0.03130148, 0.032497123, 0.03371899, 0.034967244, 0.036242045, 0.037543554, 0.038871925,
+    0.04022732, 0.041609887, 0.043019786, 0.044457164, 0.04592217, 0.047414962, 0.048935685, 0.050484486,
+    0.052061506, 0.053666897, 0.055300802, 0.05696336, 0.058654718, 0.060375012, 0.062124383, 0.063902974,
+    0.06571092, 0.06754835, 0.06941541, 0.071312234, 0.073238954, 0.07519571, 0.07718261, 0.07919982,
+    0.08124744, 0.083325624, 0.08543449, 0.087574154, 0.08974477, 0.09194644, 0.0941793, 0.096443474,
+    0.098739095, 0.10106627, 0.10342513, 0.105815805, 0.1082384, 0.110693045, 0.11317986, 0.11569897,
+    0.11825048, 0.12083452, 0.1234512, 0.12610064, 0.12878296, 0.13149826, 0.13424668, 0.1370283,
+    0.13984327, 0.14269169, 0.14557366, 0.14848931, 0.15143873, 0.15442206, 0.15743938, 0.16049083,
+    0.1635765, 0.16669649, 0.16985093, 0.17303991, 0.17626357, 0.17952198, 0.18281525, 0.1861435,
+    0.18950683, 0.19290535, 0.19633915, 0.19980834, 0.20331304, 0.20685335, 0.21042934, 0.21404114,
+    0.21768884, 0.22137256, 0.2250924, 0.22884843, 0.23264076, 0.2364695, 0.24033478, 0.24423663,
+    0.2481752, 0.25215057, 0.25616285, 0.26021212, 0.26429847, 0.26842204, 0.2725829, 0.2767811,
+    0.2810168, 0.2852901, 0.28960103, 0.29394972, 0.2983363, 0.3027608, 0.30722335, 0.31172404,
+    0.31626296, 0.32084018, 0.32545584, 0.33010998, 0.33480275, 0.33953416, 0.34430438, 0.34911346,
+    0.3539615, 0.35884857, 0.36377478, 0.36874023, 0.37374496, 0.37878913, 0.38387278, 0.388996,
+    0.3941589, 0.39936152, 0.40460402, 0.40988642, 0.41520882, 0.42057136, 0.42597404, 0.43141702,
+    0.43690035, 0.44242412, 0.44798842, 0.4535933, 0.45923892, 0.4649253, 0.47065252, 0.4764207,
+    0.48222992, 0.48808023, 0.49397177, 0.49990454, 0.5058787, 0.5118943, 0.5179514, 0.5240501,
+    0.5301905, 0.5363727, 0.54259676, 0.5488627, 0.55517066, 0.5615207, 0.5679129, 0.5743473,
+    0.58082414, 0.58734334, 0.593905, 0.6005092, 0.6071561, 0.6138457, 0.6205781, 0.62735337,
+    0.6341716, 0.6410329, 0.64793724, 0.6548848, 0.66187567, 0.6689098, 0.67598736, 0.68310845,
+    0.6902731, 0.69748133, 0.7047334, 0.71202916, 0.7193688, 0.72675246, 0.73418003, 0.7416518,
+    0.7491677, 0.7567278, 0.7643323, 0.7719811, 0.7796744, 0.7874123, 0.79519475, 0.8030219,
+    0.81089383, 0.8188105, 0.8267722, 0.8347788, 0.8428305, 0.8509273, 0.8590692, 0.8672565,
+    0.87548906, 0.88376707, 0.89209056, 0.9004596, 0.9088742, 0.91733456, 0.9258406, 0.9343926,
+    0.94299036, 0.95163417, 0.96032405, 0.96906, 0.97784215, 0.98667055, 0.99554527,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 線形値にsRGBの伝達関数を適用し、0.0 - 255.0 の値を返します。
+    fn srgb(value: f64) -> f64 {
+        let value = if value <= 0.0031308 { value * 12.92 } else { 1.055 * value.powf(1.0 / 2.4) - 0.055 };
+        value * 255.0
+    }
+
+    #[test]
+    fn linear_to_srgb_test() {
+        for (i, &threshold) in SRGB_THRESHOLDS.iter().enumerate() {
+            // 境界値はsRGBの値の中間に相当する
+            assert!((srgb(threshold as f64) - (i as f64 + 0.5)).abs() < 1e-4, "{i}");
+
+            // 境界値の直前と境界値で丸め先が切り替わる
+            assert_eq!(linear_to_srgb(f32::from_bits(threshold.to_bits() - 1)), i as u8);
+            assert_eq!(linear_to_srgb(threshold), i as u8 + 1);
+        }
+
+        // 浮動小数点数の伝達関数を丸めた値と一致する（中間との差が f32 の精度未満の場合を除く）
+        for i in 0..=1 << 20 {
+            let value = i as f32 / (1 << 20) as f32;
+            let expected = srgb(value as f64);
+
+            if (expected.fract() - 0.5).abs() > 1e-4 {
+                assert_eq!(linear_to_srgb(value), expected.round() as u8, "{value}");
+            }
+        }
+
+        assert_eq!(linear_to_srgb(-1.0), 0);
+        assert_eq!(linear_to_srgb(2.0), 255);
+        assert_eq!(linear_to_srgb(f32::NAN), 0);
+    }
+
+    #[test]
+    fn srgb_to_linear_test() {
+        for (i, &value) in SRGB_TO_LINEAR.iter().enumerate() {
+            assert!((srgb(value as f64) - i as f64).abs() < 1e-3, "{i}");
+            assert_eq!(linear_to_srgb(value), i as u8);
+        }
+    }
+}
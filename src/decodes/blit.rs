@@ -0,0 +1,131 @@
+use crate::common::color::ColorType;
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::PIXEL_BYTES;
+use crate::common::spec::ImageSpec;
+use crate::decodes::{decode_header, logic};
+use crate::decodes::options::DecodeOptions;
+use crate::error::{Error, Result};
+
+/// `data`からLimg形式データをデコードし、1行あたり`dst_stride`バイトの`dst`バッファの`(x, y)`ピクセル位置に透明色以外のピクセルを書き込みます。
+///
+/// `dst`の幅は（`dst_stride` / 色バイト数）ピクセル、高さは1行分全体が収まる行数として扱い、
+/// はみ出す部分は書き込まれません。`x`と`y`には負の値も指定できます。
+///
+/// 透明色が指定されていない画像の場合、すべてのピクセルが書き込まれます。
+///
+/// エラーではなかった場合、`ImageSpec`を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+///
+/// `dst_stride`が色バイト数未満の場合、`Error::InvalidStride`を返します。
+///
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{blit, ColorType};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let sprite = std::fs::read("sprite.limg")?;
+///
+/// // 320x240のRGB565フレームバッファ
+/// let stride = ColorType::Rgb565.bytes_per_pixel() * 320;
+/// let mut framebuffer = vec![0u8; stride * 240];
+///
+/// // 左上にはみ出した位置に描画
+/// blit(&sprite, &mut framebuffer, stride, -8, -8, ColorType::Rgb565)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn blit(data: &[u8], dst: &mut [u8], dst_stride: usize, x: i32, y: i32, color_type: ColorType) -> Result<ImageSpec> {
+    blit_with_options(data, dst, dst_stride, x, y, color_type, &DecodeOptions::new())
+}
+
+/// `data`から`options`を使用してLimg形式データをデコードし、1行あたり`dst_stride`バイトの`dst`バッファの`(x, y)`ピクセル位置に透明色以外のピクセルを書き込みます。
+///
+/// `dst`の幅は（`dst_stride` / 色バイト数）ピクセル、高さは1行分全体が収まる行数として扱い、
+/// はみ出す部分は書き込まれません。`x`と`y`には負の値も指定できます。
+///
+/// 透明色が指定されていない画像の場合、すべてのピクセルが書き込まれます。
+/// 順序ディザリングのしきい値は`dst`上のピクセル位置で決まります。
+///
+/// エラーではなかった場合、`ImageSpec`を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+///
+/// `dst_stride`が色バイト数未満の場合、`Error::InvalidStride`を返します。
+///
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{blit_with_options, ColorType, DecodeOptions, Dither};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let sprite = std::fs::read("sprite.limg")?;
+///
+/// // 320x240のRGB332フレームバッファ
+/// let stride = 320;
+/// let mut framebuffer = vec![0u8; stride * 240];
+///
+/// // ディザリングしながら描画
+/// let options = DecodeOptions { dither: Dither::Bayer4x4, ..DecodeOptions::new() };
+/// blit_with_options(&sprite, &mut framebuffer, stride, 100, 50, ColorType::Rgb332, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn blit_with_options(data: &[u8], dst: &mut [u8], dst_stride: usize, x: i32, y: i32, color_type: ColorType, options: &DecodeOptions) -> Result<ImageSpec> {
+    if !color_type.is_byte_addressable() {
+        return Err(Error::UnsupportedColorType);
+    }
+
+    let spec = decode_header(data)?;
+
+    if data.len() < HEADER_SIZE + PIXEL_BYTES * spec.num_pixels() {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    if dst_stride < bytes_per_pixel {
+        return Err(Error::InvalidStride);
+    }
+
+    let dst_width = (dst_stride / bytes_per_pixel) as i64;
+    let dst_height = ((dst.len() + dst_stride - bytes_per_pixel * dst_width as usize) / dst_stride) as i64;
+
+    // 描画先の範囲でクリッピング
+    let left = (x as i64).max(0);
+    let top = (y as i64).max(0);
+    let right = (x as i64 + spec.width as i64).min(dst_width);
+    let bottom = (y as i64 + spec.height as i64).min(dst_height);
+
+    if left >= right || top >= bottom {
+        return Ok(spec);
+    }
+
+    let width = (right - left) as usize;
+    let src_x = (left - x as i64) as usize;
+    let src_y = (top - y as i64) as usize;
+
+    for row in 0..(bottom - top) as usize {
+        unsafe {
+            let src = data.as_ptr().add(HEADER_SIZE + PIXEL_BYTES * ((src_y + row) * spec.width as usize + src_x));
+            let buf = dst.as_mut_ptr().add(dst_stride * (top as usize + row) + bytes_per_pixel * left as usize);
+            logic::blit_pixels_logic(src, buf, &spec, options, color_type, width, left as usize, top as usize + row);
+        }
+    }
+
+    Ok(spec)
+}
@@ -0,0 +1,224 @@
+use crate::common::color::ColorType;
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::PIXEL_BYTES;
+use crate::common::spec::ImageSpec;
+use crate::decodes::{decode_header, logic};
+use crate::decodes::options::DecodeOptions;
+use crate::encodes::options::Dither;
+use crate::error::{Error, Result};
+
+/// Limg形式データを出力バッファの大きさごとに分割してデコードするデコーダ
+///
+/// [`decode_next`](ChunkedDecoder::decode_next)を呼び出すたびに、
+/// 出力バッファに収まるだけの続きのピクセルをデコードします。
+/// 2つのバッファを交互に使用すれば、片方を転送している間にもう片方へデコードできます。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{ChunkedDecoder, ColorType, ImageSpec, encode, encoded_size};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(10, 10);
+/// let mut data = vec![0u8; encoded_size(&spec)];
+/// encode(&[0; 300], &mut data, &spec, ColorType::Rgb888)?;
+///
+/// let mut buffers = [[0u8; 64]; 2];
+/// let mut decoder = ChunkedDecoder::new(&data, ColorType::Rgb565)?;
+///
+/// let mut index = 0;
+/// while !decoder.is_finished() {
+///     let written_size = decoder.decode_next(&mut buffers[index])?;
+///     // buffers[index][..written_size] を転送
+///     index ^= 1;
+/// }
+///
+/// assert_eq!(decoder.decoded_pixels(), 100);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkedDecoder<'a> {
+    data: &'a [u8],
+    spec: ImageSpec,
+    color_type: ColorType,
+    options: DecodeOptions,
+    position: usize,
+}
+
+impl<'a> ChunkedDecoder<'a> {
+    /// ヘッダを含むLimg形式データと`color_type`から`ChunkedDecoder`を作成します。
+    ///
+    /// # Errors
+    ///
+    /// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+    ///
+    /// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+    ///
+    /// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+    #[inline]
+    pub fn new(data: &'a [u8], color_type: ColorType) -> Result<Self> {
+        Self::new_with_options(data, color_type, &DecodeOptions::new())
+    }
+
+    /// ヘッダを含むLimg形式データと`color_type`、`options`から`ChunkedDecoder`を作成します。
+    ///
+    /// 順序ディザリングのしきい値は、分割の位置にかかわらず画像上のピクセル位置で決まります。
+    ///
+    /// # Errors
+    ///
+    /// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+    ///
+    /// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+    ///
+    /// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use limg_core::{ChunkedDecoder, ColorType, DecodeOptions, Dither, ImageSpec, encode, encoded_size};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let spec = ImageSpec::new(10, 10);
+    /// let mut data = vec![0u8; encoded_size(&spec)];
+    /// encode(&[100; 300], &mut data, &spec, ColorType::Rgb888)?;
+    ///
+    /// let options = DecodeOptions { dither: Dither::Bayer4x4, ..DecodeOptions::new() };
+    /// let mut decoder = ChunkedDecoder::new_with_options(&data, ColorType::Rgb332, &options)?;
+    ///
+    /// let mut buf = [0u8; 16];
+    /// while !decoder.is_finished() {
+    ///     decoder.decode_next(&mut buf)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn new_with_options(data: &'a [u8], color_type: ColorType, options: &DecodeOptions) -> Result<Self> {
+        let spec = decode_header(data)?;
+
+        let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
+        Self::from_data_with_options(data, spec, color_type, options)
+    }
+
+    /// ヘッダを除いた色データと`spec`、`color_type`から`ChunkedDecoder`を作成します。
+    ///
+    /// # Errors
+    ///
+    /// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+    ///
+    /// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+    #[inline]
+    pub fn from_data(data: &'a [u8], spec: ImageSpec, color_type: ColorType) -> Result<Self> {
+        Self::from_data_with_options(data, spec, color_type, &DecodeOptions::new())
+    }
+
+    /// ヘッダを除いた色データと`spec`、`color_type`、`options`から`ChunkedDecoder`を作成します。
+    ///
+    /// 順序ディザリングのしきい値は、分割の位置にかかわらず画像上のピクセル位置で決まります。
+    ///
+    /// # Errors
+    ///
+    /// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+    ///
+    /// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+    #[inline]
+    pub fn from_data_with_options(data: &'a [u8], spec: ImageSpec, color_type: ColorType, options: &DecodeOptions) -> Result<Self> {
+        if !color_type.is_byte_addressable() {
+            return Err(Error::UnsupportedColorType);
+        }
+
+        let data_size = PIXEL_BYTES * spec.num_pixels();
+
+        if data.len() < data_size {
+            return Err(Error::InputBufferTooSmall);
+        }
+
+        let data = unsafe { data.get_unchecked(..data_size) };
+
+        Ok(Self { data, spec, color_type, options: *options, position: 0 })
+    }
+
+    /// 画像の`ImageSpec`を返します。
+    #[inline(always)]
+    pub const fn spec(&self) -> &ImageSpec {
+        &self.spec
+    }
+
+    /// デコード後の色データタイプを返します。
+    #[inline(always)]
+    pub const fn color_type(&self) -> ColorType {
+        self.color_type
+    }
+
+    /// デコード済みのピクセル数を返します。
+    #[inline(always)]
+    pub const fn decoded_pixels(&self) -> usize {
+        self.position
+    }
+
+    /// 未デコードのピクセル数を返します。
+    #[inline(always)]
+    pub const fn remaining_pixels(&self) -> usize {
+        self.spec.num_pixels() - self.position
+    }
+
+    /// すべてのピクセルのデコードが完了しているかどうかを返します。
+    #[inline(always)]
+    pub const fn is_finished(&self) -> bool {
+        self.remaining_pixels() == 0
+    }
+
+    /// 続きのピクセルを`buf`に収まるだけデコードします。
+    ///
+    /// エラーではなかった場合、書き込まれたバイト数を返します。
+    /// すべてのピクセルのデコードが完了している場合は 0 を返します。
+    ///
+    /// # Errors
+    ///
+    /// 未デコードのピクセルがあり、`buf`の長さが色バイト数未満の場合、`Error::OutputBufferTooSmall`を返します。
+    #[inline]
+    pub fn decode_next(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let remaining_pixels = self.remaining_pixels();
+
+        if remaining_pixels == 0 {
+            return Ok(0);
+        }
+
+        let num_pixels = (buf.len() / self.color_type.bytes_per_pixel()).min(remaining_pixels);
+
+        if num_pixels == 0 {
+            return Err(Error::OutputBufferTooSmall);
+        }
+
+        let bytes_per_pixel = self.color_type.bytes_per_pixel();
+
+        if self.options.dither == Dither::None {
+            unsafe {
+                let data = self.data.as_ptr().add(PIXEL_BYTES * self.position);
+                logic::decode_pixels_logic(data, buf.as_mut_ptr(), &self.spec, &self.options, self.color_type, num_pixels);
+            }
+
+            self.position += num_pixels;
+        } else {
+            // ディザリングはピクセル位置に依存するため行の境界で分けてデコードする
+            let width = self.spec.width as usize;
+            let end = self.position + num_pixels;
+            let mut buf = buf.as_mut_ptr();
+
+            while self.position < end {
+                let (x, y) = (self.position % width, self.position / width);
+                let row_pixels = (width - x).min(end - self.position);
+
+                unsafe {
+                    let data = self.data.as_ptr().add(PIXEL_BYTES * self.position);
+                    buf = buf.add(logic::decode_row_logic(data, buf, &self.spec, &self.options, self.color_type, row_pixels, x, y));
+                }
+
+                self.position += row_pixels;
+            }
+        }
+
+        Ok(bytes_per_pixel * num_pixels)
+    }
+}
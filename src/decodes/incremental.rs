@@ -0,0 +1,226 @@
+use crate::common::color::ColorType;
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::PIXEL_BYTES;
+use crate::common::spec::ImageSpec;
+use crate::decodes::{decode_header, logic};
+use crate::decodes::options::DecodeOptions;
+use crate::error::{Error, Result};
+
+/// 任意の長さに分割されて届くLimg形式データを逐次デコードするデコーダ
+///
+/// [`feed`](IncrementalDecoder::feed)にデータを渡すと、ヘッダを読み取った後、
+/// 1行分のピクセルが揃うたびにデコード済みの行を返します。
+/// 分割位置がピクセルの途中であっても、端数のバイトは次の呼び出しに持ち越されます。
+///
+/// 出力バッファは1行分（色バイト数 * 幅）以上の長さが必要です。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{ColorType, ImageSpec, IncrementalDecoder, encode, encoded_size};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(2, 2);
+/// let mut data = vec![0u8; encoded_size(&spec)];
+/// encode(&[255; 12], &mut data, &spec, ColorType::Rgb888)?;
+///
+/// let mut row_buf = [0u8; 6];
+/// let mut decoder = IncrementalDecoder::new(&mut row_buf, ColorType::Rgb888);
+///
+/// for chunk in data.chunks(5) {
+///     decoder.feed(chunk, |y, row| {
+///         assert!(y < 2);
+///         assert_eq!(row, [255; 6]);
+///     })?;
+/// }
+///
+/// assert!(decoder.is_finished());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct IncrementalDecoder<'a> {
+    buf: &'a mut [u8],
+    color_type: ColorType,
+    options: DecodeOptions,
+    header: [u8; HEADER_SIZE],
+    header_len: usize,
+    spec: Option<ImageSpec>,
+    pending: [u8; PIXEL_BYTES],
+    pending_len: usize,
+    x: usize,
+    y: u16,
+}
+
+impl<'a> IncrementalDecoder<'a> {
+    /// 1行分の出力バッファ`buf`と`color_type`を指定して`IncrementalDecoder`を作成します。
+    #[inline]
+    pub const fn new(buf: &'a mut [u8], color_type: ColorType) -> Self {
+        Self::new_with_options(buf, color_type, &DecodeOptions::new())
+    }
+
+    /// 1行分の出力バッファ`buf`と`color_type`、`options`を指定して`IncrementalDecoder`を作成します。
+    ///
+    /// 順序ディザリングのしきい値は、データの分割位置にかかわらず画像上のピクセル位置で決まります。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use limg_core::{ColorType, DecodeOptions, Dither, ImageSpec, IncrementalDecoder, encode, encoded_size};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let spec = ImageSpec::new(4, 4);
+    /// let mut data = vec![0u8; encoded_size(&spec)];
+    /// encode(&[100; 48], &mut data, &spec, ColorType::Rgb888)?;
+    ///
+    /// let options = DecodeOptions { dither: Dither::Bayer4x4, ..DecodeOptions::new() };
+    /// let mut row_buf = [0u8; 4];
+    /// let mut decoder = IncrementalDecoder::new_with_options(&mut row_buf, ColorType::Rgb332, &options);
+    ///
+    /// for chunk in data.chunks(5) {
+    ///     decoder.feed(chunk, |_, _| {})?;
+    /// }
+    ///
+    /// assert!(decoder.is_finished());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn new_with_options(buf: &'a mut [u8], color_type: ColorType, options: &DecodeOptions) -> Self {
+        Self {
+            buf,
+            color_type,
+            options: *options,
+            header: [0; HEADER_SIZE],
+            header_len: 0,
+            spec: None,
+            pending: [0; PIXEL_BYTES],
+            pending_len: 0,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    /// デコード後の色データタイプを返します。
+    #[inline(always)]
+    pub const fn color_type(&self) -> ColorType {
+        self.color_type
+    }
+
+    /// ヘッダが読み取り済みの場合、`ImageSpec`を返します。
+    #[inline(always)]
+    pub const fn spec(&self) -> Option<&ImageSpec> {
+        self.spec.as_ref()
+    }
+
+    /// デコードが完了した行数を返します。
+    #[inline(always)]
+    pub const fn decoded_rows(&self) -> u16 {
+        self.y
+    }
+
+    /// すべての行のデコードが完了しているかどうかを返します。
+    #[inline]
+    pub const fn is_finished(&self) -> bool {
+        match self.spec {
+            Some(spec) => self.y == spec.height,
+            None => false,
+        }
+    }
+
+    /// `data`を入力し、完成した行ごとに`f`を行番号とデコード済みの行データで呼び出します。
+    ///
+    /// エラーではなかった場合、消費したバイト数を返します。
+    /// すべての行のデコードが完了した後のデータは消費されません。
+    ///
+    /// # Errors
+    ///
+    /// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+    ///
+    /// 出力バッファの長さが（色バイト数 * 幅）未満の場合、`Error::OutputBufferTooSmall`を返します。
+    ///
+    /// 色データタイプが`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+    ///
+    /// エラーが発生した後のデコーダは使用できません。
+    pub fn feed<F>(&mut self, mut data: &[u8], mut f: F) -> Result<usize>
+    where
+        F: FnMut(u16, &[u8]),
+    {
+        if !self.color_type.is_byte_addressable() {
+            return Err(Error::UnsupportedColorType);
+        }
+
+        let mut consumed = 0;
+
+        let spec = match self.spec {
+            Some(spec) => spec,
+            None => {
+                let len = (HEADER_SIZE - self.header_len).min(data.len());
+                self.header[self.header_len..self.header_len + len].copy_from_slice(&data[..len]);
+                self.header_len += len;
+
+                consumed += len;
+                data = &data[len..];
+
+                if self.header_len < HEADER_SIZE {
+                    return Ok(consumed);
+                }
+
+                let spec = decode_header(&self.header)?;
+
+                if self.buf.len() < self.color_type.bytes_per_pixel() * spec.width as usize {
+                    return Err(Error::OutputBufferTooSmall);
+                }
+
+                self.spec = Some(spec);
+                spec
+            }
+        };
+
+        let bytes_per_pixel = self.color_type.bytes_per_pixel();
+        let width = spec.width as usize;
+
+        while self.y < spec.height && !data.is_empty() {
+            let buf = unsafe { self.buf.as_mut_ptr().add(bytes_per_pixel * self.x) };
+
+            if self.pending_len != 0 {
+                // 前回の端数バイトとあわせて1ピクセルをデコード
+                self.pending[1] = data[0];
+                self.pending_len = 0;
+
+                unsafe { logic::decode_row_logic(self.pending.as_ptr(), buf, &spec, &self.options, self.color_type, 1, self.x, self.y as usize); }
+
+                self.x += 1;
+                consumed += 1;
+                data = &data[1..];
+            } else {
+                let num_pixels = (width - self.x).min(data.len() / PIXEL_BYTES);
+
+                if num_pixels == 0 {
+                    // 1バイトだけ残った場合は次回に持ち越す
+                    self.pending[0] = data[0];
+                    self.pending_len = 1;
+
+                    consumed += 1;
+                    break;
+                }
+
+                // 行をまたがないので、ディザリングのしきい値は(x, y)から続く
+                unsafe { logic::decode_row_logic(data.as_ptr(), buf, &spec, &self.options, self.color_type, num_pixels, self.x, self.y as usize); }
+
+                self.x += num_pixels;
+                consumed += PIXEL_BYTES * num_pixels;
+                data = &data[PIXEL_BYTES * num_pixels..];
+            }
+
+            if self.x == width {
+                f(self.y, &self.buf[..bytes_per_pixel * width]);
+
+                self.x = 0;
+                self.y += 1;
+            }
+        }
+
+        Ok(consumed)
+    }
+}
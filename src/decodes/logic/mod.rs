@@ -3,45 +3,262 @@ mod scalar;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 mod x86_64;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-pub use x86_64::decode_logic;
+pub use x86_64::{decode_logic, decode_pixels_logic, decode_row_logic, decode_planes_logic, blit_pixels_logic, reverse_pixels};
 
 #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
-pub use scalar::decode_logic;
+pub use scalar::{decode_logic, decode_pixels_logic, decode_row_logic, decode_planes_logic, blit_pixels_logic, reverse_pixels};
+
+/// RGB332への減色で1行分の各ピクセルに加算する値
+///
+/// 各配列の`i`番目は行の`i`ピクセル目に加算する値で、8ピクセル周期で繰り返します。
+/// SIMDで16ピクセル分を読み込めるように、16ピクセル分を格納しています。
+#[derive(Clone, Copy)]
+pub struct Rgb332DitherRow {
+    /// RとGに加算する値（0 - 31）
+    pub rg: [u16; 16],
+
+    /// Bに加算する値（0 - 63）
+    pub b: [u16; 16],
+}
+
+impl Rgb332DitherRow {
+    /// `dither`の`y`行目の`Rgb332DitherRow`を作成します。
+    pub const fn new(dither: crate::encodes::options::Dither, y: usize) -> Self {
+        use crate::encodes::options::{Dither, BAYER_4X4, BAYER_8X8};
+
+        let mut rg = [0; 16];
+        let mut b = [0; 16];
+
+        let mut i = 0;
+        while i < 16 {
+            // 減色で切り捨てられる幅（R, G: 32, B: 64）をしきい値で等分する
+            (rg[i], b[i]) = match dither {
+                Dither::None => (0, 0),
+                Dither::Bayer4x4 => {
+                    let threshold = BAYER_4X4[y % 4][i % 4] as u16;
+                    (threshold * 2, threshold * 4)
+                },
+                Dither::Bayer8x8 => {
+                    let threshold = BAYER_8X8[y % 8][i % 8] as u16;
+                    (threshold / 2, threshold)
+                },
+            };
+
+            i += 1;
+        }
+
+        Self { rg, b }
+    }
+
+    /// `n`ピクセル目から始まる`Rgb332DitherRow`を返します。
+    pub const fn shifted(&self, n: usize) -> Self {
+        let mut rg = [0; 16];
+        let mut b = [0; 16];
+
+        let mut i = 0;
+        while i < 16 {
+            rg[i] = self.rg[(i + n) % 8];
+            b[i] = self.b[(i + n) % 8];
+            i += 1;
+        }
+
+        Self { rg, b }
+    }
+}
 
 macro_rules! decode_logic_fn {
     ($(#[$attr:meta])*) => {
 
+        #[inline(always)]
+        pub unsafe fn decode_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::decodes::options::DecodeOptions, color_type: crate::common::color::ColorType) -> usize {
+            if color_type == crate::common::color::ColorType::Mono1 {
+                // 各行はバイト境界までパディングされるため1行ずつデコードする
+                return unsafe { crate::decodes::mono::decode_mono(data, buf, spec, options) };
+            }
+
+            if color_type == crate::common::color::ColorType::Rgb332 && options.dither != crate::encodes::options::Dither::None {
+                // ディザリングはピクセル位置に依存するため1行ずつデコードする
+                let width = spec.width as usize;
+
+                for y in 0..spec.height as usize {
+                    unsafe {
+                        let data = data.add(crate::common::pixel::PIXEL_BYTES * width * y);
+                        let buf = buf.add(color_type.bytes_per_pixel() * width * y);
+                        decode_row_logic(data, buf, spec, options, color_type, width, 0, y);
+                    }
+                }
+
+                return color_type.bytes_per_pixel() * spec.num_pixels();
+            }
+
+            unsafe { decode_pixels_logic(data, buf, spec, options, color_type, spec.num_pixels()) }
+        }
+
+        /// `spec`のエンディアンと透明色、`options`を使用して、`(x, y)`の位置から`num_pixels`分のピクセルをデコードします。
+        /// 
+        /// 順序ディザリングは`(x, y)`から始まる位置のしきい値で行われます。ピクセルは`y`行目からはみ出してはいけません。
+        #[inline(always)]
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn decode_row_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::decodes::options::DecodeOptions, color_type: crate::common::color::ColorType, num_pixels: usize, x: usize, y: usize) -> usize {
+            if color_type == crate::common::color::ColorType::Rgb332 && options.dither != crate::encodes::options::Dither::None {
+                let dither = crate::decodes::logic::Rgb332DitherRow::new(options.dither, y).shifted(x);
+
+                unsafe {
+                    match spec.pixel_endian {
+                        crate::common::spec::PixelEndian::Big => decode_to_rgb332_be(data, buf, num_pixels, &dither),
+                        crate::common::spec::PixelEndian::Little => decode_to_rgb332_le(data, buf, num_pixels, &dither),
+                    }
+                }
+
+                return color_type.bytes_per_pixel() * num_pixels;
+            }
+
+            unsafe { decode_pixels_logic(data, buf, spec, options, color_type, num_pixels) }
+        }
+
+        /// `spec`のエンディアンと透明色、`options`を使用して、`num_pixels`分のピクセルをデコードします。
         #[inline(never)]
         $(#[$attr])*
-        pub unsafe fn decode_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, color_type: crate::common::color::ColorType) -> usize {
-            let num_pixels = spec.num_pixels();
-        
+        pub unsafe fn decode_pixels_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::decodes::options::DecodeOptions, color_type: crate::common::color::ColorType, num_pixels: usize) -> usize {
+            if let Some(narrow_type) = crate::common::wide::narrow_color_type(color_type) {
+                // チャンネルあたり8ビットにデコードしてから変換する
+                unsafe {
+                    crate::common::wide::for_each_wide_block(buf, color_type, num_pixels, options.srgb_transfer, |block, offset, block_pixels| {
+                        decode_pixels_logic(data.add(crate::common::pixel::PIXEL_BYTES * offset), block, spec, options, narrow_type, block_pixels);
+                    });
+                }
+
+                return color_type.bytes_per_pixel() * num_pixels;
+            }
+
             unsafe {
                 match spec.pixel_endian {
                     crate::common::spec::PixelEndian::Big => {
                         match color_type {
-                            crate::common::color::ColorType::Rgb888 => decode_to_rgb888_be(data, buf, num_pixels),
+                            crate::common::color::ColorType::Rgb888 => decode_to_rgb888_be::<crate::common::color::Rgb>(data, buf, num_pixels),
                             crate::common::color::ColorType::Rgb565 => decode_to_rgb565_be(data, buf, num_pixels),
                             crate::common::color::ColorType::Rgba8888 => {
                                 if let Some(transparent_color) = spec.transparent_color {
-                                    decode_to_rgba8888_alpha_be(data, buf, transparent_color, num_pixels)
+                                    decode_to_rgba8888_alpha_be::<crate::common::color::Rgb>(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_rgba8888_be::<crate::common::color::Rgb>(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::Bgr888 => decode_to_rgb888_be::<crate::common::color::Bgr>(data, buf, num_pixels),
+                            crate::common::color::ColorType::Bgra8888 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_rgba8888_alpha_be::<crate::common::color::Bgr>(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_rgba8888_be::<crate::common::color::Bgr>(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::L8 => decode_to_l8_be(data, buf, num_pixels, options.luma),
+                            crate::common::color::ColorType::La88 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_la88_alpha_be(data, buf, transparent_color, num_pixels, options.luma)
+                                } else {
+                                    decode_to_la88_be(data, buf, num_pixels, options.luma)
+                                }
+                            },
+                            crate::common::color::ColorType::Argb8888 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_rgba8888_alpha_be::<crate::common::color::Argb>(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_rgba8888_be::<crate::common::color::Argb>(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::Xrgb8888 => decode_to_rgba8888_be::<crate::common::color::Argb>(data, buf, num_pixels),
+                            crate::common::color::ColorType::Rgb555 => decode_to_rgb555_be(data, buf, num_pixels),
+                            crate::common::color::ColorType::Argb1555 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_argb1555_alpha_be(data, buf, transparent_color, num_pixels)
                                 } else {
-                                    decode_to_rgba8888_be(data, buf, num_pixels)
+                                    decode_to_argb1555_be(data, buf, num_pixels)
                                 }
-                            }
+                            },
+                            crate::common::color::ColorType::Rgb444 => decode_to_rgb444_be(data, buf, num_pixels),
+                            crate::common::color::ColorType::Rgba4444 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_rgba4444_alpha_be(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_rgba4444_be(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::Rgb332 => decode_to_rgb332_be(data, buf, num_pixels, &crate::decodes::logic::Rgb332DitherRow::new(options.dither, 0)),
+                            crate::common::color::ColorType::Bgr565 => decode_to_bgr565_be(data, buf, num_pixels),
+                            // 格納順が出力と同じビッグエンディアンなのでそのまま複製する
+                            crate::common::color::ColorType::Rgb565Be => decode_from_rgb565_direct(data, buf, num_pixels),
+                            crate::common::color::ColorType::Bgr565Be => decode_to_bgr565be_be(data, buf, num_pixels),
+                            // チャンネルあたり8ビットからの変換で処理済み
+                            crate::common::color::ColorType::Rgb16 | crate::common::color::ColorType::Rgba16 |
+                            crate::common::color::ColorType::RgbF32 | crate::common::color::ColorType::RgbaF32 => unreachable!(),
+                            crate::common::color::ColorType::Mono1 => {
+                                return crate::decodes::mono::decode_mono_row(data, buf, spec, options, 0, num_pixels);
+                            },
                         }
                     },
                     crate::common::spec::PixelEndian::Little => {
                         match color_type {
-                            crate::common::color::ColorType::Rgb888 => decode_to_rgb888_le(data, buf, num_pixels),
+                            crate::common::color::ColorType::Rgb888 => decode_to_rgb888_le::<crate::common::color::Rgb>(data, buf, num_pixels),
                             crate::common::color::ColorType::Rgb565 => decode_to_rgb565_le(data, buf, num_pixels),
                             crate::common::color::ColorType::Rgba8888 => {
                                 if let Some(transparent_color) = spec.transparent_color {
-                                    decode_to_rgba8888_alpha_le(data, buf, transparent_color, num_pixels)
+                                    decode_to_rgba8888_alpha_le::<crate::common::color::Rgb>(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_rgba8888_le::<crate::common::color::Rgb>(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::Bgr888 => decode_to_rgb888_le::<crate::common::color::Bgr>(data, buf, num_pixels),
+                            crate::common::color::ColorType::Bgra8888 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_rgba8888_alpha_le::<crate::common::color::Bgr>(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_rgba8888_le::<crate::common::color::Bgr>(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::L8 => decode_to_l8_le(data, buf, num_pixels, options.luma),
+                            crate::common::color::ColorType::La88 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_la88_alpha_le(data, buf, transparent_color, num_pixels, options.luma)
+                                } else {
+                                    decode_to_la88_le(data, buf, num_pixels, options.luma)
+                                }
+                            },
+                            crate::common::color::ColorType::Argb8888 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_rgba8888_alpha_le::<crate::common::color::Argb>(data, buf, transparent_color, num_pixels)
                                 } else {
-                                    decode_to_rgba8888_le(data, buf, num_pixels)
+                                    decode_to_rgba8888_le::<crate::common::color::Argb>(data, buf, num_pixels)
                                 }
-                            }
+                            },
+                            crate::common::color::ColorType::Xrgb8888 => decode_to_rgba8888_le::<crate::common::color::Argb>(data, buf, num_pixels),
+                            crate::common::color::ColorType::Rgb555 => decode_to_rgb555_le(data, buf, num_pixels),
+                            crate::common::color::ColorType::Argb1555 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_argb1555_alpha_le(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_argb1555_le(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::Rgb444 => decode_to_rgb444_le(data, buf, num_pixels),
+                            crate::common::color::ColorType::Rgba4444 => {
+                                if let Some(transparent_color) = spec.transparent_color {
+                                    decode_to_rgba4444_alpha_le(data, buf, transparent_color, num_pixels)
+                                } else {
+                                    decode_to_rgba4444_le(data, buf, num_pixels)
+                                }
+                            },
+                            crate::common::color::ColorType::Rgb332 => decode_to_rgb332_le(data, buf, num_pixels, &crate::decodes::logic::Rgb332DitherRow::new(options.dither, 0)),
+                            crate::common::color::ColorType::Bgr565 => decode_to_bgr565_le(data, buf, num_pixels),
+                            // 格納順のリトルエンディアンからビッグエンディアンにバイトを入れ替える
+                            crate::common::color::ColorType::Rgb565Be => decode_from_rgb565_swap(data, buf, num_pixels),
+                            crate::common::color::ColorType::Bgr565Be => decode_to_bgr565be_le(data, buf, num_pixels),
+                            // チャンネルあたり8ビットからの変換で処理済み
+                            crate::common::color::ColorType::Rgb16 | crate::common::color::ColorType::Rgba16 |
+                            crate::common::color::ColorType::RgbF32 | crate::common::color::ColorType::RgbaF32 => unreachable!(),
+                            crate::common::color::ColorType::Mono1 => {
+                                return crate::decodes::mono::decode_mono_row(data, buf, spec, options, 0, num_pixels);
+                            },
                         }
                     },
                 }
@@ -49,11 +266,91 @@ macro_rules! decode_logic_fn {
             
             color_type.bytes_per_pixel() * num_pixels
         }
+
+        /// `spec`のエンディアンを使用して、`num_pixels`分のピクセルをR、G、Bの平面に分けてデコードします。
+        /// 
+        /// 透明色は考慮されません。
+        #[inline(never)]
+        $(#[$attr])*
+        pub unsafe fn decode_planes_logic(data: *const u8, r: *mut u8, g: *mut u8, b: *mut u8, spec: &crate::common::spec::ImageSpec, num_pixels: usize) {
+            unsafe {
+                match spec.pixel_endian {
+                    crate::common::spec::PixelEndian::Big => decode_to_planes_be(data, r, g, b, num_pixels),
+                    crate::common::spec::PixelEndian::Little => decode_to_planes_le(data, r, g, b, num_pixels),
+                }
+            }
+        }
     };
 }
 
 pub(crate) use decode_logic_fn;
 
+macro_rules! blit_logic_fn {
+    ($(#[$attr:meta])*) => {
+
+        /// `(x, y)`の位置から`num_pixels`分のピクセルをデコードし、`spec`の透明色ではないピクセルのみ`buf`に書き込みます。
+        /// 
+        /// 順序ディザリングは[`decode_row_logic`]と同じく`(x, y)`から始まる位置のしきい値で行われます。
+        #[inline(never)]
+        #[allow(clippy::too_many_arguments)]
+        $(#[$attr])*
+        pub unsafe fn blit_pixels_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::decodes::options::DecodeOptions, color_type: crate::common::color::ColorType, num_pixels: usize, x: usize, y: usize) {
+            const BLOCK_PIXELS: usize = 64;
+
+            let Some(transparent_color) = spec.transparent_color else {
+                unsafe { decode_row_logic(data, buf, spec, options, color_type, num_pixels, x, y); }
+                return;
+            };
+
+            // エンディアン補正前の色データと比較する
+            let transparent_color = match spec.pixel_endian {
+                crate::common::spec::PixelEndian::Big => transparent_color.to_be(),
+                crate::common::spec::PixelEndian::Little => transparent_color.to_le(),
+            };
+
+            let bytes_per_pixel = color_type.bytes_per_pixel();
+            let mut block = [0u8; BLOCK_PIXELS * crate::common::color::ColorType::RgbaF32.bytes_per_pixel()];
+
+            let mut data = data;
+            let mut buf = buf;
+            let mut remaining = num_pixels;
+
+            while remaining > 0 {
+                let block_pixels = remaining.min(BLOCK_PIXELS);
+
+                unsafe {
+                    decode_row_logic(data, block.as_mut_ptr(), spec, options, color_type, block_pixels, x + num_pixels - remaining, y);
+
+                    match color_type {
+                        crate::common::color::ColorType::Rgb888 | crate::common::color::ColorType::Bgr888 => blit_rgb888(data, block.as_ptr(), buf, transparent_color, block_pixels),
+                        crate::common::color::ColorType::Rgb565 | crate::common::color::ColorType::La88 |
+                        crate::common::color::ColorType::Rgb555 | crate::common::color::ColorType::Argb1555 |
+                        crate::common::color::ColorType::Rgb444 | crate::common::color::ColorType::Rgba4444 |
+                        crate::common::color::ColorType::Bgr565 | crate::common::color::ColorType::Rgb565Be |
+                        crate::common::color::ColorType::Bgr565Be => blit_rgb565(data, block.as_ptr(), buf, transparent_color, block_pixels),
+                        crate::common::color::ColorType::Rgba8888 | crate::common::color::ColorType::Bgra8888 |
+                        crate::common::color::ColorType::Argb8888 | crate::common::color::ColorType::Xrgb8888 => blit_rgba8888(data, block.as_ptr(), buf, transparent_color, block_pixels),
+                        crate::common::color::ColorType::L8 | crate::common::color::ColorType::Rgb332 => blit_l8(data, block.as_ptr(), buf, transparent_color, block_pixels),
+                        crate::common::color::ColorType::Rgb16 | crate::common::color::ColorType::Rgba16 |
+                        crate::common::color::ColorType::RgbF32 | crate::common::color::ColorType::RgbaF32 => {
+                            crate::decodes::logic::scalar::blit_masked(data, block.as_ptr(), buf, transparent_color, bytes_per_pixel, block_pixels)
+                        },
+                        // ピクセル位置を指定できないため呼び出し元で除外している
+                        crate::common::color::ColorType::Mono1 => unreachable!(),
+                    }
+
+                    data = data.add(crate::common::pixel::PIXEL_BYTES * block_pixels);
+                    buf = buf.add(bytes_per_pixel * block_pixels);
+                }
+
+                remaining -= block_pixels;
+            }
+        }
+    };
+}
+
+pub(crate) use blit_logic_fn;
+
 #[cfg(test)]
 mod tests {
     use crate::common::pixel::rgb_to_pixel;
@@ -1,6 +1,6 @@
-use crate::common::color::ColorType;
-use crate::common::pixel::{pixel_to_rgb, PIXEL_BYTES};
-use crate::decodes::logic::decode_logic_fn;
+use crate::common::color::{ChannelOrder, ColorType};
+use crate::common::pixel::{pixel_to_rgb, pixel_to_rgb444, pixel_to_rgb555, swap_rb, LumaCoefficients, PIXEL_BYTES, RGB555_A_BIT};
+use crate::decodes::logic::{decode_logic_fn, blit_logic_fn, Rgb332DitherRow};
 
 #[inline(always)]
 const fn pixel_to_alpha(pixel: u16, transparent_color: u16) -> u8 {
@@ -28,10 +28,60 @@ pub unsafe fn decode_from_rgb565_swap(data: *const u8, buf: *mut u8, num_pixels:
     }
 }
 
+#[inline(always)]
+pub unsafe fn reverse_pixels(data: *const u8, buf: *mut u8, num_pixels: usize) {
+    let mut data = unsafe { data.cast::<u16>().add(num_pixels) };
+    let mut buf = buf.cast::<u16>();
+
+    for _ in 0..num_pixels {
+        unsafe {
+            data = data.sub(1);
+
+            let pixel = data.read_unaligned();
+            buf.write_unaligned(pixel);
+
+            buf = buf.add(1);
+        }
+    }
+}
+
+#[inline(always)]
+pub unsafe fn blit_masked(data: *const u8, decoded: *const u8, buf: *mut u8, transparent_color: u16, bytes_per_pixel: usize, num_pixels: usize) {
+    let data = data.cast::<u16>();
+
+    for i in 0..num_pixels {
+        unsafe {
+            if data.add(i).read_unaligned() != transparent_color {
+                ::core::ptr::copy_nonoverlapping(decoded.add(bytes_per_pixel * i), buf.add(bytes_per_pixel * i), bytes_per_pixel);
+            }
+        }
+    }
+}
+
+#[inline(always)]
+pub unsafe fn blit_rgb888(data: *const u8, decoded: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    unsafe { blit_masked(data, decoded, buf, transparent_color, ColorType::Rgb888.bytes_per_pixel(), num_pixels); }
+}
+
+#[inline(always)]
+pub unsafe fn blit_rgb565(data: *const u8, decoded: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    unsafe { blit_masked(data, decoded, buf, transparent_color, ColorType::Rgb565.bytes_per_pixel(), num_pixels); }
+}
+
+#[inline(always)]
+pub unsafe fn blit_rgba8888(data: *const u8, decoded: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    unsafe { blit_masked(data, decoded, buf, transparent_color, ColorType::Rgba8888.bytes_per_pixel(), num_pixels); }
+}
+
+#[inline(always)]
+pub unsafe fn blit_l8(data: *const u8, decoded: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    unsafe { blit_masked(data, decoded, buf, transparent_color, ColorType::L8.bytes_per_pixel(), num_pixels); }
+}
+
 macro_rules! decode_endian {
-    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident, $rgba8888_alpha: ident) => {
+    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident, $rgba8888_alpha: ident, $l8: ident, $la88: ident, $la88_alpha: ident, $rgb555: ident, $argb1555: ident, $argb1555_alpha: ident, $rgb444: ident, $rgba4444: ident, $rgba4444_alpha: ident, $rgb332: ident, $bgr565: ident, $bgr565be: ident, $planes: ident) => {
         #[inline(always)]
-        pub unsafe fn $rgb888(data: *const u8, buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgb888<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize) {
             const COLOR_TYPE: ColorType = ColorType::Rgb888;
 
             let mut data = data.cast::<u16>();
@@ -40,7 +90,7 @@ macro_rules! decode_endian {
             for _ in 0..num_pixels {
                 unsafe {
                     let pixel = data.read_unaligned().$endian_fn();
-                    buf.write(pixel_to_rgb(pixel));
+                    buf.write(O::order(pixel_to_rgb(pixel)));
         
                     data = data.add(1);
                     buf = buf.add(1);
@@ -58,7 +108,7 @@ macro_rules! decode_endian {
         }
 
         #[inline(always)]
-        pub unsafe fn $rgba8888(data: *const u8, buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgba8888<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize) {
             const COLOR_TYPE: ColorType = ColorType::Rgba8888;
 
             let mut data = data.cast::<u16>();
@@ -67,8 +117,7 @@ macro_rules! decode_endian {
             for _ in 0..num_pixels {
                 unsafe {
                     let pixel = data.read_unaligned().$endian_fn();
-                    let rgb = pixel_to_rgb(pixel);
-                    buf.write([rgb[0], rgb[1], rgb[2], u8::MAX]);
+                    buf.write(O::pack_rgba(pixel_to_rgb(pixel), u8::MAX));
         
                     data = data.add(1);
                     buf = buf.add(1);
@@ -77,7 +126,7 @@ macro_rules! decode_endian {
         }
 
         #[inline(always)]
-        pub unsafe fn $rgba8888_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+        pub unsafe fn $rgba8888_alpha<O: ChannelOrder>(data: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
             const COLOR_TYPE: ColorType = ColorType::Rgba8888;
         
             let mut data = data.cast::<u16>();
@@ -86,19 +135,243 @@ macro_rules! decode_endian {
             for _ in 0..num_pixels {
                 unsafe {
                     let pixel = data.read_unaligned().$endian_fn();
-                    let rgb = pixel_to_rgb(pixel);
                     let alpha = pixel_to_alpha(pixel, transparent_color);
-                    buf.write([rgb[0], rgb[1], rgb[2], alpha]);
+                    buf.write(O::pack_rgba(pixel_to_rgb(pixel), alpha));
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $l8(data: *const u8, buf: *mut u8, num_pixels: usize, luma: LumaCoefficients) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf;
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    buf.write(luma.luma(pixel_to_rgb(pixel)));
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $la88(data: *const u8, buf: *mut u8, num_pixels: usize, luma: LumaCoefficients) {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    buf.write([luma.luma(pixel_to_rgb(pixel)), u8::MAX]);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $la88_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize, luma: LumaCoefficients) {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    let alpha = pixel_to_alpha(pixel, transparent_color);
+                    buf.write([luma.luma(pixel_to_rgb(pixel)), alpha]);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgb555(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    buf.write_unaligned(pixel_to_rgb555(pixel));
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $argb1555(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    buf.write_unaligned(pixel_to_rgb555(pixel) | RGB555_A_BIT);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $argb1555_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    let alpha = if pixel != transparent_color { RGB555_A_BIT } else { 0 };
+                    buf.write_unaligned(pixel_to_rgb555(pixel) | alpha);
         
                     data = data.add(1);
                     buf = buf.add(1);
                 }
             }
         }
+
+        #[inline(always)]
+        pub unsafe fn $rgb444(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let [r, g, b] = pixel_to_rgb444(data.read_unaligned().$endian_fn());
+                    buf.write_unaligned(((r as u16) << 8) | ((g as u16) << 4) | b as u16);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgba4444(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let [r, g, b] = pixel_to_rgb444(data.read_unaligned().$endian_fn());
+                    buf.write_unaligned(((r as u16) << 12) | ((g as u16) << 8) | ((b as u16) << 4) | 0x000F);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgba4444_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    let [r, g, b] = pixel_to_rgb444(pixel);
+                    let alpha = (pixel_to_alpha(pixel, transparent_color) >> 4) as u16;
+                    buf.write_unaligned(((r as u16) << 12) | ((g as u16) << 8) | ((b as u16) << 4) | alpha);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgb332(data: *const u8, buf: *mut u8, num_pixels: usize, dither: &Rgb332DitherRow) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf;
+        
+            for i in 0..num_pixels {
+                unsafe {
+                    let [r, g, b] = pixel_to_rgb(data.read_unaligned().$endian_fn());
+
+                    // しきい値を加算してから切り捨てる
+                    let r = r.saturating_add(dither.rg[i % 8] as u8) >> 5;
+                    let g = g.saturating_add(dither.rg[i % 8] as u8) >> 5;
+                    let b = b.saturating_add(dither.b[i % 8] as u8) >> 6;
+                    buf.write((r << 5) | (g << 2) | b);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $bgr565(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    buf.write_unaligned(swap_rb(pixel));
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $bgr565be(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = data.read_unaligned().$endian_fn();
+                    buf.write_unaligned(swap_rb(pixel).to_be());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $planes(data: *const u8, mut r: *mut u8, mut g: *mut u8, mut b: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+
+            for _ in 0..num_pixels {
+                unsafe {
+                    let [r_value, g_value, b_value] = pixel_to_rgb(data.read_unaligned().$endian_fn());
+                    r.write(r_value);
+                    g.write(g_value);
+                    b.write(b_value);
+
+                    data = data.add(1);
+                    r = r.add(1);
+                    g = g.add(1);
+                    b = b.add(1);
+                }
+            }
+        }
     };
 }
 
 decode_logic_fn!();
+blit_logic_fn!();
 
 decode_endian!(
     "big",
@@ -106,7 +379,20 @@ decode_endian!(
     decode_to_rgb888_be,
     decode_to_rgb565_be,
     decode_to_rgba8888_be,
-    decode_to_rgba8888_alpha_be
+    decode_to_rgba8888_alpha_be,
+    decode_to_l8_be,
+    decode_to_la88_be,
+    decode_to_la88_alpha_be,
+    decode_to_rgb555_be,
+    decode_to_argb1555_be,
+    decode_to_argb1555_alpha_be,
+    decode_to_rgb444_be,
+    decode_to_rgba4444_be,
+    decode_to_rgba4444_alpha_be,
+    decode_to_rgb332_be,
+    decode_to_bgr565_be,
+    decode_to_bgr565be_be,
+    decode_to_planes_be
 );
 
 decode_endian!(
@@ -115,5 +401,18 @@ decode_endian!(
     decode_to_rgb888_le,
     decode_to_rgb565_le,
     decode_to_rgba8888_le,
-    decode_to_rgba8888_alpha_le
+    decode_to_rgba8888_alpha_le,
+    decode_to_l8_le,
+    decode_to_la88_le,
+    decode_to_la88_alpha_le,
+    decode_to_rgb555_le,
+    decode_to_argb1555_le,
+    decode_to_argb1555_alpha_le,
+    decode_to_rgb444_le,
+    decode_to_rgba4444_le,
+    decode_to_rgba4444_alpha_le,
+    decode_to_rgb332_le,
+    decode_to_bgr565_le,
+    decode_to_bgr565be_le,
+    decode_to_planes_le
 );
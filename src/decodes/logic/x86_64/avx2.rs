@@ -1,9 +1,10 @@
 #![allow(unsafe_op_in_unsafe_fn)]
 
-use crate::decodes::logic::{scalar, decode_logic_fn};
-use crate::common::color::ColorType;
-use crate::common::logic::x86_64::M256I;
-use crate::common::pixel::{PIXEL_BYTES, PIXEL_R_MASK, PIXEL_G_MASK, PIXEL_B_MASK};
+use crate::decodes::logic::{scalar, decode_logic_fn, blit_logic_fn, Rgb332DitherRow};
+use crate::decodes::logic::scalar::decode_from_rgb565_direct;
+use crate::common::color::{ChannelOrder, ColorType};
+use crate::common::logic::x86_64::{M128I, M256I};
+use crate::common::pixel::{LumaCoefficients, PIXEL_BYTES, PIXEL_R_MASK, PIXEL_G_MASK, PIXEL_B_MASK, RGB555_R_MASK, RGB555_G_MASK, RGB555_A_BIT};
 
 const PIXEL_BLOCK_LEN: usize = 16; // u16(16 bit) * 16 = 256 bit
 
@@ -27,6 +28,65 @@ unsafe fn get_rgb_vec(pixel: M256I) -> (M256I, M256I, M256I) {
     )
 }
 
+/// ピクセルからRGB555を求めます。最上位ビットは 0 になります。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn get_rgb555_vec(pixel: M256I) -> M256I {
+    const RG_MASK: M256I = unsafe { M256I::const1_u16::<{ RGB555_R_MASK | RGB555_G_MASK }>() };
+    const B_MASK: M256I = unsafe { M256I::const1_u16::<PIXEL_B_MASK>() };
+
+    // RとGを1ビット右に移動し、Gの最下位ビットを捨てる
+    (pixel.srli_epi16::<1>() & RG_MASK) | (pixel & B_MASK)
+}
+
+/// ピクセルのRとBを入れ替えます。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn swap_rb_vec(pixel: M256I) -> M256I {
+    const G_MASK: M256I = unsafe { M256I::const1_u16::<PIXEL_G_MASK>() };
+
+    // シフトで16bitレーンからはみ出したビットは捨てられる
+    pixel.slli_epi16::<11>() | (pixel & G_MASK) | pixel.srli_epi16::<11>()
+}
+
+/// ピクセルから16bitレーンの4ビットのRGBを求めます。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn get_rgb444_vec(pixel: M256I) -> (M256I, M256I, M256I) {
+    const NIBBLE_MASK: M256I = unsafe { M256I::const1_u16::<0x000F>() };
+
+    // 各チャンネルの上位4ビットを抽出
+    (
+        pixel.srli_epi16::<12>(),
+        pixel.srli_epi16::<7>() & NIBBLE_MASK,
+        pixel.srli_epi16::<1>() & NIBBLE_MASK
+    )
+}
+
+/// `luma`の重みを16bitレーンに展開します。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn get_luma_weights(luma: LumaCoefficients) -> [M256I; 3] {
+    let [r_weight, g_weight, b_weight] = luma.weights();
+    [M256I::set1_epi16(r_weight as i16), M256I::set1_epi16(g_weight as i16), M256I::set1_epi16(b_weight as i16)]
+}
+
+/// ピクセルから16bitレーンの輝度を求めます。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn get_luma_vec(pixel: M256I, weights: &[M256I; 3]) -> M256I {
+    const ROUNDING: M256I = unsafe { M256I::const1_u16::<128>() };
+
+    let (r_vec, g_vec, b_vec) = get_rgb_vec(pixel);
+
+    // 重みの合計は256なので16bitに収まる
+    r_vec.mullo_epi16(weights[0])
+        .add_epi16(g_vec.mullo_epi16(weights[1]))
+        .add_epi16(b_vec.mullo_epi16(weights[2]))
+        .add_epi16(ROUNDING)
+        .srli_epi16::<8>()
+}
+
 #[inline]
 #[target_feature(enable = "avx2")]
 pub unsafe fn decode_from_rgb565_swap(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
@@ -45,14 +105,192 @@ pub unsafe fn decode_from_rgb565_swap(mut data: *const u8, mut buf: *mut u8, num
     scalar::decode_from_rgb565_swap(data, buf, remainder)
 }
 
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn reverse_pixels(data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+    const REVERSE_MASK: M256I = unsafe { M256I::const_i8::<
+        14, 15, 12, 13, 10, 11, 8, 9, 6, 7, 4, 5, 2, 3, 0, 1,
+        14, 15, 12, 13, 10, 11, 8, 9, 6, 7, 4, 5, 2, 3, 0, 1,
+    >() };
+
+    // 128bitレーンを入れ替える
+    const PERMUTE_IMM8: i32 = 0b_01_00_11_10;
+
+    let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+    let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+    // 末尾から読み込む
+    let mut data = data.add(num_pixels * PIXEL_BYTES);
+
+    for _ in 0..pixel_blocks {
+        data = data.sub(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+
+        let pixel = M256I::loadu_si256(data.cast::<M256I>())
+            .shuffle_epi8(REVERSE_MASK)
+            .permute4x64_epi64::<PERMUTE_IMM8>();
+
+        pixel.storeu_si256(buf.cast::<M256I>());
+
+        buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+    }
+
+    scalar::reverse_pixels(data.sub(remainder * PIXEL_BYTES), buf, remainder)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn blit_rgb888(mut data: *const u8, mut decoded: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    const COLOR_TYPE: ColorType = ColorType::Rgb888;
+
+    // 16bitマスクを1ピクセル3バイトに展開
+    // 0..32バイト目はピクセル0..11、32..48バイト目はピクセル10..16を使用する
+    const MASK_1: M256I = unsafe { M256I::const_i8::<
+        0, 0, 0, 2, 2, 2, 4, 4, 4,  6,  6,  6,  8,  8,  8, 10,
+        2, 2, 4, 4, 4, 6, 6, 6, 8,  8,  8, 10, 10, 10, 12, 12,
+    >() };
+    const MASK_2: M128I = unsafe { M128I::const_i8::<4, 6, 6, 6, 8, 8, 8, 10, 10, 10, 12, 12, 12, 14, 14, 14>() };
+
+    // (q0, q1, q1, q2)に並び替え
+    const PERMUTE_IMM8: i32 = 0b_10_01_01_00;
+
+    let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+    let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+    let transparent_vec = M256I::set1_epi16(transparent_color as i16);
+
+    for _ in 0..pixel_blocks {
+        let mask = M256I::loadu_si256(data.cast::<M256I>()).cmpeq_epi16(transparent_vec);
+        let mask_1 = mask.permute4x64_epi64::<PERMUTE_IMM8>().shuffle_epi8(MASK_1);
+        let mask_2 = mask.extracti128_si256::<1>().shuffle_epi8(MASK_2);
+
+        // 前半32バイト
+        let color = M256I::loadu_si256(decoded.cast::<M256I>());
+        let dst = M256I::loadu_si256(buf.cast::<M256I>());
+        ((dst & mask_1) | mask_1.andnot_si256(color)).storeu_si256(buf.cast::<M256I>());
+
+        // 後半16バイト
+        let color = M128I::loadu_si128(decoded.add(32).cast::<M128I>());
+        let dst = M128I::loadu_si128(buf.add(32).cast::<M128I>());
+        ((dst & mask_2) | mask_2.andnot_si128(color)).storeu_si128(buf.add(32).cast::<M128I>());
+
+        data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+        decoded = decoded.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+        buf = buf.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+    }
+
+    scalar::blit_rgb888(data, decoded, buf, transparent_color, remainder)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn blit_rgb565(mut data: *const u8, mut decoded: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+    let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+    let transparent_vec = M256I::set1_epi16(transparent_color as i16);
+
+    for _ in 0..pixel_blocks {
+        let mask = M256I::loadu_si256(data.cast::<M256I>()).cmpeq_epi16(transparent_vec);
+
+        let color = M256I::loadu_si256(decoded.cast::<M256I>());
+        let dst = M256I::loadu_si256(buf.cast::<M256I>());
+        ((dst & mask) | mask.andnot_si256(color)).storeu_si256(buf.cast::<M256I>());
+
+        data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+        decoded = decoded.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+        buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+    }
+
+    scalar::blit_rgb565(data, decoded, buf, transparent_color, remainder)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn blit_rgba8888(mut data: *const u8, mut decoded: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    const COLOR_TYPE: ColorType = ColorType::Rgba8888;
+
+    // 16bitマスクを1ピクセル4バイトに展開
+    const MASK: M256I = unsafe { M256I::const_i8::<
+        0, 0, 0, 0, 2, 2, 2, 2, 4, 4, 4, 4, 6, 6, 6, 6,
+        0, 0, 0, 0, 2, 2, 2, 2, 4, 4, 4, 4, 6, 6, 6, 6,
+    >() };
+
+    // (q0, q0, q1, q1)と(q2, q2, q3, q3)に並び替え
+    const PERMUTE_IMM8_1: i32 = 0b_01_01_00_00;
+    const PERMUTE_IMM8_2: i32 = 0b_11_11_10_10;
+
+    let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+    let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+    let transparent_vec = M256I::set1_epi16(transparent_color as i16);
+
+    for _ in 0..pixel_blocks {
+        let mask = M256I::loadu_si256(data.cast::<M256I>()).cmpeq_epi16(transparent_vec);
+
+        let masks = [
+            (0, mask.permute4x64_epi64::<PERMUTE_IMM8_1>().shuffle_epi8(MASK)),
+            (32, mask.permute4x64_epi64::<PERMUTE_IMM8_2>().shuffle_epi8(MASK)),
+        ];
+
+        for (offset, mask) in masks {
+            let color = M256I::loadu_si256(decoded.add(offset).cast::<M256I>());
+            let dst = M256I::loadu_si256(buf.add(offset).cast::<M256I>());
+            ((dst & mask) | mask.andnot_si256(color)).storeu_si256(buf.add(offset).cast::<M256I>());
+        }
+
+        data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+        decoded = decoded.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+        buf = buf.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+    }
+
+    scalar::blit_rgba8888(data, decoded, buf, transparent_color, remainder)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn blit_l8(mut data: *const u8, mut decoded: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+    const COLOR_TYPE: ColorType = ColorType::L8;
+
+    // 16bitマスクを1ピクセル1バイトに詰める
+    const MASK: M256I = unsafe { M256I::const_i8::<
+        0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+        0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+    >() };
+
+    // 各128bitレーンの前半8バイトを連結する
+    const PERMUTE_IMM8: i32 = 0b_00_00_10_00;
+
+    let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+    let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+    let transparent_vec = M256I::set1_epi16(transparent_color as i16);
+
+    for _ in 0..pixel_blocks {
+        let mask = M256I::loadu_si256(data.cast::<M256I>()).cmpeq_epi16(transparent_vec)
+            .shuffle_epi8(MASK)
+            .permute4x64_epi64::<PERMUTE_IMM8>()
+            .extracti128_si256::<0>();
+
+        let color = M128I::loadu_si128(decoded.cast::<M128I>());
+        let dst = M128I::loadu_si128(buf.cast::<M128I>());
+        ((dst & mask) | mask.andnot_si128(color)).storeu_si128(buf.cast::<M128I>());
+
+        data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+        decoded = decoded.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+        buf = buf.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+    }
+
+    scalar::blit_l8(data, decoded, buf, transparent_color, remainder)
+}
+
 macro_rules! decode_from_endian {
-    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident, $rgba8888_alpha: ident) => {
+    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident, $rgba8888_alpha: ident, $l8: ident, $la88: ident, $la88_alpha: ident, $rgb555: ident, $argb1555: ident, $argb1555_alpha: ident, $rgb444: ident, $rgba4444: ident, $rgba4444_alpha: ident, $rgb332: ident, $bgr565: ident, $bgr565be: ident, $planes: ident) => {
 
         // -- rgb888 ------------------------------
 
         #[inline]
         #[target_feature(enable = "avx2")]
-        pub unsafe fn $rgb888(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgb888<O: ChannelOrder>(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
             const COLOR_TYPE: ColorType = ColorType::Rgb888;
 
             const RGB_MASK: M256I = unsafe { M256I::const_i8::<
@@ -64,12 +302,12 @@ macro_rules! decode_from_endian {
 
             // バッファオーバーしないための前後ピクセルを加味する
             if num_pixels < PIXEL_BLOCK_LEN + 2 + 2 {
-                scalar::$rgb888(data, buf, num_pixels);
+                scalar::$rgb888::<O>(data, buf, num_pixels);
                 return;
             }
 
             // 先頭の2ピクセル先に処理する
-            scalar::$rgb888(data, buf, 2);
+            scalar::$rgb888::<O>(data, buf, 2);
             let num_pixels = num_pixels - 2;
 
             // 2ピクセル部分進めておく
@@ -88,6 +326,7 @@ macro_rules! decode_from_endian {
         
                 // マスクで色を分離
                 let (r_vec, g_vec, b_vec) = get_rgb_vec(pixel);
+                let (r_vec, b_vec) = O::swap_rb(r_vec, b_vec);
 
                 let rgb_1 = r_vec.shuffle_epi8(RGB_MASK).slli_si256::<4>() |
                     g_vec.shuffle_epi8(RGB_MASK).slli_si256::<5>() |
@@ -115,7 +354,7 @@ macro_rules! decode_from_endian {
                 buf = buf.add(8 * COLOR_TYPE.bytes_per_pixel());
             }
             
-            scalar::$rgb888(data, buf.add(4), remainder)
+            scalar::$rgb888::<O>(data, buf.add(4), remainder)
         }
 
         // -- rgb565 ------------------------------
@@ -137,7 +376,7 @@ macro_rules! decode_from_endian {
 
         #[inline]
         #[target_feature(enable = "avx2")]
-        pub unsafe fn $rgba8888(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgba8888<O: ChannelOrder>(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
             const COLOR_TYPE: ColorType = ColorType::Rgba8888;
 
             const RGBA_MASK: M256I = unsafe { M256I::const_i8::<
@@ -159,6 +398,7 @@ macro_rules! decode_from_endian {
         
                 // マスクで色を分離
                 let (r_vec, g_vec, b_vec) = get_rgb_vec(pixel);
+                let (r_vec, b_vec) = O::swap_rb(r_vec, b_vec);
 
                 let rgb_1 = r_vec.shuffle_epi8(RGBA_MASK) |
                     g_vec.shuffle_epi8(RGBA_MASK).slli_si256::<1>() |
@@ -185,14 +425,14 @@ macro_rules! decode_from_endian {
                 buf = buf.add(8 * COLOR_TYPE.bytes_per_pixel());
             }
             
-            scalar::$rgba8888(data, buf, remainder)
+            scalar::$rgba8888::<O>(data, buf, remainder)
         }
 
         // -- rgba8888 alpha ----------------------
 
         #[inline]
         #[target_feature(enable = "avx2")]
-        pub unsafe fn $rgba8888_alpha(mut data: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+        pub unsafe fn $rgba8888_alpha<O: ChannelOrder>(mut data: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
             const COLOR_TYPE: ColorType = ColorType::Rgba8888;
 
             const RGBA_MASK: M256I = unsafe { M256I::const_i8::<
@@ -214,6 +454,7 @@ macro_rules! decode_from_endian {
         
                 // マスクで色を分離
                 let (r_vec, g_vec, b_vec) = get_rgb_vec(pixel);
+                let (r_vec, b_vec) = O::swap_rb(r_vec, b_vec);
 
                 let rgb_1 = r_vec.shuffle_epi8(RGBA_MASK) |
                     g_vec.shuffle_epi8(RGBA_MASK).slli_si256::<1>() |
@@ -240,165 +481,580 @@ macro_rules! decode_from_endian {
                 buf = buf.add(8 * COLOR_TYPE.bytes_per_pixel());
             }
             
-            scalar::$rgba8888_alpha(data, buf, transparent_color, remainder)
+            scalar::$rgba8888_alpha::<O>(data, buf, transparent_color, remainder)
         }
-    };
-}
+        // -- l8 ----------------------------------
 
-decode_logic_fn!();
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $l8(mut data: *const u8, mut buf: *mut u8, num_pixels: usize, luma: LumaCoefficients) {
+            const COLOR_TYPE: ColorType = ColorType::L8;
 
-decode_from_endian!(
-    "big",
-    be_epi16,
-    decode_to_rgb888_be,
-    decode_to_rgb565_be,
-    decode_to_rgba8888_be,
-    decode_to_rgba8888_alpha_be
-);
+            // 16bitレーンの下位バイトを各128bitレーンの前半8バイトに詰める
+            const PACK_MASK: M256I = unsafe { M256I::const_i8::<
+                0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+                0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-decode_from_endian!(
-    "little",
-    le_epi16,
-    decode_to_rgb888_le,
-    decode_to_rgb565_le,
-    decode_to_rgba8888_le,
-    decode_to_rgba8888_alpha_le
-);
+            // 各128bitレーンの前半8バイトを連結する
+            const PERMUTE_IMM8: i32 = 0b_00_00_10_00;
 
-#[cfg(test)]
-mod tests {
-    use crate::common::color::ColorType;
-    use crate::common::spec::{ImageSpec, PixelEndian};
-    use crate::decodes::logic::scalar;
-    use crate::decodes::logic::tests::{NUM_PIXELS, RGB565_DATA_BE, RGB565_DATA_LE};
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
 
-    #[test]
-    fn decode_logic_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
-        }
+            let weights = get_luma_weights(luma);
 
-        let mut a_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
-        let mut b_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
 
-        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
-        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+                let l_vec = get_luma_vec(pixel, &weights);
 
-        let mut spec = ImageSpec {
-            width: NUM_PIXELS as u16,
-            height: 1,
-            transparent_color: Some(0xFF),
-            pixel_endian: PixelEndian::Big
-        };
+                // 16ピクセル書き込み
+                l_vec.shuffle_epi8(PACK_MASK).permute4x64_epi64::<PERMUTE_IMM8>().extracti128_si256::<0>().storeu_si128(buf.cast::<M128I>());
 
-        unsafe {
-            super::decode_logic(rgb565_be_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb888);
-            super::decode_to_rgb888_be(rgb565_be_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+            }
 
-            super::decode_logic(rgb565_be_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb565);
-            super::decode_to_rgb565_be(rgb565_be_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+            scalar::$l8(data, buf, remainder, luma)
+        }
 
-            super::decode_logic(rgb565_be_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgba8888);
-            super::decode_to_rgba8888_be(rgb565_be_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+        // -- la88 --------------------------------
 
-            spec.pixel_endian = PixelEndian::Little;
-            
-            super::decode_logic(rgb565_le_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb888);
-            super::decode_to_rgb888_le(rgb565_le_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $la88(mut data: *const u8, mut buf: *mut u8, num_pixels: usize, luma: LumaCoefficients) {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+            const ALPHA_VEC: M256I = unsafe { M256I::const1_u16::<0xFF00>() };
 
-            super::decode_logic(rgb565_le_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb565);
-            super::decode_to_rgb565_le(rgb565_le_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
 
-            super::decode_logic(rgb565_le_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgba8888);
-            super::decode_to_rgba8888_le(rgb565_le_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
-        }
-    }
+            let weights = get_luma_weights(luma);
 
-    #[test]
-    fn decode_rgb888_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
-        }
-        
-        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Rgb888.bytes_per_pixel()];
-        let mut simd_buf = [0; NUM_PIXELS * ColorType::Rgb888.bytes_per_pixel()];
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
 
-        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
-        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+                // 下位バイトに輝度、上位バイトにalpha
+                let la = get_luma_vec(pixel, &weights) | ALPHA_VEC;
 
-        unsafe {
-            scalar::decode_to_rgb888_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::decode_to_rgb888_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+                // 16ピクセル書き込み
+                la.storeu_si256(buf.cast::<M256I>());
 
-        assert_eq!(scalar_buf, simd_buf);
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+            }
 
-        unsafe {
-            scalar::decode_to_rgb888_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::decode_to_rgb888_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            scalar::$la88(data, buf, remainder, luma)
         }
 
-        assert_eq!(scalar_buf, simd_buf);
-    }
+        // -- la88 alpha --------------------------
 
-    #[test]
-    fn decode_rgb565_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
-        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $la88_alpha(mut data: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize, luma: LumaCoefficients) {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+            const ALPHA_VEC: M256I = unsafe { M256I::const1_u16::<0xFF00>() };
 
-        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Rgb565.bytes_per_pixel()];
-        let mut simd_buf = [0; NUM_PIXELS * ColorType::Rgb565.bytes_per_pixel()];
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
 
-        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
-        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+            let transparent_vec = M256I::set1_epi16(transparent_color as i16);
+            let weights = get_luma_weights(luma);
 
-        unsafe {
-            scalar::decode_to_rgb565_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::decode_to_rgb565_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
 
-        assert_eq!(scalar_buf, simd_buf);
+                // alpha作成
+                let a_vec = pixel.cmpeq_epi16(transparent_vec).andnot_si256(ALPHA_VEC);
 
-        unsafe {
-            scalar::decode_to_rgb565_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::decode_to_rgb565_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+                // 下位バイトに輝度、上位バイトにalpha
+                let la = get_luma_vec(pixel, &weights) | a_vec;
 
-        assert_eq!(scalar_buf, simd_buf);
-    }
+                // 16ピクセル書き込み
+                la.storeu_si256(buf.cast::<M256I>());
 
-    #[test]
-    fn decode_rgba8888_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+            }
+
+            scalar::$la88_alpha(data, buf, transparent_color, remainder, luma)
         }
 
-        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
-        let mut simd_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+        // -- rgb555 ------------------------------
 
-        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
-        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgb555(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
 
-        unsafe {
-            scalar::decode_to_rgba8888_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::decode_to_rgba8888_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
 
-        assert_eq!(scalar_buf, simd_buf);
+                // 16ピクセル書き込み
+                get_rgb555_vec(pixel).storeu_si256(buf.cast::<M256I>());
 
-        unsafe {
-            scalar::decode_to_rgba8888_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::decode_to_rgba8888_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgb555(data, buf, remainder)
         }
 
-        assert_eq!(scalar_buf, simd_buf);
+        // -- argb1555 ----------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $argb1555(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+            const ALPHA_VEC: M256I = unsafe { M256I::const1_u16::<RGB555_A_BIT>() };
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                // 16ピクセル書き込み
+                (get_rgb555_vec(pixel) | ALPHA_VEC).storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$argb1555(data, buf, remainder)
+        }
+
+        // -- argb1555 alpha ----------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $argb1555_alpha(mut data: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+            const ALPHA_VEC: M256I = unsafe { M256I::const1_u16::<RGB555_A_BIT>() };
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            let transparent_vec = M256I::set1_epi16(transparent_color as i16);
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                // alpha作成
+                let a_vec = pixel.cmpeq_epi16(transparent_vec).andnot_si256(ALPHA_VEC);
+
+                // 16ピクセル書き込み
+                (get_rgb555_vec(pixel) | a_vec).storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$argb1555_alpha(data, buf, transparent_color, remainder)
+        }
+
+        // -- rgb444 ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgb444(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                let (r_vec, g_vec, b_vec) = get_rgb444_vec(pixel);
+
+                // 16ピクセル書き込み
+                (r_vec.slli_epi16::<8>() | g_vec.slli_epi16::<4>() | b_vec).storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgb444(data, buf, remainder)
+        }
+
+        // -- rgba4444 ----------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgba4444(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+            const ALPHA_VEC: M256I = unsafe { M256I::const1_u16::<0x000F>() };
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                let (r_vec, g_vec, b_vec) = get_rgb444_vec(pixel);
+
+                // 16ピクセル書き込み
+                (r_vec.slli_epi16::<12>() | g_vec.slli_epi16::<8>() | b_vec.slli_epi16::<4>() | ALPHA_VEC).storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgba4444(data, buf, remainder)
+        }
+
+        // -- rgba4444 alpha ----------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgba4444_alpha(mut data: *const u8, mut buf: *mut u8, transparent_color: u16, num_pixels: usize) {
+            const ALPHA_VEC: M256I = unsafe { M256I::const1_u16::<0x000F>() };
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            let transparent_vec = M256I::set1_epi16(transparent_color as i16);
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                let (r_vec, g_vec, b_vec) = get_rgb444_vec(pixel);
+
+                // alpha作成
+                let a_vec = pixel.cmpeq_epi16(transparent_vec).andnot_si256(ALPHA_VEC);
+
+                // 16ピクセル書き込み
+                (r_vec.slli_epi16::<12>() | g_vec.slli_epi16::<8>() | b_vec.slli_epi16::<4>() | a_vec).storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgba4444_alpha(data, buf, transparent_color, remainder)
+        }
+
+        // -- rgb332 ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgb332(mut data: *const u8, mut buf: *mut u8, num_pixels: usize, dither: &Rgb332DitherRow) {
+            const COLOR_TYPE: ColorType = ColorType::Rgb332;
+            const MAX_VEC: M256I = unsafe { M256I::const1_u16::<0x00FF>() };
+
+            // 16bitレーンの下位バイトを各128bitレーンの前半8バイトに詰める
+            const PACK_MASK: M256I = unsafe { M256I::const_i8::<
+                0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+                0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
+
+            // 各128bitレーンの前半8バイトを連結する
+            const PERMUTE_IMM8: i32 = 0b_00_00_10_00;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            // ブロックは8ピクセル周期の先頭から始まる
+            let rg_dither = M256I::loadu_si256(dither.rg.as_ptr().cast::<M256I>());
+            let b_dither = M256I::loadu_si256(dither.b.as_ptr().cast::<M256I>());
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                let (r_vec, g_vec, b_vec) = get_rgb_vec(pixel);
+
+                // しきい値を加算してから切り捨てる
+                let r_vec = r_vec.add_epi16(rg_dither).min_epi16(MAX_VEC).srli_epi16::<5>();
+                let g_vec = g_vec.add_epi16(rg_dither).min_epi16(MAX_VEC).srli_epi16::<5>();
+                let b_vec = b_vec.add_epi16(b_dither).min_epi16(MAX_VEC).srli_epi16::<6>();
+
+                let rgb332 = r_vec.slli_epi16::<5>() | g_vec.slli_epi16::<2>() | b_vec;
+
+                // 16ピクセル書き込み
+                rgb332.shuffle_epi8(PACK_MASK).permute4x64_epi64::<PERMUTE_IMM8>().extracti128_si256::<0>().storeu_si128(buf.cast::<M128I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+            }
+
+            scalar::$rgb332(data, buf, remainder, dither)
+        }
+
+        // -- bgr565 ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $bgr565(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                // 16ピクセル書き込み
+                swap_rb_vec(pixel).storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$bgr565(data, buf, remainder)
+        }
+
+        // -- bgr565be ----------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $bgr565be(mut data: *const u8, mut buf: *mut u8, num_pixels: usize) {
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                // ビッグエンディアンで16ピクセル書き込み
+                swap_rb_vec(pixel).be_epi16().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$bgr565be(data, buf, remainder)
+        }
+
+        // -- planes ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $planes(mut data: *const u8, mut r: *mut u8, mut g: *mut u8, mut b: *mut u8, num_pixels: usize) {
+            // 16bitレーンの下位バイトを各128bitレーンの前半8バイトに詰める
+            const PACK_MASK: M256I = unsafe { M256I::const_i8::<
+                0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+                0, 2, 4, 6, 8, 10, 12, 14, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
+
+            // 各128bitレーンの前半8バイトを連結する
+            const PERMUTE_IMM8: i32 = 0b_00_00_10_00;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let pixel = M256I::loadu_si256(data.cast::<M256I>()).$endian_fn();
+
+                // マスクで色を分離
+                let (r_vec, g_vec, b_vec) = get_rgb_vec(pixel);
+
+                // 各平面に16ピクセル書き込み
+                r_vec.shuffle_epi8(PACK_MASK).permute4x64_epi64::<PERMUTE_IMM8>().extracti128_si256::<0>().storeu_si128(r.cast::<M128I>());
+                g_vec.shuffle_epi8(PACK_MASK).permute4x64_epi64::<PERMUTE_IMM8>().extracti128_si256::<0>().storeu_si128(g.cast::<M128I>());
+                b_vec.shuffle_epi8(PACK_MASK).permute4x64_epi64::<PERMUTE_IMM8>().extracti128_si256::<0>().storeu_si128(b.cast::<M128I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                r = r.add(PIXEL_BLOCK_LEN);
+                g = g.add(PIXEL_BLOCK_LEN);
+                b = b.add(PIXEL_BLOCK_LEN);
+            }
+
+            scalar::$planes(data, r, g, b, remainder)
+        }
+    };
+}
+
+decode_logic_fn!();
+blit_logic_fn!();
+
+decode_from_endian!(
+    "big",
+    be_epi16,
+    decode_to_rgb888_be,
+    decode_to_rgb565_be,
+    decode_to_rgba8888_be,
+    decode_to_rgba8888_alpha_be,
+    decode_to_l8_be,
+    decode_to_la88_be,
+    decode_to_la88_alpha_be,
+    decode_to_rgb555_be,
+    decode_to_argb1555_be,
+    decode_to_argb1555_alpha_be,
+    decode_to_rgb444_be,
+    decode_to_rgba4444_be,
+    decode_to_rgba4444_alpha_be,
+    decode_to_rgb332_be,
+    decode_to_bgr565_be,
+    decode_to_bgr565be_be,
+    decode_to_planes_be
+);
+
+decode_from_endian!(
+    "little",
+    le_epi16,
+    decode_to_rgb888_le,
+    decode_to_rgb565_le,
+    decode_to_rgba8888_le,
+    decode_to_rgba8888_alpha_le,
+    decode_to_l8_le,
+    decode_to_la88_le,
+    decode_to_la88_alpha_le,
+    decode_to_rgb555_le,
+    decode_to_argb1555_le,
+    decode_to_argb1555_alpha_le,
+    decode_to_rgb444_le,
+    decode_to_rgba4444_le,
+    decode_to_rgba4444_alpha_le,
+    decode_to_rgb332_le,
+    decode_to_bgr565_le,
+    decode_to_bgr565be_le,
+    decode_to_planes_le
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::common::color::{Argb, Bgr, ColorType, Rgb};
+    use crate::common::pixel::{pixel_to_rgb, rgb_to_pixel, LumaCoefficients, PIXEL_BYTES};
+    use crate::common::spec::{ImageSpec, PixelEndian};
+    use crate::decodes::logic::{scalar, Rgb332DitherRow};
+    use crate::encodes::options::Dither;
+    use crate::decodes::options::DecodeOptions;
+    use crate::decodes::logic::tests::{NUM_PIXELS, RGB565_DATA_BE, RGB565_DATA_LE};
+
+    #[test]
+    fn decode_logic_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut a_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+        let mut b_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        let mut spec = ImageSpec {
+            width: NUM_PIXELS as u16,
+            height: 1,
+            transparent_color: Some(0xFF),
+            pixel_endian: PixelEndian::Big
+        };
+
+        unsafe {
+            super::decode_logic(rgb565_be_ptr, a_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), ColorType::Rgb888);
+            super::decode_to_rgb888_be::<Rgb>(rgb565_be_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+
+            super::decode_logic(rgb565_be_ptr, a_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), ColorType::Rgb565);
+            super::decode_to_rgb565_be(rgb565_be_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+
+            super::decode_logic(rgb565_be_ptr, a_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), ColorType::Rgba8888);
+            super::decode_to_rgba8888_be::<Rgb>(rgb565_be_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+
+            spec.pixel_endian = PixelEndian::Little;
+            
+            super::decode_logic(rgb565_le_ptr, a_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), ColorType::Rgb888);
+            super::decode_to_rgb888_le::<Rgb>(rgb565_le_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+
+            super::decode_logic(rgb565_le_ptr, a_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), ColorType::Rgb565);
+            super::decode_to_rgb565_le(rgb565_le_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+
+            super::decode_logic(rgb565_le_ptr, a_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), ColorType::Rgba8888);
+            super::decode_to_rgba8888_le::<Rgb>(rgb565_le_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+        }
+    }
+
+    #[test]
+    fn decode_rgb888_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+        
+        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Rgb888.bytes_per_pixel()];
+        let mut simd_buf = [0; NUM_PIXELS * ColorType::Rgb888.bytes_per_pixel()];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::decode_to_rgb888_be::<Rgb>(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgb888_be::<Rgb>(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+
+        unsafe {
+            scalar::decode_to_rgb888_le::<Rgb>(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgb888_le::<Rgb>(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+    }
+
+    #[test]
+    fn decode_rgb565_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Rgb565.bytes_per_pixel()];
+        let mut simd_buf = [0; NUM_PIXELS * ColorType::Rgb565.bytes_per_pixel()];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::decode_to_rgb565_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgb565_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+
+        unsafe {
+            scalar::decode_to_rgb565_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgb565_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+    }
+
+    #[test]
+    fn decode_rgba8888_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+        let mut simd_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::decode_to_rgba8888_be::<Rgb>(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgba8888_be::<Rgb>(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+
+        unsafe {
+            scalar::decode_to_rgba8888_le::<Rgb>(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgba8888_le::<Rgb>(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
     }
 
     #[test]
@@ -416,17 +1072,400 @@ mod tests {
         let transparent_color = crate::common::pixel::rgb_to_pixel([255, 255, 255]);
 
         unsafe {
-            scalar::decode_to_rgba8888_alpha_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
-            super::decode_to_rgba8888_alpha_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            scalar::decode_to_rgba8888_alpha_be::<Rgb>(rgb565_be_ptr, scalar_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            super::decode_to_rgba8888_alpha_be::<Rgb>(rgb565_be_ptr, simd_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+
+        unsafe {
+            scalar::decode_to_rgba8888_alpha_le::<Rgb>(rgb565_le_ptr, scalar_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            super::decode_to_rgba8888_alpha_le::<Rgb>(rgb565_le_ptr, simd_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
         }
 
         assert_eq!(scalar_buf, simd_buf);
+    }
+
+    #[test]
+    fn decode_bgr_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Bgra8888.bytes_per_pixel()];
+        let mut simd_buf = [0; NUM_PIXELS * ColorType::Bgra8888.bytes_per_pixel()];
+        let mut rgb_buf = [0; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        let transparent_color = rgb_to_pixel([255, 255, 255]);
+
+        unsafe {
+            scalar::decode_to_rgb888_be::<Bgr>(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgb888_be::<Bgr>(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_rgba8888_le::<Bgr>(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgba8888_le::<Bgr>(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_rgba8888_alpha_be::<Bgr>(rgb565_be_ptr, scalar_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            super::decode_to_rgba8888_alpha_be::<Bgr>(rgb565_be_ptr, simd_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            // RGBAとはRとBのみ入れ替わる
+            super::decode_to_rgba8888_alpha_be::<Rgb>(rgb565_be_ptr, rgb_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+        }
+
+        for (bgra, rgba) in simd_buf.chunks_exact(4).zip(rgb_buf.chunks_exact(4)) {
+            assert_eq!(bgra, [rgba[2], rgba[1], rgba[0], rgba[3]]);
+        }
+    }
+
+    #[test]
+    fn decode_rgb332_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0u8; NUM_PIXELS];
+        let mut simd_buf = [0u8; NUM_PIXELS];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            for dither in [Dither::None, Dither::Bayer4x4, Dither::Bayer8x8] {
+                for y in 0..8 {
+                    let dither_row = Rgb332DitherRow::new(dither, y);
+
+                    scalar::decode_to_rgb332_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, &dither_row);
+                    super::decode_to_rgb332_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, &dither_row);
+                    assert_eq!(scalar_buf, simd_buf);
+
+                    super::decode_to_rgb332_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, &dither_row);
+                    assert_eq!(scalar_buf, simd_buf);
+                }
+            }
+
+            super::decode_to_rgb332_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, &Rgb332DitherRow::new(Dither::None, 0));
+        }
+
+        // ディザリングしない場合は各チャンネルの上位ビット
+        for (&rgb332, &pixel) in simd_buf.iter().zip(&RGB565_DATA_LE) {
+            let [r, g, b] = pixel_to_rgb(u16::from_le(pixel));
+            assert_eq!(rgb332, (r & 0xE0) | ((g >> 5) << 2) | (b >> 6));
+        }
+    }
+
+    #[test]
+    fn decode_bgr565_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0u8; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0u8; NUM_PIXELS * PIXEL_BYTES];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::decode_to_bgr565_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_bgr565_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_bgr565_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_bgr565_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // RとBを入れ替えたネイティブエンディアンのピクセル
+        for (bgr565, &pixel) in simd_buf.chunks_exact(PIXEL_BYTES).zip(&RGB565_DATA_LE) {
+            let pixel = u16::from_le(pixel);
+            let expected = ((pixel & 0x001F) << 11) | (pixel & 0x07E0) | ((pixel & 0xF800) >> 11);
+            assert_eq!(u16::from_ne_bytes([bgr565[0], bgr565[1]]), expected);
+        }
+    }
+
+    #[test]
+    fn decode_bgr565be_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0u8; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0u8; NUM_PIXELS * PIXEL_BYTES];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::decode_to_bgr565be_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_bgr565be_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_bgr565be_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_bgr565be_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // RとBを入れ替えたビッグエンディアンのピクセル
+        for (bgr565, &pixel) in simd_buf.chunks_exact(PIXEL_BYTES).zip(&RGB565_DATA_LE) {
+            let pixel = u16::from_le(pixel);
+            let expected = ((pixel & 0x001F) << 11) | (pixel & 0x07E0) | ((pixel & 0xF800) >> 11);
+            assert_eq!(u16::from_be_bytes([bgr565[0], bgr565[1]]), expected);
+        }
+    }
+
+    #[test]
+    fn decode_planes_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0u8; 3 * NUM_PIXELS];
+        let mut simd_buf = [0u8; 3 * NUM_PIXELS];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            let (scalar_ptr, simd_ptr) = (scalar_buf.as_mut_ptr(), simd_buf.as_mut_ptr());
+
+            scalar::decode_to_planes_be(rgb565_be_ptr, scalar_ptr, scalar_ptr.add(NUM_PIXELS), scalar_ptr.add(2 * NUM_PIXELS), NUM_PIXELS);
+            super::decode_to_planes_be(rgb565_be_ptr, simd_ptr, simd_ptr.add(NUM_PIXELS), simd_ptr.add(2 * NUM_PIXELS), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_planes_le(rgb565_le_ptr, scalar_ptr, scalar_ptr.add(NUM_PIXELS), scalar_ptr.add(2 * NUM_PIXELS), NUM_PIXELS);
+            super::decode_to_planes_le(rgb565_le_ptr, simd_ptr, simd_ptr.add(NUM_PIXELS), simd_ptr.add(2 * NUM_PIXELS), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // 各平面の値はRGB888と同じ
+        for (i, &pixel) in RGB565_DATA_LE.iter().enumerate() {
+            let [r, g, b] = pixel_to_rgb(u16::from_le(pixel));
+            assert_eq!([simd_buf[i], simd_buf[NUM_PIXELS + i], simd_buf[2 * NUM_PIXELS + i]], [r, g, b]);
+        }
+
+        // ロジック関数も同じ結果になる
+        let spec = ImageSpec::with_pixel_endian(NUM_PIXELS as u16, 1, PixelEndian::Little);
+        let mut logic_buf = [0u8; 3 * NUM_PIXELS];
+
+        unsafe {
+            let logic_ptr = logic_buf.as_mut_ptr();
+            super::decode_planes_logic(rgb565_le_ptr, logic_ptr, logic_ptr.add(NUM_PIXELS), logic_ptr.add(2 * NUM_PIXELS), &spec, NUM_PIXELS);
+        }
+
+        assert_eq!(logic_buf, simd_buf);
+    }
+
+    #[test]
+    fn decode_rgb444_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0u16; NUM_PIXELS];
+        let mut simd_buf = [0u16; NUM_PIXELS];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        let transparent_color = rgb_to_pixel([255, 255, 255]);
+
+        unsafe {
+            scalar::decode_to_rgb444_be(rgb565_be_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            super::decode_to_rgb444_be(rgb565_be_ptr, simd_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            super::decode_to_rgb444_le(rgb565_le_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            for (&rgb444, &pixel) in simd_buf.iter().zip(&RGB565_DATA_LE) {
+                let [r, g, b] = pixel_to_rgb(u16::from_le(pixel));
+                assert_eq!(rgb444, ((r as u16 >> 4) << 8) | ((g as u16 >> 4) << 4) | (b as u16 >> 4));
+            }
+
+            scalar::decode_to_rgba4444_le(rgb565_le_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            super::decode_to_rgba4444_le(rgb565_le_ptr, simd_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_rgba4444_alpha_be(rgb565_be_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), transparent_color, NUM_PIXELS);
+            super::decode_to_rgba4444_alpha_be(rgb565_be_ptr, simd_buf.as_mut_ptr().cast::<u8>(), transparent_color, NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // 透明色のみAが 0
+        for (&rgba, &pixel) in simd_buf.iter().zip(&RGB565_DATA_BE) {
+            let expected = if u16::from_be(pixel) == transparent_color { 0x0 } else { 0xF };
+            assert_eq!(rgba & 0x000F, expected);
+        }
+    }
+
+    #[test]
+    fn decode_rgb555_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0u16; NUM_PIXELS];
+        let mut simd_buf = [0u16; NUM_PIXELS];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        let transparent_color = rgb_to_pixel([255, 255, 255]);
 
         unsafe {
-            scalar::decode_to_rgba8888_alpha_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
-            super::decode_to_rgba8888_alpha_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            scalar::decode_to_rgb555_be(rgb565_be_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            super::decode_to_rgb555_be(rgb565_be_ptr, simd_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            super::decode_to_rgb555_le(rgb565_le_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            for (&rgb555, &pixel) in simd_buf.iter().zip(&RGB565_DATA_LE) {
+                let [r, g, b] = pixel_to_rgb(u16::from_le(pixel));
+                assert_eq!(rgb555, ((r as u16 >> 3) << 10) | ((g as u16 >> 3) << 5) | (b as u16 >> 3));
+            }
+
+            scalar::decode_to_argb1555_le(rgb565_le_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            super::decode_to_argb1555_le(rgb565_le_ptr, simd_buf.as_mut_ptr().cast::<u8>(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_argb1555_alpha_be(rgb565_be_ptr, scalar_buf.as_mut_ptr().cast::<u8>(), transparent_color, NUM_PIXELS);
+            super::decode_to_argb1555_alpha_be(rgb565_be_ptr, simd_buf.as_mut_ptr().cast::<u8>(), transparent_color, NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // 透明色のみAが 0
+        for (&argb, &pixel) in simd_buf.iter().zip(&RGB565_DATA_BE) {
+            assert_eq!(argb & 0x8000 == 0, u16::from_be(pixel) == transparent_color);
+        }
+    }
+
+    #[test]
+    fn decode_argb_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Argb8888.bytes_per_pixel()];
+        let mut simd_buf = [0; NUM_PIXELS * ColorType::Argb8888.bytes_per_pixel()];
+
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        let transparent_color = rgb_to_pixel([255, 255, 255]);
+
+        unsafe {
+            scalar::decode_to_rgba8888_le::<Argb>(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::decode_to_rgba8888_le::<Argb>(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::decode_to_rgba8888_alpha_le::<Argb>(rgb565_le_ptr, scalar_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            super::decode_to_rgba8888_alpha_le::<Argb>(rgb565_le_ptr, simd_buf.as_mut_ptr(), transparent_color, NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // ネイティブエンディアンのu32として0xAARRGGBB
+        for (argb, pixel) in simd_buf.chunks_exact(4).zip(RGB565_DATA_LE) {
+            let pixel = u16::from_le(pixel);
+            let [r, g, b] = pixel_to_rgb(pixel);
+            let a = if pixel == transparent_color { 0 } else { 255 };
+            assert_eq!(u32::from_ne_bytes([argb[0], argb[1], argb[2], argb[3]]), u32::from_be_bytes([a, r, g, b]));
+        }
+    }
+
+    #[test]
+    fn decode_gray_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * ColorType::La88.bytes_per_pixel()];
+        let mut simd_buf = [0; NUM_PIXELS * ColorType::La88.bytes_per_pixel()];
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        let transparent_color = rgb_to_pixel([255, 255, 255]);
+
+        for luma in [LumaCoefficients::Bt601, LumaCoefficients::Bt709] {
+            unsafe {
+                scalar::decode_to_l8_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, luma);
+                super::decode_to_l8_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, luma);
+                assert_eq!(scalar_buf, simd_buf);
+
+                for (&l, pixel) in simd_buf.iter().zip(RGB565_DATA_BE) {
+                    assert_eq!(l, luma.luma(pixel_to_rgb(u16::from_be(pixel))));
+                }
+
+                scalar::decode_to_la88_le(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, luma);
+                super::decode_to_la88_le(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, luma);
+                assert_eq!(scalar_buf, simd_buf);
+
+                scalar::decode_to_la88_alpha_be(rgb565_be_ptr, scalar_buf.as_mut_ptr(), transparent_color, NUM_PIXELS, luma);
+                super::decode_to_la88_alpha_be(rgb565_be_ptr, simd_buf.as_mut_ptr(), transparent_color, NUM_PIXELS, luma);
+                assert_eq!(scalar_buf, simd_buf);
+
+                for (la, pixel) in simd_buf.chunks_exact(2).zip(RGB565_DATA_BE) {
+                    let pixel = u16::from_be(pixel);
+                    assert_eq!(la, [luma.luma(pixel_to_rgb(pixel)), if pixel == transparent_color { 0 } else { 255 }]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_pixels_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * ColorType::Rgb565.bytes_per_pixel()];
+        let mut simd_buf = [0; NUM_PIXELS * ColorType::Rgb565.bytes_per_pixel()];
+
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::reverse_pixels(rgb565_le_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::reverse_pixels(rgb565_le_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
         }
 
         assert_eq!(scalar_buf, simd_buf);
+        assert_eq!(scalar_buf[..2], RGB565_DATA_LE[NUM_PIXELS - 1].to_ne_bytes());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn blit_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let rgb565_be_ptr = RGB565_DATA_BE.as_ptr().cast::<u8>();
+        let rgb565_le_ptr = RGB565_DATA_LE.as_ptr().cast::<u8>();
+
+        let mut a_buf = [0xAA; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+        let mut b_buf = [0xAA; NUM_PIXELS * ColorType::Rgba8888.bytes_per_pixel()];
+
+        let transparent_color = rgb_to_pixel([0, 128, 0]);
+
+        for (data_ptr, pixel_endian) in [(rgb565_be_ptr, PixelEndian::Big), (rgb565_le_ptr, PixelEndian::Little)] {
+            let spec = ImageSpec { transparent_color: Some(transparent_color), ..ImageSpec::with_pixel_endian(NUM_PIXELS as u16, 1, pixel_endian) };
+
+            for color_type in [ColorType::Rgb888, ColorType::Rgb565, ColorType::Rgba8888, ColorType::L8, ColorType::La88] {
+                a_buf.fill(0xAA);
+                b_buf.fill(0xAA);
+
+                unsafe {
+                    super::blit_pixels_logic(data_ptr, a_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), color_type, NUM_PIXELS, 0, 0);
+                    scalar::blit_pixels_logic(data_ptr, b_buf.as_mut_ptr(), &spec, &DecodeOptions::new(), color_type, NUM_PIXELS, 0, 0);
+                }
+
+                assert_eq!(a_buf, b_buf);
+            }
+        }
+    }
+}
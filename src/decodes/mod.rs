@@ -1,14 +1,24 @@
 mod logic;
+pub mod options;
+pub mod view;
+pub mod incremental;
+pub mod chunked;
+pub mod orientation;
+pub mod blit;
+pub mod mono;
+pub mod tensor;
 
 use crate::common::color::ColorType;
 use crate::common::header::{ImageHeader, FLAG_ENDIAN_BIT, FLAG_USE_TRANSPARENT_BIT, HEADER_SIZE, SIGNATURE_U32_NE};
-use crate::common::spec::ImageSpec;
+use crate::common::spec::{ImageSpec, PixelEndian};
 use crate::common::pixel::PIXEL_BYTES;
+use crate::decodes::options::DecodeOptions;
 use crate::error::{Error, Result};
 
 /// `spec`と`color_type`からデコードに必要なバイト数を取得します。
 /// 
 /// サイズは（色バイト数 * 総ピクセル数）です。
+/// `ColorType::Mono1`の場合は（1行のバイト数 * 高さ）です。
 /// 
 /// # Examples
 /// 
@@ -24,7 +34,7 @@ use crate::error::{Error, Result};
 /// ```
 #[inline(always)]
 pub const fn decoded_size(spec: &ImageSpec, color_type: ColorType) -> usize {
-    color_type.bytes_per_pixel() * spec.num_pixels()
+    color_type.bytes_per_row(spec.width as usize) * spec.height as usize
 }
 
 /// `data`と`color_type`からLimg形式データをデコードし、`buf`バッファに書き込みます。
@@ -55,10 +65,40 @@ pub const fn decoded_size(spec: &ImageSpec, color_type: ColorType) -> usize {
 /// ```
 #[inline]
 pub fn decode(data: &[u8], buf: &mut [u8], color_type: ColorType) -> Result<(ImageSpec, usize)> {
+    decode_with_options(data, buf, color_type, &DecodeOptions::new())
+}
+
+/// `data`と`color_type`から`options`を使用してLimg形式データをデコードし、`buf`バッファに書き込みます。
+/// 
+/// エラーではなかった場合、`ImageSpec`と書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+/// 
+/// `buf`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use limg_core::{decode_with_options, ColorType, DecodeOptions, LumaCoefficients};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// # let mut buf = vec![0u8; 0];
+/// let options = DecodeOptions { luma: LumaCoefficients::Bt709, ..DecodeOptions::new() };
+/// let (spec, written_size) = decode_with_options(&data, &mut buf, ColorType::L8, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_with_options(data: &[u8], buf: &mut [u8], color_type: ColorType, options: &DecodeOptions) -> Result<(ImageSpec, usize)> {
     let spec = decode_header(data)?;
 
     let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
-    let written_size = decode_data(data, buf, &spec, color_type)?;
+    let written_size = decode_data_with_options(data, buf, &spec, color_type, options)?;
 
     Ok((spec, written_size))
 }
@@ -103,7 +143,7 @@ pub fn decode_header(data: &[u8]) -> Result<ImageSpec> {
     }
 
     let transparent_color = if (header.flag & FLAG_USE_TRANSPARENT_BIT) != 0 { Some(header.transparent_color) } else { None };
-    let pixel_endian = unsafe { ::core::mem::transmute(header.flag & FLAG_ENDIAN_BIT) };
+    let pixel_endian = unsafe { ::core::mem::transmute::<u8, PixelEndian>(header.flag & FLAG_ENDIAN_BIT) };
 
     let spec = ImageSpec {
         width: u16::from_le(header.width),
@@ -143,20 +183,384 @@ pub fn decode_header(data: &[u8]) -> Result<ImageSpec> {
 /// ```
 #[inline]
 pub fn decode_data(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType) -> Result<usize> {
+    decode_data_with_options(data, buf, spec, color_type, &DecodeOptions::new())
+}
+
+/// `data`と`spec`、`color_type`から`options`を使用して色データをデコードし、`buf`バッファに書き込みます。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{decode_data_with_options, rgb_to_pixel, ColorType, DecodeOptions, ImageSpec, LumaCoefficients};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(1, 1);
+/// let data = rgb_to_pixel([0, 255, 0]).to_le_bytes();
+/// 
+/// let mut buf = [0u8; 1];
+/// let options = DecodeOptions { luma: LumaCoefficients::Bt709, ..DecodeOptions::new() };
+/// decode_data_with_options(&data, &mut buf, &spec, ColorType::L8, &options)?;
+/// 
+/// assert_eq!(buf, [LumaCoefficients::Bt709.luma([0, 255, 0])]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode_data_with_options(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, options: &DecodeOptions) -> Result<usize> {
     let num_pixels = spec.num_pixels();
 
     if data.len() < PIXEL_BYTES * num_pixels {
         return Err(Error::InputBufferTooSmall);
     }
 
-    if buf.len() < color_type.bytes_per_pixel() * num_pixels {
+    if buf.len() < decoded_size(spec, color_type) {
         return Err(Error::OutputBufferTooSmall);
     }
 
-    unsafe { Ok(decode_data_unchecked(data, buf, spec, color_type)) }
+    unsafe { Ok(decode_data_unchecked(data, buf, spec, options, color_type)) }
+}
+
+/// `data`からLimg形式データの矩形領域`(x, y, width, height)`をデコードし、`buf`バッファに書き込みます。
+/// 
+/// 必要な行の色データのみを読み取ります。出力は矩形の幅で詰めて書き込まれます。
+/// 
+/// エラーではなかった場合、画像全体の`ImageSpec`と書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `width`か`height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+/// 
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+/// 
+/// 矩形が画像の範囲外にはみ出す場合、`Error::RectOutOfBounds`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `buf`の長さが（色バイト数 * `width` * `height`）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use limg_core::{decode_rect, ColorType};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// let mut buf = vec![0u8; ColorType::Rgb565.bytes_per_pixel() * 32 * 32];
+/// 
+/// // (16, 16)から32x32の領域をデコード
+/// let (spec, written_size) = decode_rect(&data, &mut buf, 16, 16, 32, 32, ColorType::Rgb565)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_rect(data: &[u8], buf: &mut [u8], x: u16, y: u16, width: u16, height: u16, color_type: ColorType) -> Result<(ImageSpec, usize)> {
+    decode_rect_with_options(data, buf, x, y, width, height, color_type, &DecodeOptions::new())
+}
+
+/// `data`から`options`を使用してLimg形式データの矩形領域`(x, y, width, height)`をデコードし、`buf`バッファに書き込みます。
+/// 
+/// 必要な行の色データのみを読み取ります。出力は矩形の幅で詰めて書き込まれます。
+/// 順序ディザリングのしきい値は画像上のピクセル位置で決まるため、画像全体をデコードした結果の同じ領域と一致します。
+/// 
+/// エラーではなかった場合、画像全体の`ImageSpec`と書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `width`か`height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+/// 
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+/// 
+/// 矩形が画像の範囲外にはみ出す場合、`Error::RectOutOfBounds`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `buf`の長さが（色バイト数 * `width` * `height`）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use limg_core::{decode_rect_with_options, ColorType, DecodeOptions, Dither};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// let mut buf = vec![0u8; 32 * 32];
+/// 
+/// // (16, 16)から32x32の領域をディザリングしながらRGB332にデコード
+/// let options = DecodeOptions { dither: Dither::Bayer4x4, ..DecodeOptions::new() };
+/// let (spec, written_size) = decode_rect_with_options(&data, &mut buf, 16, 16, 32, 32, ColorType::Rgb332, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn decode_rect_with_options(data: &[u8], buf: &mut [u8], x: u16, y: u16, width: u16, height: u16, color_type: ColorType, options: &DecodeOptions) -> Result<(ImageSpec, usize)> {
+    if width == 0 || height == 0 {
+        return Err(Error::ZeroImageDimensions);
+    }
+
+    if !color_type.is_byte_addressable() {
+        return Err(Error::UnsupportedColorType);
+    }
+
+    let spec = decode_header(data)?;
+
+    if data.len() < HEADER_SIZE + PIXEL_BYTES * spec.num_pixels() {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    if x as u32 + width as u32 > spec.width as u32 || y as u32 + height as u32 > spec.height as u32 {
+        return Err(Error::RectOutOfBounds);
+    }
+
+    let row_size = color_type.bytes_per_pixel() * width as usize;
+
+    if buf.len() < row_size * height as usize {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    let data_stride = PIXEL_BYTES * spec.width as usize;
+    let offset = HEADER_SIZE + data_stride * y as usize + PIXEL_BYTES * x as usize;
+
+    unsafe {
+        let data = data.as_ptr().add(offset);
+        decode_rows_unchecked(data, data_stride, buf.as_mut_ptr(), row_size, width as usize, height as usize, &spec, options, color_type, x as usize, y as usize);
+    }
+
+    Ok((spec, row_size * height as usize))
+}
+
+/// `data`と`color_type`からLimg形式データをデコードし、1行あたり`stride`バイトの`buf`バッファの`(x, y)`ピクセル位置に書き込みます。
+/// 
+/// 各行は`buf`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から書き込まれ、行間のバイトは変更されません。
+/// 
+/// エラーではなかった場合、`ImageSpec`と書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `buf`に最終行が収まらない場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use limg_core::{decode_strided, ColorType};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// 
+/// // 320x240のRGB565フレームバッファ
+/// let stride = ColorType::Rgb565.bytes_per_pixel() * 320;
+/// let mut framebuffer = vec![0u8; stride * 240];
+/// 
+/// // (10, 20)の位置にデコード
+/// let (spec, written_size) = decode_strided(&data, &mut framebuffer, stride, 10, 20, ColorType::Rgb565)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_strided(data: &[u8], buf: &mut [u8], stride: usize, x: usize, y: usize, color_type: ColorType) -> Result<(ImageSpec, usize)> {
+    decode_strided_with_options(data, buf, stride, x, y, color_type, &DecodeOptions::new())
+}
+
+/// `data`と`color_type`から`options`を使用してLimg形式データをデコードし、1行あたり`stride`バイトの`buf`バッファの`(x, y)`ピクセル位置に書き込みます。
+/// 
+/// 各行は`buf`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から書き込まれ、行間のバイトは変更されません。
+/// 順序ディザリングのしきい値は`buf`上のピクセル位置で決まります。
+/// 
+/// エラーではなかった場合、`ImageSpec`と書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `buf`に最終行が収まらない場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use limg_core::{decode_strided_with_options, ColorType, DecodeOptions, Dither};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// 
+/// // 320x240のRGB332フレームバッファ
+/// let stride = 320;
+/// let mut framebuffer = vec![0u8; stride * 240];
+/// 
+/// // (10, 20)の位置にディザリングしながらデコード
+/// let options = DecodeOptions { dither: Dither::Bayer8x8, ..DecodeOptions::new() };
+/// let (spec, written_size) = decode_strided_with_options(&data, &mut framebuffer, stride, 10, 20, ColorType::Rgb332, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_strided_with_options(data: &[u8], buf: &mut [u8], stride: usize, x: usize, y: usize, color_type: ColorType, options: &DecodeOptions) -> Result<(ImageSpec, usize)> {
+    let spec = decode_header(data)?;
+
+    let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
+    let written_size = decode_data_strided_with_options(data, buf, &spec, stride, x, y, color_type, options)?;
+
+    Ok((spec, written_size))
+}
+
+/// `data`と`spec`、`color_type`から色データをデコードし、1行あたり`stride`バイトの`buf`バッファの`(x, y)`ピクセル位置に書き込みます。
+/// 
+/// 各行は`buf`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から書き込まれ、行間のバイトは変更されません。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `buf`に最終行が収まらない場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{decode_data_strided, ColorType, ImageSpec};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(2, 2);
+/// let data = [0xFFu8; 8];
+/// 
+/// // 4x4のRGB565バッファの(1, 1)にデコード
+/// let mut buf = [0u8; 32];
+/// let written_size = decode_data_strided(&data, &mut buf, &spec, 8, 1, 1, ColorType::Rgb565)?;
+/// 
+/// assert_eq!(written_size, 8);
+/// assert_eq!(buf[8..16], [0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0]);
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_data_strided(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, x: usize, y: usize, color_type: ColorType) -> Result<usize> {
+    decode_data_strided_with_options(data, buf, spec, stride, x, y, color_type, &DecodeOptions::new())
+}
+
+/// `data`と`spec`、`color_type`から`options`を使用して色データをデコードし、1行あたり`stride`バイトの`buf`バッファの`(x, y)`ピクセル位置に書き込みます。
+/// 
+/// 各行は`buf`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から書き込まれ、行間のバイトは変更されません。
+/// 順序ディザリングのしきい値は`buf`上のピクセル位置で決まります。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `buf`に最終行が収まらない場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{decode_data_strided_with_options, rgb_to_pixel, ColorType, DecodeOptions, Dither, ImageSpec};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(2, 2);
+/// let data = rgb_to_pixel([16, 16, 16]).to_le_bytes().repeat(4);
+/// 
+/// // 4x4のRGB332バッファの(2, 2)にディザリングしながらデコード
+/// let mut buf = [0u8; 16];
+/// let options = DecodeOptions { dither: Dither::Bayer4x4, ..DecodeOptions::new() };
+/// decode_data_strided_with_options(&data, &mut buf, &spec, 4, 2, 2, ColorType::Rgb332, &options)?;
+/// 
+/// // しきい値はバッファ上の(2, 2)から始まる位置のもの
+/// assert_eq!(buf[10..12], [0x00, 0x24]);
+/// # Ok(())
+/// # }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn decode_data_strided_with_options(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, x: usize, y: usize, color_type: ColorType, options: &DecodeOptions) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if data.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    if !color_type.is_byte_addressable() {
+        return Err(Error::UnsupportedColorType);
+    }
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    if num_pixels == 0 {
+        return Ok(0);
+    }
+
+    let row_size = bytes_per_pixel * spec.width as usize;
+
+    let row_end = x.checked_mul(bytes_per_pixel)
+        .and_then(|offset| offset.checked_add(row_size))
+        .ok_or(Error::InvalidStride)?;
+
+    if stride < row_end {
+        return Err(Error::InvalidStride);
+    }
+
+    let required_size = y.checked_add(spec.height as usize - 1)
+        .and_then(|last_row| last_row.checked_mul(stride))
+        .and_then(|offset| offset.checked_add(row_end))
+        .ok_or(Error::OutputBufferTooSmall)?;
+
+    if buf.len() < required_size {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    unsafe {
+        let buf = buf.as_mut_ptr().add(stride * y + bytes_per_pixel * x);
+        decode_rows_unchecked(data.as_ptr(), PIXEL_BYTES * spec.width as usize, buf, stride, spec.width as usize, spec.height as usize, spec, options, color_type, x, y);
+    }
+
+    Ok(bytes_per_pixel * num_pixels)
+}
+
+/// 行ごとに`data_stride`、`buf_stride`バイトずつ進めながら`width` * `height`ピクセルをデコードします。
+/// 
+/// 順序ディザリングのしきい値は、先頭のピクセルを`(x, y)`の位置として決まります。
+#[allow(clippy::too_many_arguments)]
+unsafe fn decode_rows_unchecked(data: *const u8, data_stride: usize, buf: *mut u8, buf_stride: usize, width: usize, height: usize, spec: &ImageSpec, options: &DecodeOptions, color_type: ColorType, x: usize, y: usize) {
+    for row in 0..height {
+        unsafe {
+            let data = data.add(data_stride * row);
+            let buf = buf.add(buf_stride * row);
+            logic::decode_row_logic(data, buf, spec, options, color_type, width, x, y + row);
+        }
+    }
 }
 
 #[inline(always)]
-unsafe fn decode_data_unchecked(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType) -> usize {
-    unsafe { logic::decode_logic(data.as_ptr(), buf.as_mut_ptr(), spec, color_type) }
+unsafe fn decode_data_unchecked(data: &[u8], buf: &mut [u8], spec: &ImageSpec, options: &DecodeOptions, color_type: ColorType) -> usize {
+    unsafe { logic::decode_logic(data.as_ptr(), buf.as_mut_ptr(), spec, options, color_type) }
 }
@@ -0,0 +1,214 @@
+use crate::common::color::ColorType;
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::{pixel_to_rgb, PIXEL_BYTES};
+use crate::common::spec::{ImageSpec, PixelEndian};
+use crate::decodes::{decode_header, decoded_size, logic};
+use crate::decodes::options::DecodeOptions;
+use crate::encodes::diffusion::{add_error, diffuse_errors, diffusion_scratch_len, ErrorDiffusion, ERROR_SHIFT};
+use crate::error::{Error, Result};
+
+/// 輝度をまとめて求めるピクセル数
+const BLOCK_PIXELS: usize = 64;
+
+/// `spec`の透明色をエンディアン補正前の色データと比較できる値に変換します。
+#[inline(always)]
+fn raw_transparent_color(spec: &ImageSpec) -> Option<u16> {
+    spec.transparent_color.map(|transparent_color| match spec.pixel_endian {
+        PixelEndian::Big => transparent_color.to_be(),
+        PixelEndian::Little => transparent_color.to_le(),
+    })
+}
+
+/// `(x, y)`のピクセル位置の輝度`luma`を`options`のしきい値と比較し、白になるかどうかを返します。
+#[inline(always)]
+const fn is_white(luma: u8, x: usize, y: usize, options: &DecodeOptions) -> bool {
+    // しきい値の中央（128）が`mono_threshold`になるようにずらす
+    luma as i16 >= options.mono_threshold as i16 - 128 + options.mono_dither.threshold(x, y) as i16
+}
+
+/// `options`を使用して`y`行目の`num_pixels`分のピクセルを1ビットに変換し、最上位ビットから詰めて`buf`に書き込みます。
+///
+/// 書き込んだバイト数を返します。
+pub(crate) unsafe fn decode_mono_row(data: *const u8, buf: *mut u8, spec: &ImageSpec, options: &DecodeOptions, y: usize, num_pixels: usize) -> usize {
+    let transparent_color = raw_transparent_color(spec);
+
+    let mut luma_block = [0u8; BLOCK_PIXELS];
+    let mut byte = 0u8;
+    let mut start = 0;
+
+    while start < num_pixels {
+        let block_pixels = (num_pixels - start).min(BLOCK_PIXELS);
+
+        unsafe { logic::decode_pixels_logic(data.add(PIXEL_BYTES * start), luma_block.as_mut_ptr(), spec, options, ColorType::L8, block_pixels); }
+
+        for (i, &luma) in luma_block[..block_pixels].iter().enumerate() {
+            let x = start + i;
+            let pixel = unsafe { data.add(PIXEL_BYTES * x).cast::<u16>().read_unaligned() };
+
+            let white = if transparent_color == Some(pixel) {
+                options.mono_background
+            } else {
+                is_white(luma, x, y, options)
+            };
+
+            byte |= (white as u8) << (7 - x % 8);
+
+            // 行末の端数ビットは 0 のまま書き込む
+            if x % 8 == 7 || x + 1 == num_pixels {
+                unsafe { buf.add(x / 8).write(byte); }
+                byte = 0;
+            }
+        }
+
+        start += block_pixels;
+    }
+
+    ColorType::Mono1.bytes_per_row(num_pixels)
+}
+
+/// `options`を使用してすべてのピクセルを1ビットに変換し、1行ずつバイト境界までパディングして`buf`に書き込みます。
+///
+/// 書き込んだバイト数を返します。
+pub(crate) unsafe fn decode_mono(data: *const u8, buf: *mut u8, spec: &ImageSpec, options: &DecodeOptions) -> usize {
+    let width = spec.width as usize;
+    let row_size = ColorType::Mono1.bytes_per_row(width);
+
+    for y in 0..spec.height as usize {
+        unsafe {
+            let data = data.add(PIXEL_BYTES * width * y);
+            let buf = buf.add(row_size * y);
+            decode_mono_row(data, buf, spec, options, y, width);
+        }
+    }
+
+    row_size * spec.height as usize
+}
+
+/// `data`から誤差拡散を適用してLimg形式データを`ColorType::Mono1`にデコードし、`buf`バッファに書き込みます。
+///
+/// `scratch`は誤差の保持に使用され、内容は上書きされます。
+/// `options.mono_dither`は無視され、`options.mono_threshold`は量子化の閾値として使用されます。
+///
+/// エラーではなかった場合、`ImageSpec`と書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+///
+/// `buf`の長さが（1行のバイト数 * 高さ）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `scratch`の長さが[`diffusion_scratch_len`]未満の場合、`Error::ScratchBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{decode_header, decode_mono_error_diffused, decoded_size, diffusion_scratch_len};
+/// use limg_core::{ColorType, DecodeOptions, DiffusionKernel, ErrorDiffusion};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// let spec = decode_header(&data)?;
+///
+/// let mut buf = vec![0u8; decoded_size(&spec, ColorType::Mono1)];
+/// let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+/// let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+///
+/// decode_mono_error_diffused(&data, &mut buf, &DecodeOptions::new(), &diffusion, &mut scratch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_mono_error_diffused(data: &[u8], buf: &mut [u8], options: &DecodeOptions, diffusion: &ErrorDiffusion, scratch: &mut [i16]) -> Result<(ImageSpec, usize)> {
+    let spec = decode_header(data)?;
+
+    let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
+    let written_size = decode_data_mono_error_diffused(data, buf, &spec, options, diffusion, scratch)?;
+
+    Ok((spec, written_size))
+}
+
+/// `data`と`spec`から誤差拡散を適用して色データを`ColorType::Mono1`にデコードし、`buf`バッファに書き込みます。
+///
+/// `scratch`は誤差の保持に使用され、内容は上書きされます。
+/// `options.mono_dither`は無視され、`options.mono_threshold`は量子化の閾値として使用されます。
+///
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（1行のバイト数 * 高さ）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `scratch`の長さが[`diffusion_scratch_len`]未満の場合、`Error::ScratchBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{decode_data_mono_error_diffused, diffusion_scratch_len, rgb_to_pixel};
+/// use limg_core::{DecodeOptions, DiffusionKernel, ErrorDiffusion, ImageSpec};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(8, 1);
+/// let data = rgb_to_pixel([128, 128, 128]).to_le_bytes().repeat(8);
+///
+/// let mut buf = [0u8; 1];
+/// let mut scratch = [0i16; diffusion_scratch_len(8)];
+/// let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+///
+/// decode_data_mono_error_diffused(&data, &mut buf, &spec, &DecodeOptions::new(), &diffusion, &mut scratch)?;
+///
+/// // 中間の灰色は白と黒が交互になる
+/// assert_eq!(buf[0].count_ones(), 4);
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode_data_mono_error_diffused(data: &[u8], buf: &mut [u8], spec: &ImageSpec, options: &DecodeOptions, diffusion: &ErrorDiffusion, scratch: &mut [i16]) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if data.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::InputBufferTooSmall);
+    }
+    if buf.len() < decoded_size(spec, ColorType::Mono1) {
+        return Err(Error::OutputBufferTooSmall);
+    }
+    if scratch.len() < diffusion_scratch_len(spec.width) {
+        return Err(Error::ScratchBufferTooSmall);
+    }
+
+    let width = spec.width as usize;
+    let row_size = ColorType::Mono1.bytes_per_row(width);
+
+    let transparent_color = raw_transparent_color(spec);
+    let threshold = (options.mono_threshold as i32) << ERROR_SHIFT;
+
+    // パディングを含めて黒で初期化し、白のビットのみ立てる
+    buf[..row_size * spec.height as usize].fill(0);
+
+    diffuse_errors::<1>(width, spec.height as usize, diffusion, scratch, |x, y, [error]| {
+        let i = width * y + x;
+        let pixel = u16::from_ne_bytes([data[PIXEL_BYTES * i], data[PIXEL_BYTES * i + 1]]);
+        let byte = &mut buf[row_size * y + x / 8];
+
+        if transparent_color == Some(pixel) {
+            *byte |= (options.mono_background as u8) << (7 - x % 8);
+            return None;
+        }
+
+        let pixel = match spec.pixel_endian {
+            PixelEndian::Big => u16::from_be(pixel),
+            PixelEndian::Little => u16::from_le(pixel),
+        };
+
+        let value = add_error(options.luma.luma(pixel_to_rgb(pixel)), error);
+
+        let white = value >= threshold;
+        *byte |= (white as u8) << (7 - x % 8);
+
+        Some([if white { value - (255 << ERROR_SHIFT) } else { value }])
+    });
+
+    Ok(row_size * spec.height as usize)
+}
@@ -0,0 +1,81 @@
+use crate::common::pixel::LumaCoefficients;
+use crate::encodes::options::Dither;
+
+/// デコード時のオプション
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{DecodeOptions, LumaCoefficients};
+///
+/// let options = DecodeOptions {
+///     luma: LumaCoefficients::Bt709,
+///     ..DecodeOptions::new()
+/// };
+///
+/// assert_eq!(options.luma, LumaCoefficients::Bt709);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// 輝度を求める際の係数
+    ///
+    /// `ColorType::L8`、`ColorType::La88`および`ColorType::Mono1`へのデコードに適用されます。
+    pub luma: LumaCoefficients,
+
+    /// 順序ディザリングの種類
+    ///
+    /// `ColorType::Rgb332`へのデコードに適用されます。
+    /// しきい値の位置は画像の左上を原点とします。
+    pub dither: Dither,
+
+    /// 白にする輝度の閾値
+    ///
+    /// `ColorType::Mono1`へのデコードで、輝度がこの値以上のピクセルは白になります。
+    /// 順序ディザリングを適用する場合、しきい値の中央がこの値になります。
+    pub mono_threshold: u8,
+
+    /// 順序ディザリングの種類
+    ///
+    /// `ColorType::Mono1`へのデコードに適用されます。
+    /// しきい値の位置は画像の左上を原点とします。
+    pub mono_dither: Dither,
+
+    /// 透明色のピクセルのビット
+    ///
+    /// `ColorType::Mono1`へのデコードで、透明色のピクセルは`true`の場合は白、`false`の場合は黒になります。
+    pub mono_background: bool,
+
+    /// sRGBの伝達関数を適用するかどうか
+    ///
+    /// `ColorType::RgbF32`と`ColorType::RgbaF32`へのデコードで、`true`の場合はRGBを線形値に変換し、
+    /// `false`の場合は 0 - 255 をそのまま 0.0 - 1.0 に対応させます。
+    pub srgb_transfer: bool,
+}
+
+impl DecodeOptions {
+    /// `mono_threshold`の既定値
+    pub const DEFAULT_MONO_THRESHOLD: u8 = 128;
+
+    /// 既定値の`DecodeOptions`を作成します。
+    ///
+    /// `luma`は`LumaCoefficients::Bt601`、`dither`は`Dither::None`、`mono_threshold`は[`DEFAULT_MONO_THRESHOLD`](Self::DEFAULT_MONO_THRESHOLD)、
+    /// `mono_dither`は`Dither::None`、`mono_background`は`true`（白）、`srgb_transfer`は`true`になります。
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            luma: LumaCoefficients::Bt601,
+            dither: Dither::None,
+            mono_threshold: Self::DEFAULT_MONO_THRESHOLD,
+            mono_dither: Dither::None,
+            mono_background: true,
+            srgb_transfer: true,
+        }
+    }
+}
+
+impl Default for DecodeOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,305 @@
+use crate::common::color::ColorType;
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::PIXEL_BYTES;
+use crate::common::spec::ImageSpec;
+use crate::decodes::{decode_header, logic};
+use crate::decodes::options::DecodeOptions;
+use crate::error::{Error, Result};
+
+/// 回転および反転時に一度に処理するピクセル数
+const BLOCK_PIXELS: usize = 64;
+
+/// デコード時に適用する画像の向き
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// 変換しない
+    Normal,
+
+    /// 時計回りに90度回転
+    Rotate90,
+
+    /// 180度回転
+    Rotate180,
+
+    /// 時計回りに270度回転
+    Rotate270,
+
+    /// 左右反転
+    FlipHorizontal,
+
+    /// 上下反転
+    FlipVertical,
+}
+
+impl Orientation {
+    /// 変換によって幅と高さが入れ替わるかどうかを返します。
+    #[inline(always)]
+    pub const fn swaps_dimensions(self) -> bool {
+        matches!(self, Orientation::Rotate90 | Orientation::Rotate270)
+    }
+
+    /// 変換後の画像の`ImageSpec`を返します。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use limg_core::{ImageSpec, Orientation};
+    ///
+    /// let spec = ImageSpec::new(320, 240);
+    /// let oriented_spec = Orientation::Rotate90.oriented_spec(&spec);
+    ///
+    /// assert_eq!(oriented_spec.width, 240);
+    /// assert_eq!(oriented_spec.height, 320);
+    /// ```
+    #[inline]
+    pub const fn oriented_spec(self, spec: &ImageSpec) -> ImageSpec {
+        if self.swaps_dimensions() {
+            ImageSpec { width: spec.height, height: spec.width, ..*spec }
+        } else {
+            *spec
+        }
+    }
+}
+
+/// `data`と`color_type`からLimg形式データを`orientation`の向きに変換しながらデコードし、`buf`バッファに書き込みます。
+///
+/// エラーではなかった場合、変換後の`ImageSpec`と書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+///
+/// `buf`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{decode_oriented, ColorType, Orientation};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// # let mut buf = vec![0u8; 0];
+/// let (oriented_spec, written_size) = decode_oriented(&data, &mut buf, ColorType::Rgb565, Orientation::Rotate90)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_oriented(data: &[u8], buf: &mut [u8], color_type: ColorType, orientation: Orientation) -> Result<(ImageSpec, usize)> {
+    decode_oriented_with_options(data, buf, color_type, orientation, &DecodeOptions::new())
+}
+
+/// `data`と`color_type`から`options`を使用してLimg形式データを`orientation`の向きに変換しながらデコードし、`buf`バッファに書き込みます。
+///
+/// 順序ディザリングのしきい値は変換後の画像上のピクセル位置で決まります。
+///
+/// エラーではなかった場合、変換後の`ImageSpec`と書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+///
+/// `buf`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{decode_oriented_with_options, ColorType, DecodeOptions, Dither, Orientation};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// # let mut buf = vec![0u8; 0];
+/// let options = DecodeOptions { dither: Dither::Bayer4x4, ..DecodeOptions::new() };
+/// let (oriented_spec, written_size) = decode_oriented_with_options(&data, &mut buf, ColorType::Rgb332, Orientation::Rotate90, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_oriented_with_options(data: &[u8], buf: &mut [u8], color_type: ColorType, orientation: Orientation, options: &DecodeOptions) -> Result<(ImageSpec, usize)> {
+    let spec = decode_header(data)?;
+
+    let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
+    decode_data_oriented_with_options(data, buf, &spec, color_type, orientation, options)
+}
+
+/// `data`と`spec`、`color_type`から色データを`orientation`の向きに変換しながらデコードし、`buf`バッファに書き込みます。
+///
+/// エラーではなかった場合、変換後の`ImageSpec`と書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{decode_data_oriented, ColorType, ImageSpec, Orientation};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(2, 1);
+/// let data = [0x00, 0x00, 0xFF, 0xFF];
+///
+/// let mut buf = [0u8; 4];
+/// let (oriented_spec, _) = decode_data_oriented(&data, &mut buf, &spec, ColorType::Rgb565, Orientation::Rotate90)?;
+///
+/// assert_eq!((oriented_spec.width, oriented_spec.height), (1, 2));
+/// assert_eq!(buf, [0x00, 0x00, 0xFF, 0xFF]);
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_data_oriented(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, orientation: Orientation) -> Result<(ImageSpec, usize)> {
+    decode_data_oriented_with_options(data, buf, spec, color_type, orientation, &DecodeOptions::new())
+}
+
+/// `data`と`spec`、`color_type`から`options`を使用して色データを`orientation`の向きに変換しながらデコードし、`buf`バッファに書き込みます。
+///
+/// 順序ディザリングのしきい値は変換後の画像上のピクセル位置で決まります。
+///
+/// エラーではなかった場合、変換後の`ImageSpec`と書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{decode_data_oriented_with_options, ColorType, DecodeOptions, ImageSpec, Orientation};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(2, 1);
+/// let data = [0x00, 0x00, 0xFF, 0xFF];
+///
+/// let mut buf = [0u8; 2];
+/// let (oriented_spec, _) = decode_data_oriented_with_options(&data, &mut buf, &spec, ColorType::Rgb332, Orientation::Rotate270, &DecodeOptions::new())?;
+///
+/// assert_eq!((oriented_spec.width, oriented_spec.height), (1, 2));
+/// assert_eq!(buf, [0xFF, 0x00]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode_data_oriented_with_options(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, orientation: Orientation, options: &DecodeOptions) -> Result<(ImageSpec, usize)> {
+    let num_pixels = spec.num_pixels();
+
+    if data.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    if !color_type.is_byte_addressable() {
+        return Err(Error::UnsupportedColorType);
+    }
+
+    if buf.len() < color_type.bytes_per_pixel() * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    unsafe { decode_oriented_unchecked(data.as_ptr(), buf.as_mut_ptr(), spec, options, color_type, orientation); }
+
+    Ok((orientation.oriented_spec(spec), color_type.bytes_per_pixel() * num_pixels))
+}
+
+unsafe fn decode_oriented_unchecked(data: *const u8, buf: *mut u8, spec: &ImageSpec, options: &DecodeOptions, color_type: ColorType, orientation: Orientation) {
+    let width = spec.width as usize;
+    let height = spec.height as usize;
+    let row_size = color_type.bytes_per_pixel() * width;
+    let column_size = color_type.bytes_per_pixel() * height;
+
+    // 順序ディザリングのしきい値が変換後の位置で決まるよう、出力の1行ずつデコードする
+    unsafe {
+        match orientation {
+            Orientation::Normal => {
+                logic::decode_logic(data, buf, spec, options, color_type);
+            },
+            Orientation::Rotate180 => {
+                for y in 0..height {
+                    decode_reversed(data.add(PIXEL_BYTES * width * (height - 1 - y)), buf.add(row_size * y), spec, options, color_type, width, y);
+                }
+            },
+            Orientation::FlipHorizontal => {
+                for y in 0..height {
+                    decode_reversed(data.add(PIXEL_BYTES * width * y), buf.add(row_size * y), spec, options, color_type, width, y);
+                }
+            },
+            Orientation::FlipVertical => {
+                for y in 0..height {
+                    logic::decode_row_logic(data.add(PIXEL_BYTES * width * y), buf.add(row_size * (height - 1 - y)), spec, options, color_type, width, 0, height - 1 - y);
+                }
+            },
+            Orientation::Rotate90 => {
+                // 出力のx行目は入力のx列目を下から読んだもの
+                for x in 0..width {
+                    let start = width * (height - 1) + x;
+                    decode_gathered(data, start, -(width as isize), buf.add(column_size * x), spec, options, color_type, height, x);
+                }
+            },
+            Orientation::Rotate270 => {
+                // 出力のx行目は入力の(width - 1 - x)列目を上から読んだもの
+                for x in 0..width {
+                    let start = width - 1 - x;
+                    decode_gathered(data, start, width as isize, buf.add(column_size * x), spec, options, color_type, height, x);
+                }
+            },
+        }
+    }
+}
+
+/// `data`の`num_pixels`ピクセルを逆順にデコードし、出力の`y`行目の先頭から書き込みます。
+#[allow(clippy::too_many_arguments)]
+unsafe fn decode_reversed(data: *const u8, mut buf: *mut u8, spec: &ImageSpec, options: &DecodeOptions, color_type: ColorType, num_pixels: usize, y: usize) {
+    let mut block = [0u16; BLOCK_PIXELS];
+    let mut data = unsafe { data.add(PIXEL_BYTES * num_pixels) };
+    let mut remaining = num_pixels;
+
+    while remaining > 0 {
+        let block_pixels = remaining.min(BLOCK_PIXELS);
+
+        unsafe {
+            data = data.sub(PIXEL_BYTES * block_pixels);
+
+            logic::reverse_pixels(data, block.as_mut_ptr().cast::<u8>(), block_pixels);
+            buf = buf.add(logic::decode_row_logic(block.as_ptr().cast::<u8>(), buf, spec, options, color_type, block_pixels, num_pixels - remaining, y));
+        }
+
+        remaining -= block_pixels;
+    }
+}
+
+/// `data`の`start`ピクセル目から`step`ピクセル間隔で`num_pixels`ピクセルを集めてデコードし、出力の`y`行目の先頭から書き込みます。
+#[allow(clippy::too_many_arguments)]
+unsafe fn decode_gathered(data: *const u8, start: usize, step: isize, mut buf: *mut u8, spec: &ImageSpec, options: &DecodeOptions, color_type: ColorType, num_pixels: usize, y: usize) {
+    let mut block = [0u16; BLOCK_PIXELS];
+    let data = data.cast::<u16>();
+    let mut index = start as isize;
+    let mut remaining = num_pixels;
+
+    while remaining > 0 {
+        let block_pixels = remaining.min(BLOCK_PIXELS);
+
+        for pixel in block[..block_pixels].iter_mut() {
+            *pixel = unsafe { data.offset(index).read_unaligned() };
+            index += step;
+        }
+
+        unsafe {
+            buf = buf.add(logic::decode_row_logic(block.as_ptr().cast::<u8>(), buf, spec, options, color_type, block_pixels, num_pixels - remaining, y));
+        }
+
+        remaining -= block_pixels;
+    }
+}
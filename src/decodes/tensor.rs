@@ -0,0 +1,300 @@
+use crate::common::color::ColorType;
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::PIXEL_BYTES;
+use crate::common::spec::ImageSpec;
+use crate::decodes::{decode_header, logic};
+use crate::decodes::options::DecodeOptions;
+use crate::error::{Error, Result};
+
+/// 浮動小数点数に変換する際にまとめてデコードするピクセル数
+const BLOCK_PIXELS: usize = 64;
+
+/// テンソルのチャンネル数
+const NUM_CHANNELS: usize = 3;
+
+/// テンソルの要素の並び順
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TensorLayout {
+    /// R、G、Bの順に各チャンネルの平面を並べる（`[R平面, G平面, B平面]`）
+    #[default]
+    Chw,
+
+    /// ピクセルごとにR、G、Bを並べる（`[r, g, b, r, g, b, ...]`）
+    Hwc,
+}
+
+/// `f32`のテンソルに適用するチャンネルごとの正規化
+///
+/// 0.0 - 1.0 に変換した値`v`は`(v - mean) / std`になります。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::TensorNormalization;
+///
+/// let normalization = TensorNormalization::new([0.5; 3], [0.5; 3]);
+///
+/// assert_eq!(normalization.apply(0, 255), 1.0);
+/// assert_eq!(normalization.apply(0, 0), -1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TensorNormalization {
+    /// R、G、Bの平均
+    pub mean: [f32; 3],
+
+    /// R、G、Bの標準偏差
+    ///
+    /// 0.0 の場合、結果は無限大または NaN になります。
+    pub std: [f32; 3],
+}
+
+impl TensorNormalization {
+    /// ImageNetの平均と標準偏差
+    pub const IMAGENET: Self = Self::new([0.485, 0.456, 0.406], [0.229, 0.224, 0.225]);
+
+    /// `mean`と`std`から`TensorNormalization`を作成します。
+    #[inline]
+    pub const fn new(mean: [f32; 3], std: [f32; 3]) -> Self {
+        Self { mean, std }
+    }
+
+    /// `channel`（0: R、1: G、2: B）の8ビットの`value`を正規化します。
+    #[inline]
+    pub fn apply(&self, channel: usize, value: u8) -> f32 {
+        (value as f32 / 255.0 - self.mean[channel]) / self.std[channel]
+    }
+}
+
+/// `spec`のテンソルの要素数を取得します。
+///
+/// 要素数は（3 * 総ピクセル数）です。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{tensor_len, ImageSpec};
+///
+/// assert_eq!(tensor_len(&ImageSpec::new(224, 224)), 3 * 224 * 224);
+/// ```
+#[inline(always)]
+pub const fn tensor_len(spec: &ImageSpec) -> usize {
+    NUM_CHANNELS * spec.num_pixels()
+}
+
+/// `data`からLimg形式データを`layout`の並び順の`u8`のテンソルにデコードし、`buf`バッファに書き込みます。
+///
+/// 透明色のピクセルは透明色の色になります。
+///
+/// エラーではなかった場合、`ImageSpec`と書き込まれた要素数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+///
+/// `buf`の長さが[`tensor_len`]未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{decode_header, decode_tensor, tensor_len, TensorLayout};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// let spec = decode_header(&data)?;
+///
+/// let mut buf = vec![0u8; tensor_len(&spec)];
+/// decode_tensor(&data, &mut buf, TensorLayout::Chw)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_tensor(data: &[u8], buf: &mut [u8], layout: TensorLayout) -> Result<(ImageSpec, usize)> {
+    let spec = decode_header(data)?;
+
+    let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
+    let written_len = decode_data_tensor(data, buf, &spec, layout)?;
+
+    Ok((spec, written_len))
+}
+
+/// `data`と`spec`から色データを`layout`の並び順の`u8`のテンソルにデコードし、`buf`バッファに書き込みます。
+///
+/// 透明色のピクセルは透明色の色になります。
+///
+/// エラーではなかった場合、書き込まれた要素数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが[`tensor_len`]未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{decode_data_tensor, rgb_to_pixel, ImageSpec, TensorLayout};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(2, 1);
+/// let data = [rgb_to_pixel([255, 0, 0]).to_le_bytes(), rgb_to_pixel([0, 0, 255]).to_le_bytes()].concat();
+///
+/// let mut buf = [0u8; 6];
+/// decode_data_tensor(&data, &mut buf, &spec, TensorLayout::Chw)?;
+///
+/// assert_eq!(buf, [255, 0, 0, 0, 0, 255]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode_data_tensor(data: &[u8], buf: &mut [u8], spec: &ImageSpec, layout: TensorLayout) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if data.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::InputBufferTooSmall);
+    }
+    if buf.len() < tensor_len(spec) {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    unsafe {
+        match layout {
+            TensorLayout::Chw => {
+                let r = buf.as_mut_ptr();
+                logic::decode_planes_logic(data.as_ptr(), r, r.add(num_pixels), r.add(2 * num_pixels), spec, num_pixels);
+            },
+            TensorLayout::Hwc => {
+                logic::decode_pixels_logic(data.as_ptr(), buf.as_mut_ptr(), spec, &DecodeOptions::new(), ColorType::Rgb888, num_pixels);
+            },
+        }
+    }
+
+    Ok(tensor_len(spec))
+}
+
+/// `data`からLimg形式データを`layout`の並び順の`f32`のテンソルにデコードし、`buf`バッファに書き込みます。
+///
+/// 各要素は 0.0 - 1.0 に変換され、`normalization`が指定されている場合はさらに正規化されます。
+/// 透明色のピクセルは透明色の色になります。
+///
+/// エラーではなかった場合、`ImageSpec`と書き込まれた要素数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+///
+/// `buf`の長さが[`tensor_len`]未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{decode_header, decode_tensor_f32, tensor_len, TensorLayout, TensorNormalization};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("image.limg")?;
+/// let spec = decode_header(&data)?;
+///
+/// let mut buf = vec![0f32; tensor_len(&spec)];
+/// decode_tensor_f32(&data, &mut buf, TensorLayout::Chw, Some(&TensorNormalization::IMAGENET))?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn decode_tensor_f32(data: &[u8], buf: &mut [f32], layout: TensorLayout, normalization: Option<&TensorNormalization>) -> Result<(ImageSpec, usize)> {
+    let spec = decode_header(data)?;
+
+    let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
+    let written_len = decode_data_tensor_f32(data, buf, &spec, layout, normalization)?;
+
+    Ok((spec, written_len))
+}
+
+/// `data`と`spec`から色データを`layout`の並び順の`f32`のテンソルにデコードし、`buf`バッファに書き込みます。
+///
+/// 各要素は 0.0 - 1.0 に変換され、`normalization`が指定されている場合はさらに正規化されます。
+/// 透明色のピクセルは透明色の色になります。
+///
+/// エラーではなかった場合、書き込まれた要素数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが[`tensor_len`]未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{decode_data_tensor_f32, rgb_to_pixel, ImageSpec, TensorLayout, TensorNormalization};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(1, 1);
+/// let data = rgb_to_pixel([255, 0, 255]).to_le_bytes();
+///
+/// let mut buf = [0f32; 3];
+/// let normalization = TensorNormalization::new([0.5; 3], [0.5; 3]);
+/// decode_data_tensor_f32(&data, &mut buf, &spec, TensorLayout::Hwc, Some(&normalization))?;
+///
+/// assert_eq!(buf, [1.0, -1.0, 1.0]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode_data_tensor_f32(data: &[u8], buf: &mut [f32], spec: &ImageSpec, layout: TensorLayout, normalization: Option<&TensorNormalization>) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if data.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::InputBufferTooSmall);
+    }
+    if buf.len() < tensor_len(spec) {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    // 8ビットの値ごとの変換結果
+    let mut table = [[0f32; 256]; NUM_CHANNELS];
+    for (channel, table) in table.iter_mut().enumerate() {
+        for (value, element) in table.iter_mut().enumerate() {
+            *element = match normalization {
+                Some(normalization) => normalization.apply(channel, value as u8),
+                None => value as f32 / 255.0,
+            };
+        }
+    }
+
+    let mut block = [0u8; NUM_CHANNELS * BLOCK_PIXELS];
+    let mut start = 0;
+
+    while start < num_pixels {
+        let block_pixels = (num_pixels - start).min(BLOCK_PIXELS);
+        let data = unsafe { data.as_ptr().add(PIXEL_BYTES * start) };
+
+        match layout {
+            TensorLayout::Chw => {
+                let r = block.as_mut_ptr();
+                unsafe { logic::decode_planes_logic(data, r, r.add(BLOCK_PIXELS), r.add(2 * BLOCK_PIXELS), spec, block_pixels); }
+
+                for (channel, plane) in block.chunks_exact(BLOCK_PIXELS).enumerate() {
+                    let dst = &mut buf[num_pixels * channel + start..num_pixels * channel + start + block_pixels];
+
+                    for (element, &value) in dst.iter_mut().zip(&plane[..block_pixels]) {
+                        *element = table[channel][value as usize];
+                    }
+                }
+            },
+            TensorLayout::Hwc => {
+                unsafe { logic::decode_pixels_logic(data, block.as_mut_ptr(), spec, &DecodeOptions::new(), ColorType::Rgb888, block_pixels); }
+
+                let dst = &mut buf[NUM_CHANNELS * start..NUM_CHANNELS * (start + block_pixels)];
+
+                for (i, (element, &value)) in dst.iter_mut().zip(&block[..NUM_CHANNELS * block_pixels]).enumerate() {
+                    *element = table[i % NUM_CHANNELS][value as usize];
+                }
+            },
+        }
+
+        start += block_pixels;
+    }
+
+    Ok(tensor_len(spec))
+}
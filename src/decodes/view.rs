@@ -0,0 +1,215 @@
+use ::core::iter::FusedIterator;
+use ::core::slice::ChunksExact;
+
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::{pixel_to_rgb, PIXEL_BYTES};
+use crate::common::spec::{ImageSpec, PixelEndian};
+use crate::decodes::decode_header;
+use crate::error::{Error, Result};
+
+/// Limg形式データを複製せずに参照するビュー
+///
+/// 出力バッファを確保せずに、エンコード済みのデータから直接ピクセルを読み出せます。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{ImageSpec, LimgView, ColorType, encode, encoded_size};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(2, 1);
+/// let mut data = vec![0u8; encoded_size(&spec)];
+/// encode(&[255, 0, 0, 0, 0, 255], &mut data, &spec, ColorType::Rgb888)?;
+///
+/// let view = LimgView::new(&data)?;
+/// assert_eq!(view.rgb(0, 0), [255, 0, 0]);
+/// assert_eq!(view.rgb(1, 0), [0, 0, 255]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct LimgView<'a> {
+    data: &'a [u8],
+    spec: ImageSpec,
+}
+
+impl<'a> LimgView<'a> {
+    /// ヘッダを含むLimg形式データから`LimgView`を作成します。
+    ///
+    /// # Errors
+    ///
+    /// `data`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+    ///
+    /// ヘッダが不正なデータだった場合、`Error::UnsupportedFormat`を返します。
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        let spec = decode_header(data)?;
+
+        let data = unsafe { data.get_unchecked(HEADER_SIZE..) };
+        Self::from_data(data, spec)
+    }
+
+    /// ヘッダを除いた色データと`spec`から`LimgView`を作成します。
+    ///
+    /// # Errors
+    ///
+    /// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+    ///
+    /// `data`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+    #[inline]
+    pub fn from_data(data: &'a [u8], spec: ImageSpec) -> Result<Self> {
+        let data_size = PIXEL_BYTES * spec.num_pixels();
+
+        if data_size == 0 {
+            return Err(Error::ZeroImageDimensions);
+        }
+        if data.len() < data_size {
+            return Err(Error::InputBufferTooSmall);
+        }
+
+        let data = unsafe { data.get_unchecked(..data_size) };
+
+        Ok(Self { data, spec })
+    }
+
+    /// 画像の`ImageSpec`を返します。
+    #[inline(always)]
+    pub const fn spec(&self) -> &ImageSpec {
+        &self.spec
+    }
+
+    /// ヘッダを除いた色データを返します。
+    #[inline(always)]
+    pub const fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// 座標`(x, y)`のピクセルをエンディアン補正済みのRGB565形式で返します。
+    ///
+    /// 座標が範囲外の場合、`None`を返します。
+    #[inline]
+    pub fn get_pixel(&self, x: u16, y: u16) -> Option<u16> {
+        if x >= self.spec.width || y >= self.spec.height {
+            return None;
+        }
+
+        let index = PIXEL_BYTES * (y as usize * self.spec.width as usize + x as usize);
+        let bytes = unsafe { [*self.data.get_unchecked(index), *self.data.get_unchecked(index + 1)] };
+
+        let pixel = match self.spec.pixel_endian {
+            PixelEndian::Big => u16::from_be_bytes(bytes),
+            PixelEndian::Little => u16::from_le_bytes(bytes),
+        };
+
+        Some(pixel)
+    }
+
+    /// 座標`(x, y)`のピクセルをエンディアン補正済みのRGB565形式で返します。
+    ///
+    /// # Panics
+    ///
+    /// 座標が画像の範囲外の場合、パニックします。
+    #[inline]
+    pub fn pixel(&self, x: u16, y: u16) -> u16 {
+        match self.get_pixel(x, y) {
+            Some(pixel) => pixel,
+            None => panic!("pixel coordinates out of bounds"),
+        }
+    }
+
+    /// 座標`(x, y)`のピクセルを`[R, G, B]`配列で返します。
+    ///
+    /// # Panics
+    ///
+    /// 座標が画像の範囲外の場合、パニックします。
+    #[inline]
+    pub fn rgb(&self, x: u16, y: u16) -> [u8; 3] {
+        pixel_to_rgb(self.pixel(x, y))
+    }
+
+    /// 座標`(x, y)`のピクセルが透明色かどうかを返します。
+    ///
+    /// 透明色が指定されていない場合、常に`false`です。
+    ///
+    /// # Panics
+    ///
+    /// 座標が画像の範囲外の場合、パニックします。
+    #[inline]
+    pub fn is_transparent(&self, x: u16, y: u16) -> bool {
+        let pixel = self.pixel(x, y);
+        self.spec.transparent_color == Some(pixel)
+    }
+
+    /// `y`行目の色データを返します。
+    ///
+    /// スライスはエンディアン補正前のバイト列で、長さは（[`PIXEL_BYTES`] * 幅）です。
+    ///
+    /// # Panics
+    ///
+    /// `y`が画像の高さ以上の場合、パニックします。
+    #[inline]
+    pub fn row(&self, y: u16) -> &'a [u8] {
+        assert!(y < self.spec.height, "row out of bounds");
+
+        let row_size = PIXEL_BYTES * self.spec.width as usize;
+        let start = row_size * y as usize;
+        unsafe { self.data.get_unchecked(start..start + row_size) }
+    }
+
+    /// 各行の色データを上から順に返すイテレータを作成します。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use limg_core::{ImageSpec, LimgView, PIXEL_BYTES};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let spec = ImageSpec::new(3, 2);
+    /// let data = [0u8; 12];
+    ///
+    /// let view = LimgView::from_data(&data, spec)?;
+    /// for row in view.rows() {
+    ///     assert_eq!(row.len(), PIXEL_BYTES * 3);
+    /// }
+    /// assert_eq!(view.rows().len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn rows(&self) -> Rows<'a> {
+        Rows {
+            chunks: self.data.chunks_exact(PIXEL_BYTES * self.spec.width as usize),
+        }
+    }
+}
+
+/// [`LimgView::rows`]が返す行イテレータ
+#[derive(Clone, Debug)]
+pub struct Rows<'a> {
+    chunks: ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Rows<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back()
+    }
+}
+
+impl ExactSizeIterator for Rows<'_> {}
+
+impl FusedIterator for Rows<'_> {}
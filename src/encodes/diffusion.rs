@@ -0,0 +1,352 @@
+use crate::common::color::{Argb, ChannelOrder, ColorType};
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::{expand_bits, pixel_to_rgb, rgb332_to_pixel, rgb555_to_pixel, Quantization, PIXEL_BYTES, RGB555_A_BIT};
+use crate::common::spec::{ImageSpec, PixelEndian};
+use crate::common::wide::narrow_pixel;
+use crate::encodes::{encode_data_with_options, encode_header};
+use crate::encodes::options::EncodeOptions;
+use crate::error::{Error, Result};
+
+/// 誤差を保持するチャンネル数
+const NUM_CHANNELS: usize = 3;
+
+/// 誤差の小数部のビット数
+///
+/// 拡散時の切り捨てで小さな誤差が失われないよう、誤差は1/16単位で保持します。
+pub(crate) const ERROR_SHIFT: u32 = 4;
+
+/// 誤差拡散に使用する拡散係数
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffusionKernel {
+    /// Floyd–Steinberg
+    ///
+    /// 誤差を右に7/16、左下に3/16、下に5/16、右下に1/16拡散します。
+    FloydSteinberg,
+
+    /// Atkinson
+    ///
+    /// 誤差の6/8を右、2つ右、左下、下、右下、2つ下に1/8ずつ拡散します。
+    Atkinson,
+}
+
+/// 誤差拡散の設定
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{DiffusionKernel, ErrorDiffusion};
+///
+/// let diffusion = ErrorDiffusion {
+///     serpentine: false,
+///     ..ErrorDiffusion::new(DiffusionKernel::Atkinson)
+/// };
+///
+/// assert_eq!(diffusion.kernel, DiffusionKernel::Atkinson);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorDiffusion {
+    /// 拡散係数
+    pub kernel: DiffusionKernel,
+
+    /// 蛇行走査
+    ///
+    /// `true`の場合、奇数行を右から左に走査して、拡散方向の偏りによる模様を抑えます。
+    pub serpentine: bool,
+}
+
+impl ErrorDiffusion {
+    /// `kernel`を指定して`ErrorDiffusion`を作成します。
+    ///
+    /// `serpentine`は`true`になります。
+    #[inline]
+    pub const fn new(kernel: DiffusionKernel) -> Self {
+        Self { kernel, serpentine: true }
+    }
+}
+
+/// 幅が`width`の画像を誤差拡散でエンコードするために必要な作業バッファの長さ（要素数）を返します。
+///
+/// 作業バッファには2行分のRGBの誤差が保持されます。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::diffusion_scratch_len;
+///
+/// let scratch = vec![0i16; diffusion_scratch_len(320)];
+/// assert_eq!(scratch.len(), 2 * 3 * (320 + 2));
+/// ```
+#[inline(always)]
+pub const fn diffusion_scratch_len(width: u16) -> usize {
+    // 左右の拡散先のために両端に1ピクセルずつ余白を設ける
+    2 * NUM_CHANNELS * (width as usize + 2)
+}
+
+/// `data`と`spec`、`color_type`から誤差拡散を適用してLimg形式データをエンコードし、`buf`に書き込みます。
+///
+/// `scratch`は誤差の保持に使用され、内容は上書きされます。
+/// `options.dither`と`options.quantization`は無視されます。`ColorType::Rgb565`、`ColorType::Bgr565`、`ColorType::Rgb565Be`、`ColorType::Bgr565Be`、`ColorType::Mono1`からのエンコードには誤差拡散は適用されません。
+///
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+///
+/// `data`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `scratch`の長さが[`diffusion_scratch_len`]未満の場合、`Error::ScratchBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{ColorType, DiffusionKernel, EncodeOptions, ErrorDiffusion, ImageSpec};
+/// use limg_core::{diffusion_scratch_len, encode_error_diffused, encoded_size};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(64, 64);
+/// let data = vec![100u8; 3 * spec.num_pixels()];
+///
+/// let mut buf = vec![0u8; encoded_size(&spec)];
+/// let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+/// let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+///
+/// encode_error_diffused(&data, &mut buf, &spec, ColorType::Rgb888, &EncodeOptions::new(), &diffusion, &mut scratch)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_error_diffused(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, options: &EncodeOptions, diffusion: &ErrorDiffusion, scratch: &mut [i16]) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if num_pixels == 0 {
+        return Err(Error::ZeroImageDimensions);
+    }
+    if buf.len() < HEADER_SIZE + PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    let (header_buf, data_buf) = buf.split_at_mut(HEADER_SIZE);
+
+    let written_size = encode_data_error_diffused(data, data_buf, spec, color_type, options, diffusion, scratch)?;
+    let written_size = encode_header(header_buf, spec)? + written_size;
+
+    debug_assert_eq!(written_size, HEADER_SIZE + PIXEL_BYTES * num_pixels);
+
+    Ok(written_size)
+}
+
+/// `data`と`spec`、`color_type`から誤差拡散を適用して色データをエンコードし、`buf`に書き込みます。
+///
+/// `scratch`は誤差の保持に使用され、内容は上書きされます。
+/// `options.dither`と`options.quantization`は無視されます。`ColorType::Rgb565`、`ColorType::Bgr565`、`ColorType::Rgb565Be`、`ColorType::Bgr565Be`、`ColorType::Mono1`からのエンコードには誤差拡散は適用されません。
+///
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `data`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// `scratch`の長さが[`diffusion_scratch_len`]未満の場合、`Error::ScratchBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{ColorType, DiffusionKernel, EncodeOptions, ErrorDiffusion, ImageSpec};
+/// use limg_core::{diffusion_scratch_len, encode_data_error_diffused, PIXEL_BYTES};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::new(4, 1);
+/// let data = [0x84; 4 * 3];
+///
+/// let mut buf = [0u8; PIXEL_BYTES * 4];
+/// let mut scratch = [0i16; diffusion_scratch_len(4)];
+/// let diffusion = ErrorDiffusion::new(DiffusionKernel::Atkinson);
+///
+/// encode_data_error_diffused(&data, &mut buf, &spec, ColorType::Rgb888, &EncodeOptions::new(), &diffusion, &mut scratch)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_data_error_diffused(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, options: &EncodeOptions, diffusion: &ErrorDiffusion, scratch: &mut [i16]) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if data.len() < color_type.bytes_per_row(spec.width as usize) * spec.height as usize {
+        return Err(Error::InputBufferTooSmall);
+    }
+    if buf.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+    if scratch.len() < diffusion_scratch_len(spec.width) {
+        return Err(Error::ScratchBufferTooSmall);
+    }
+
+    if matches!(color_type, ColorType::Rgb565 | ColorType::Bgr565 | ColorType::Rgb565Be | ColorType::Bgr565Be | ColorType::Mono1) {
+        return encode_data_with_options(data, buf, spec, color_type, options);
+    }
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+    let width = spec.width as usize;
+
+    // 透明にするピクセルのアルファ値の閾値
+    let transparent = spec.transparent_color.map(|transparent_color| (transparent_color, options.alpha_threshold));
+
+    diffuse_errors::<NUM_CHANNELS>(width, spec.height as usize, diffusion, scratch, |x, y, errors| {
+        let i = width * y + x;
+        let (rgb, alpha) = read_pixel(&data[bytes_per_pixel * i..bytes_per_pixel * (i + 1)], color_type, options.srgb_transfer);
+
+        if let Some((transparent_color, alpha_threshold)) = transparent
+            && alpha < alpha_threshold {
+            write_pixel(buf, i, transparent_color, spec.pixel_endian);
+            return None;
+        }
+
+        let mut quantized = [0u16; NUM_CHANNELS];
+        let mut errors = errors;
+
+        for channel in 0..NUM_CHANNELS {
+            let bits = if channel == 1 { 6 } else { 5 };
+            let value = add_error(rgb[channel], errors[channel]);
+
+            quantized[channel] = Quantization::Nearest.quantize(((value + (1 << (ERROR_SHIFT - 1))) >> ERROR_SHIFT) as u8, bits) as u16;
+            errors[channel] = value - ((expand_bits(quantized[channel] as u8, bits) as i32) << ERROR_SHIFT);
+        }
+
+        write_pixel(buf, i, (quantized[0] << 11) | (quantized[1] << 5) | quantized[2], spec.pixel_endian);
+
+        Some(errors)
+    });
+
+    Ok(PIXEL_BYTES * num_pixels)
+}
+
+/// `value`に拡散された`error`を加え、[`ERROR_SHIFT`]ビットの小数部を持つ 0 - 255 の値に制限します。
+#[inline(always)]
+pub(crate) fn add_error(value: u8, error: i32) -> i32 {
+    (((value as i32) << ERROR_SHIFT) + error).clamp(0, 255 << ERROR_SHIFT)
+}
+
+/// `width`×`height`のピクセルを`diffusion`の順に走査し、量子化で生じた誤差を周囲のピクセルに拡散します。
+///
+/// `quantize`は`(x, y)`のピクセルに拡散された`N`チャンネル分の誤差を受け取り、量子化した結果を書き込んで新たに生じた誤差を返します。
+/// 誤差を受け取らず、拡散もしないピクセル（透明なピクセル）の場合は`None`を返します。
+///
+/// `scratch`の長さは（2 * `N` * (`width` + 2)）以上である必要があり、内容は上書きされます。
+pub(crate) fn diffuse_errors<const N: usize>(width: usize, height: usize, diffusion: &ErrorDiffusion, scratch: &mut [i16], mut quantize: impl FnMut(usize, usize, [i32; N]) -> Option<[i32; N]>) {
+    // 左右の拡散先のために両端に1ピクセルずつ余白を設ける
+    let row_len = N * (width + 2);
+
+    let (mut current, mut next) = scratch[..2 * row_len].split_at_mut(row_len);
+    current.fill(0);
+    next.fill(0);
+
+    for y in 0..height {
+        // 余白には前の行の拡散先が残っているため、次の行の分を初期化する
+        next[..N].fill(0);
+        next[N * (width + 1)..].fill(0);
+
+        let reverse = diffusion.serpentine && y % 2 == 1;
+
+        // 同じ行の1つ先と2つ先に拡散する誤差
+        let mut forward_1 = [0i32; N];
+        let mut forward_2 = [0i32; N];
+
+        for i in 0..width {
+            let x = if reverse { width - 1 - i } else { i };
+
+            // 誤差のインデックス（余白の分ずらす）
+            let center = N * (x + 1);
+            let (behind, ahead) = if reverse { (center + N, center - N) } else { (center - N, center + N) };
+
+            let Some(errors) = quantize(x, y, core::array::from_fn(|channel| current[center + channel] as i32 + forward_1[channel])) else {
+                // 透明なピクセルは誤差を受け取らず、拡散もしない
+                current[center..center + N].fill(0);
+                forward_1 = forward_2;
+                forward_2 = [0; N];
+                continue;
+            };
+
+            for channel in 0..N {
+                let error = errors[channel];
+
+                match diffusion.kernel {
+                    DiffusionKernel::FloydSteinberg => {
+                        forward_1[channel] = forward_2[channel] + error * 7 / 16;
+                        forward_2[channel] = 0;
+
+                        next[behind + channel] += (error * 3 / 16) as i16;
+                        next[center + channel] += (error * 5 / 16) as i16;
+                        next[ahead + channel] += (error / 16) as i16;
+
+                        current[center + channel] = 0;
+                    },
+                    DiffusionKernel::Atkinson => {
+                        let error = error / 8;
+
+                        forward_1[channel] = forward_2[channel] + error;
+                        forward_2[channel] = error;
+
+                        next[behind + channel] += error as i16;
+                        next[center + channel] += error as i16;
+                        next[ahead + channel] += error as i16;
+
+                        // 読み終えた位置に2行下への誤差を保持する
+                        current[center + channel] = error as i16;
+                    },
+                }
+            }
+        }
+
+        (current, next) = (next, current);
+    }
+}
+
+/// `color_type`の1ピクセル分の`src`から`[R, G, B]`配列とアルファ値を読み取ります。
+/// 
+/// アルファを持たない色データタイプの場合、アルファ値は 255 になります。
+#[inline(always)]
+fn read_pixel(src: &[u8], color_type: ColorType, srgb_transfer: bool) -> ([u8; 3], u8) {
+    match color_type {
+        ColorType::Rgb888 => ([src[0], src[1], src[2]], u8::MAX),
+        ColorType::Rgba8888 => ([src[0], src[1], src[2]], src[3]),
+        ColorType::Bgr888 => ([src[2], src[1], src[0]], u8::MAX),
+        ColorType::Bgra8888 => ([src[2], src[1], src[0]], src[3]),
+        ColorType::L8 => ([src[0]; 3], u8::MAX),
+        ColorType::La88 => ([src[0]; 3], src[1]),
+        ColorType::Argb8888 => Argb::unpack_rgba([src[0], src[1], src[2], src[3]]),
+        ColorType::Xrgb8888 => (Argb::unpack_rgba([src[0], src[1], src[2], src[3]]).0, u8::MAX),
+        ColorType::Rgb555 => (pixel_to_rgb(rgb555_to_pixel(u16::from_ne_bytes([src[0], src[1]]))), u8::MAX),
+        ColorType::Argb1555 => {
+            let argb = u16::from_ne_bytes([src[0], src[1]]);
+            let alpha = if argb & RGB555_A_BIT != 0 { u8::MAX } else { 0 };
+            (pixel_to_rgb(rgb555_to_pixel(argb)), alpha)
+        },
+        ColorType::Rgb444 => {
+            let rgb = u16::from_ne_bytes([src[0], src[1]]);
+            ([expand_bits(((rgb >> 8) & 0x0F) as u8, 4), expand_bits(((rgb >> 4) & 0x0F) as u8, 4), expand_bits((rgb & 0x0F) as u8, 4)], u8::MAX)
+        },
+        ColorType::Rgba4444 => {
+            let rgba = u16::from_ne_bytes([src[0], src[1]]);
+            ([expand_bits((rgba >> 12) as u8, 4), expand_bits(((rgba >> 8) & 0x0F) as u8, 4), expand_bits(((rgba >> 4) & 0x0F) as u8, 4)], expand_bits((rgba & 0x0F) as u8, 4))
+        },
+        ColorType::Rgb332 => (pixel_to_rgb(rgb332_to_pixel(src[0])), u8::MAX),
+        ColorType::Rgb16 | ColorType::Rgba16 | ColorType::RgbF32 | ColorType::RgbaF32 => {
+            let [r, g, b, a] = narrow_pixel(src, color_type, srgb_transfer);
+            ([r, g, b], a)
+        },
+        // 誤差拡散を適用しないため呼び出されない
+        ColorType::Rgb565 | ColorType::Bgr565 | ColorType::Rgb565Be | ColorType::Bgr565Be | ColorType::Mono1 => unreachable!(),
+    }
+}
+
+#[inline(always)]
+fn write_pixel(buf: &mut [u8], index: usize, pixel: u16, pixel_endian: PixelEndian) {
+    let bytes = match pixel_endian {
+        PixelEndian::Big => pixel.to_be_bytes(),
+        PixelEndian::Little => pixel.to_le_bytes(),
+    };
+
+    buf[PIXEL_BYTES * index..PIXEL_BYTES * (index + 1)].copy_from_slice(&bytes);
+}
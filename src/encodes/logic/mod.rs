@@ -3,33 +3,326 @@ mod scalar;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 mod x86_64;
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-pub use x86_64::encode_logic;
+pub use x86_64::{encode_logic, encode_pixels_logic, encode_dither_logic, encode_collision_free_logic, encode_yuyv_logic, encode_nv12_logic};
 
 #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
-pub use scalar::encode_logic;
+pub use scalar::{encode_logic, encode_pixels_logic, encode_dither_logic, encode_collision_free_logic, encode_yuyv_logic, encode_nv12_logic};
+
+use crate::encodes::options::{BAYER_4X4, BAYER_8X8};
+
+/// 透明色と衝突したピクセルの補正に使用する値（Gの最下位ビット）
+pub const COLLISION_XOR: u16 = 0x0020;
+
+/// 順序ディザリングで1行分の各ピクセルに加算する値
+/// 
+/// 各配列の`i`番目は行の`i`ピクセル目に加算する値で、8ピクセル周期で繰り返します。
+/// SIMDで周期内の任意の位置から16ピクセル分を読み込めるように、32ピクセル分を格納しています。
+#[derive(Clone, Copy)]
+pub struct DitherRow {
+    /// RとBに加算する値（0 - 7）
+    pub rb: [u16; 32],
+
+    /// Gに加算する値（0 - 3）
+    pub g: [u16; 32],
+}
+
+impl DitherRow {
+    /// `dither`の`y`行目の`DitherRow`を作成します。
+    pub const fn new(dither: crate::encodes::options::Dither, y: usize) -> Self {
+        use crate::encodes::options::Dither;
+
+        let mut rb = [0; 32];
+        let mut g = [0; 32];
+
+        let mut i = 0;
+        while i < 32 {
+            // 減色で切り捨てられる幅（R, B: 8, G: 4）をしきい値で等分する
+            (rb[i], g[i]) = match dither {
+                Dither::None => (0, 0),
+                Dither::Bayer4x4 => {
+                    let threshold = BAYER_4X4[y % 4][i % 4] as u16;
+                    (threshold / 2, threshold / 4)
+                },
+                Dither::Bayer8x8 => {
+                    let threshold = BAYER_8X8[y % 8][i % 8] as u16;
+                    (threshold / 8, threshold / 16)
+                },
+            };
+
+            i += 1;
+        }
+
+        Self { rb, g }
+    }
+
+    /// `n`ピクセル目から始まる`DitherRow`を返します。
+    pub const fn shifted(&self, n: usize) -> Self {
+        let mut rb = [0; 32];
+        let mut g = [0; 32];
+
+        let mut i = 0;
+        while i < 32 {
+            rb[i] = self.rb[(i + n) % 8];
+            g[i] = self.g[(i + n) % 8];
+            i += 1;
+        }
+
+        Self { rb, g }
+    }
+}
+
+/// 順序ディザリングを適用する色データタイプかどうかを返します。
+///
+/// それ以外の色データタイプでは`Dither`は無視されます。
+#[inline(always)]
+pub const fn is_dither_target(color_type: crate::common::color::ColorType) -> bool {
+    use crate::common::color::ColorType;
+
+    matches!(color_type, ColorType::Rgb888 | ColorType::Rgba8888 | ColorType::Bgr888 | ColorType::Bgra8888 | ColorType::Argb8888 | ColorType::Xrgb8888 |
+        ColorType::Rgb16 | ColorType::Rgba16 | ColorType::RgbF32 | ColorType::RgbaF32)
+}
+
+/// YUVからRGBに変換する際の係数
+/// 
+/// 各係数は8ビットの小数部を持つ固定小数点数です。
+/// 積は16bitレーンに収まらないため、SIMDでは32bitレーンで積和を求めます。
+#[derive(Clone, Copy)]
+pub struct YuvCoefficients {
+    /// Yに乗算する値
+    pub y: i16,
+
+    /// 乗算前にYから減算する値
+    pub y_offset: i16,
+
+    /// Rに加算するVの係数
+    pub r_v: i16,
+
+    /// Gから減算するUの係数
+    pub g_u: i16,
+
+    /// Gから減算するVの係数
+    pub g_v: i16,
+
+    /// Bに加算するUの係数
+    pub b_u: i16,
+}
+
+impl YuvCoefficients {
+    /// `coefficients`の規格と`range`の範囲の`YuvCoefficients`を作成します。
+    pub const fn new(coefficients: crate::common::pixel::LumaCoefficients, range: crate::encodes::yuv::YuvRange) -> Self {
+        use crate::common::pixel::LumaCoefficients;
+        use crate::encodes::yuv::YuvRange;
+
+        // 実数の係数を256倍して丸めた値
+        let (y, y_offset) = match range {
+            YuvRange::Full => (256, 0),
+            YuvRange::Limited => (298, 16),
+        };
+
+        let (r_v, g_u, g_v, b_u) = match (coefficients, range) {
+            (LumaCoefficients::Bt601, YuvRange::Full) => (359, 88, 183, 454),
+            (LumaCoefficients::Bt601, YuvRange::Limited) => (409, 100, 208, 516),
+            (LumaCoefficients::Bt709, YuvRange::Full) => (403, 48, 120, 475),
+            (LumaCoefficients::Bt709, YuvRange::Limited) => (459, 55, 136, 541),
+        };
+
+        Self { y, y_offset, r_v, g_u, g_v, b_u }
+    }
+
+    /// 1ピクセル分のY, U, Vから`[R, G, B]`配列を求めます。
+    /// 
+    /// 範囲外の値は 0 - 255 に丸められます。
+    #[inline(always)]
+    pub fn to_rgb(self, y: u8, u: u8, v: u8) -> [u8; 3] {
+        let y = (y as i32 - self.y_offset as i32) * self.y as i32 + 128;
+        let u = u as i32 - 128;
+        let v = v as i32 - 128;
+
+        [
+            ((y + self.r_v as i32 * v) >> 8).clamp(0, 255) as u8,
+            ((y - self.g_u as i32 * u - self.g_v as i32 * v) >> 8).clamp(0, 255) as u8,
+            ((y + self.b_u as i32 * u) >> 8).clamp(0, 255) as u8,
+        ]
+    }
+}
 
 macro_rules! encode_logic_fn {
     ($(#[$attr:meta])*) => {
 
+      #[inline(always)]
+      pub unsafe fn encode_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::encodes::options::EncodeOptions, color_type: crate::common::color::ColorType) -> usize {
+        if color_type == crate::common::color::ColorType::Mono1 {
+            // 各行はバイト境界までパディングされているため1行ずつエンコードする
+            let width = spec.width as usize;
+
+            for y in 0..spec.height as usize {
+                unsafe {
+                    let data = data.add(color_type.bytes_per_row(width) * y);
+                    let buf = buf.add(crate::common::pixel::PIXEL_BYTES * width * y);
+                    encode_pixels_logic(data, buf, spec, options, color_type, width);
+                }
+            }
+
+            return crate::common::pixel::PIXEL_BYTES * spec.num_pixels();
+        }
+
+        if options.dither == crate::encodes::options::Dither::None || !crate::encodes::logic::is_dither_target(color_type) {
+            return unsafe { encode_pixels_logic(data, buf, spec, options, color_type, spec.num_pixels()) };
+        }
+
+        // ディザリングはピクセル位置に依存するため1行ずつエンコードする
+        let width = spec.width as usize;
+
+        for y in 0..spec.height as usize {
+            unsafe {
+                let data = data.add(color_type.bytes_per_pixel() * width * y);
+                let buf = buf.add(crate::common::pixel::PIXEL_BYTES * width * y);
+                encode_dither_logic(data, buf, spec, options, color_type, width, 0, y, 0);
+            }
+        }
+
+        crate::common::pixel::PIXEL_BYTES * spec.num_pixels()
+      }
+
+      /// `spec`のエンディアンと`options`を使用して、`num_pixels`分のピクセルをエンコードします。
       #[inline(never)]
       $(#[$attr])*
-      pub unsafe fn encode_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, color_type: crate::common::color::ColorType) -> usize {
-		let num_pixels = spec.num_pixels();
+      pub unsafe fn encode_pixels_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::encodes::options::EncodeOptions, color_type: crate::common::color::ColorType, num_pixels: usize) -> usize {
+        if let Some(narrow_type) = crate::common::wide::narrow_color_type(color_type) {
+            // チャンネルあたり8ビットに変換してからエンコードする
+            unsafe {
+                crate::common::wide::for_each_narrow_block(data, color_type, num_pixels, options.srgb_transfer, |block, offset, block_pixels| {
+                    encode_pixels_logic(block, buf.add(crate::common::pixel::PIXEL_BYTES * offset), spec, options, narrow_type, block_pixels);
+                });
+            }
+
+            return crate::common::pixel::PIXEL_BYTES * num_pixels;
+        }
 
         unsafe {
 			match spec.pixel_endian {
 				crate::common::spec::PixelEndian::Big => {
 					match color_type {
-						crate::common::color::ColorType::Rgb888 => encode_from_rgb888_be(data, buf, num_pixels),
+						crate::common::color::ColorType::Rgb888 => encode_from_rgb888_be::<crate::common::color::Rgb>(data, buf, num_pixels, options.quantization),
 						crate::common::color::ColorType::Rgb565 => encode_from_rgb565_be(data, buf, num_pixels),
-						crate::common::color::ColorType::Rgba8888 => encode_from_rgba8888_be(data, buf, num_pixels),
+						crate::common::color::ColorType::Rgba8888 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba8888_alpha_be::<crate::common::color::Rgb>(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_rgba8888_be::<crate::common::color::Rgb>(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::Bgr888 => encode_from_rgb888_be::<crate::common::color::Bgr>(data, buf, num_pixels, options.quantization),
+						crate::common::color::ColorType::Bgra8888 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba8888_alpha_be::<crate::common::color::Bgr>(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_rgba8888_be::<crate::common::color::Bgr>(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::L8 => encode_from_l8_be(data, buf, num_pixels, options.quantization),
+						crate::common::color::ColorType::La88 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_la88_alpha_be(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_la88_be(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::Argb8888 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba8888_alpha_be::<crate::common::color::Argb>(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_rgba8888_be::<crate::common::color::Argb>(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::Xrgb8888 => encode_from_rgba8888_be::<crate::common::color::Argb>(data, buf, num_pixels, options.quantization),
+						crate::common::color::ColorType::Rgb555 => encode_from_rgb555_be(data, buf, num_pixels),
+						crate::common::color::ColorType::Argb1555 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_argb1555_alpha_be(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels);
+							} else {
+								encode_from_rgb555_be(data, buf, num_pixels);
+							}
+						},
+						crate::common::color::ColorType::Rgb444 => encode_from_rgb444_be(data, buf, num_pixels),
+						crate::common::color::ColorType::Rgba4444 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba4444_alpha_be(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels);
+							} else {
+								encode_from_rgba4444_be(data, buf, num_pixels);
+							}
+						},
+						crate::common::color::ColorType::Rgb332 => encode_from_rgb332_be(data, buf, num_pixels),
+						crate::common::color::ColorType::Bgr565 => encode_from_bgr565_be(data, buf, num_pixels),
+						// 入力と格納順が同じビッグエンディアンなのでそのまま複製する
+						crate::common::color::ColorType::Rgb565Be => encode_from_rgb565_direct(data, buf, num_pixels),
+						crate::common::color::ColorType::Bgr565Be => encode_from_bgr565be_be(data, buf, num_pixels),
+						crate::common::color::ColorType::Mono1 => encode_from_mono1(data, buf, num_pixels),
+						// チャンネルあたり8ビットに変換済み
+						crate::common::color::ColorType::Rgb16 | crate::common::color::ColorType::Rgba16 |
+						crate::common::color::ColorType::RgbF32 | crate::common::color::ColorType::RgbaF32 => unreachable!(),
 					}
 				},
 				crate::common::spec::PixelEndian::Little => {
 					match color_type {
-						crate::common::color::ColorType::Rgb888 => encode_from_rgb888_le(data, buf, num_pixels),
+						crate::common::color::ColorType::Rgb888 => encode_from_rgb888_le::<crate::common::color::Rgb>(data, buf, num_pixels, options.quantization),
 						crate::common::color::ColorType::Rgb565 => encode_from_rgb565_le(data, buf, num_pixels),
-						crate::common::color::ColorType::Rgba8888 => encode_from_rgba8888_le(data, buf, num_pixels),
+						crate::common::color::ColorType::Rgba8888 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba8888_alpha_le::<crate::common::color::Rgb>(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_rgba8888_le::<crate::common::color::Rgb>(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::Bgr888 => encode_from_rgb888_le::<crate::common::color::Bgr>(data, buf, num_pixels, options.quantization),
+						crate::common::color::ColorType::Bgra8888 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba8888_alpha_le::<crate::common::color::Bgr>(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_rgba8888_le::<crate::common::color::Bgr>(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::L8 => encode_from_l8_le(data, buf, num_pixels, options.quantization),
+						crate::common::color::ColorType::La88 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_la88_alpha_le(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_la88_le(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::Argb8888 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba8888_alpha_le::<crate::common::color::Argb>(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels, options.quantization);
+							} else {
+								encode_from_rgba8888_le::<crate::common::color::Argb>(data, buf, num_pixels, options.quantization);
+							}
+						},
+						crate::common::color::ColorType::Xrgb8888 => encode_from_rgba8888_le::<crate::common::color::Argb>(data, buf, num_pixels, options.quantization),
+						crate::common::color::ColorType::Rgb555 => encode_from_rgb555_le(data, buf, num_pixels),
+						crate::common::color::ColorType::Argb1555 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_argb1555_alpha_le(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels);
+							} else {
+								encode_from_rgb555_le(data, buf, num_pixels);
+							}
+						},
+						crate::common::color::ColorType::Rgb444 => encode_from_rgb444_le(data, buf, num_pixels),
+						crate::common::color::ColorType::Rgba4444 => {
+							if let Some(transparent_color) = spec.transparent_color {
+								encode_from_rgba4444_alpha_le(data, buf, transparent_color, options.alpha_threshold, 0, num_pixels);
+							} else {
+								encode_from_rgba4444_le(data, buf, num_pixels);
+							}
+						},
+						crate::common::color::ColorType::Rgb332 => encode_from_rgb332_le(data, buf, num_pixels),
+						crate::common::color::ColorType::Bgr565 => encode_from_bgr565_le(data, buf, num_pixels),
+						// ビッグエンディアンの入力を格納順のリトルエンディアンにバイトを入れ替える
+						crate::common::color::ColorType::Rgb565Be => encode_from_rgb565_swap(data, buf, num_pixels),
+						crate::common::color::ColorType::Bgr565Be => encode_from_bgr565be_le(data, buf, num_pixels),
+						crate::common::color::ColorType::Mono1 => encode_from_mono1(data, buf, num_pixels),
+						// チャンネルあたり8ビットに変換済み
+						crate::common::color::ColorType::Rgb16 | crate::common::color::ColorType::Rgba16 |
+						crate::common::color::ColorType::RgbF32 | crate::common::color::ColorType::RgbaF32 => unreachable!(),
 					}
 				},
 			}
@@ -37,6 +330,194 @@ macro_rules! encode_logic_fn {
     
         crate::common::pixel::PIXEL_BYTES * num_pixels
     }
+
+      /// `spec`のエンディアンと`coefficients`、`quantization`を使用して、YUYVの1行の`num_pixels`分のピクセルをエンコードします。
+      #[inline(never)]
+      $(#[$attr])*
+      pub unsafe fn encode_yuyv_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, coefficients: &crate::encodes::logic::YuvCoefficients, quantization: crate::common::pixel::Quantization, num_pixels: usize) {
+        unsafe {
+            match spec.pixel_endian {
+                crate::common::spec::PixelEndian::Big => encode_from_yuyv_be(data, buf, num_pixels, coefficients, quantization),
+                crate::common::spec::PixelEndian::Little => encode_from_yuyv_le(data, buf, num_pixels, coefficients, quantization),
+            }
+        }
+      }
+
+      /// `spec`のエンディアンと`coefficients`、`quantization`を使用して、NV12のY平面とUV平面の1行の`num_pixels`分のピクセルをエンコードします。
+      #[inline(never)]
+      $(#[$attr])*
+      pub unsafe fn encode_nv12_logic(y_data: *const u8, uv_data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, coefficients: &crate::encodes::logic::YuvCoefficients, quantization: crate::common::pixel::Quantization, num_pixels: usize) {
+        unsafe {
+            match spec.pixel_endian {
+                crate::common::spec::PixelEndian::Big => encode_from_nv12_be(y_data, uv_data, buf, num_pixels, coefficients, quantization),
+                crate::common::spec::PixelEndian::Little => encode_from_nv12_le(y_data, uv_data, buf, num_pixels, coefficients, quantization),
+            }
+        }
+      }
+
+      /// `spec`のエンディアンと`options`を使用して`(x, y)`の位置から`num_pixels`分のピクセルを順序ディザリングしながらエンコードします。
+      /// 
+      /// しきい値は`(x, y)`から始まる位置のものが使われます。ピクセルは`y`行目からはみ出してはいけません。
+      /// 
+      /// `collision_xor`が 0 ではない場合、透明色と一致する不透明なピクセルを`collision_xor`との排他的論理和に置き換え、置き換えたピクセル数を返します。
+      #[inline(never)]
+      #[allow(clippy::too_many_arguments)]
+      $(#[$attr])*
+      pub unsafe fn encode_dither_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::encodes::options::EncodeOptions, color_type: crate::common::color::ColorType, num_pixels: usize, x: usize, y: usize, collision_xor: u16) -> usize {
+        if let Some(narrow_type) = crate::common::wide::narrow_color_type(color_type) {
+            // ブロックはディザリングの周期の倍数なので、ブロックごとにしきい値の位置がずれない
+            let mut collisions = 0;
+
+            unsafe {
+                crate::common::wide::for_each_narrow_block(data, color_type, num_pixels, options.srgb_transfer, |block, offset, block_pixels| {
+                    collisions += encode_dither_logic(block, buf.add(crate::common::pixel::PIXEL_BYTES * offset), spec, options, narrow_type, block_pixels, x + offset, y, collision_xor);
+                });
+            }
+
+            return collisions;
+        }
+
+        let dither = crate::encodes::logic::DitherRow::new(options.dither, y).shifted(x);
+
+        unsafe {
+            if matches!(color_type, crate::common::color::ColorType::Rgba8888 | crate::common::color::ColorType::Bgra8888 | crate::common::color::ColorType::Argb8888 | crate::common::color::ColorType::Xrgb8888) {
+                // Xはアルファ値として扱わない
+                let alpha_threshold = if color_type == crate::common::color::ColorType::Xrgb8888 { 0 } else { options.alpha_threshold };
+
+                let (transparent_color, alpha_threshold, collision_xor) = match spec.transparent_color {
+                    Some(transparent_color) => (transparent_color, alpha_threshold, collision_xor),
+                    None => (0, 0, 0),
+                };
+
+                let collisions = match (color_type, spec.pixel_endian) {
+                    (crate::common::color::ColorType::Rgba8888, crate::common::spec::PixelEndian::Big) => encode_from_rgba8888_dither_be::<crate::common::color::Rgb>(data, buf, transparent_color, alpha_threshold, collision_xor, num_pixels, &dither),
+                    (crate::common::color::ColorType::Rgba8888, crate::common::spec::PixelEndian::Little) => encode_from_rgba8888_dither_le::<crate::common::color::Rgb>(data, buf, transparent_color, alpha_threshold, collision_xor, num_pixels, &dither),
+                    (crate::common::color::ColorType::Bgra8888, crate::common::spec::PixelEndian::Big) => encode_from_rgba8888_dither_be::<crate::common::color::Bgr>(data, buf, transparent_color, alpha_threshold, collision_xor, num_pixels, &dither),
+                    (crate::common::color::ColorType::Bgra8888, crate::common::spec::PixelEndian::Little) => encode_from_rgba8888_dither_le::<crate::common::color::Bgr>(data, buf, transparent_color, alpha_threshold, collision_xor, num_pixels, &dither),
+                    (_, crate::common::spec::PixelEndian::Big) => encode_from_rgba8888_dither_be::<crate::common::color::Argb>(data, buf, transparent_color, alpha_threshold, collision_xor, num_pixels, &dither),
+                    (_, crate::common::spec::PixelEndian::Little) => encode_from_rgba8888_dither_le::<crate::common::color::Argb>(data, buf, transparent_color, alpha_threshold, collision_xor, num_pixels, &dither),
+                };
+
+                return if collision_xor != 0 { collisions } else { 0 };
+            }
+
+            match (color_type, spec.pixel_endian) {
+                (crate::common::color::ColorType::Rgb888, crate::common::spec::PixelEndian::Big) => encode_from_rgb888_dither_be::<crate::common::color::Rgb>(data, buf, num_pixels, &dither),
+                (crate::common::color::ColorType::Rgb888, crate::common::spec::PixelEndian::Little) => encode_from_rgb888_dither_le::<crate::common::color::Rgb>(data, buf, num_pixels, &dither),
+                (crate::common::color::ColorType::Bgr888, crate::common::spec::PixelEndian::Big) => encode_from_rgb888_dither_be::<crate::common::color::Bgr>(data, buf, num_pixels, &dither),
+                (crate::common::color::ColorType::Bgr888, crate::common::spec::PixelEndian::Little) => encode_from_rgb888_dither_le::<crate::common::color::Bgr>(data, buf, num_pixels, &dither),
+                _ => { encode_pixels_logic(data, buf, spec, options, color_type, num_pixels); },
+            }
+
+            match spec.transparent_color {
+                Some(transparent_color) if collision_xor != 0 => {
+                    replace_collisions(buf, spec, transparent_color, collision_xor, num_pixels)
+                },
+                _ => 0,
+            }
+        }
+      }
+
+      /// `spec`のエンディアンと`options`を使用してすべてのピクセルをエンコードし、透明色と一致する不透明なピクセルを補正します。
+      /// 
+      /// 補正したピクセル数を返します。
+      #[inline(never)]
+      $(#[$attr])*
+      pub unsafe fn encode_collision_free_logic(data: *const u8, buf: *mut u8, spec: &crate::common::spec::ImageSpec, options: &crate::encodes::options::EncodeOptions, color_type: crate::common::color::ColorType) -> usize {
+        let num_pixels = spec.num_pixels();
+        let collision_xor = crate::encodes::logic::COLLISION_XOR;
+
+        let Some(transparent_color) = spec.transparent_color else {
+            unsafe { encode_logic(data, buf, spec, options, color_type); }
+            return 0;
+        };
+
+        if options.dither != crate::encodes::options::Dither::None && crate::encodes::logic::is_dither_target(color_type) {
+            let width = spec.width as usize;
+            let mut collisions = 0;
+
+            for y in 0..spec.height as usize {
+                unsafe {
+                    let data = data.add(color_type.bytes_per_pixel() * width * y);
+                    let buf = buf.add(crate::common::pixel::PIXEL_BYTES * width * y);
+                    collisions += encode_dither_logic(data, buf, spec, options, color_type, width, 0, y, collision_xor);
+                }
+            }
+
+            return collisions;
+        }
+
+        unsafe {
+            match (color_type, spec.pixel_endian) {
+                (crate::common::color::ColorType::Rgba8888, crate::common::spec::PixelEndian::Big) => {
+                    encode_from_rgba8888_alpha_be::<crate::common::color::Rgb>(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::Rgba8888, crate::common::spec::PixelEndian::Little) => {
+                    encode_from_rgba8888_alpha_le::<crate::common::color::Rgb>(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::Bgra8888, crate::common::spec::PixelEndian::Big) => {
+                    encode_from_rgba8888_alpha_be::<crate::common::color::Bgr>(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::La88, crate::common::spec::PixelEndian::Big) => {
+                    encode_from_la88_alpha_be(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::Argb8888, crate::common::spec::PixelEndian::Big) => {
+                    encode_from_rgba8888_alpha_be::<crate::common::color::Argb>(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::Argb1555, crate::common::spec::PixelEndian::Big) => {
+                    encode_from_argb1555_alpha_be(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels)
+                },
+                (crate::common::color::ColorType::Rgba4444, crate::common::spec::PixelEndian::Big) => {
+                    encode_from_rgba4444_alpha_be(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels)
+                },
+                (crate::common::color::ColorType::Bgra8888, crate::common::spec::PixelEndian::Little) => {
+                    encode_from_rgba8888_alpha_le::<crate::common::color::Bgr>(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::La88, crate::common::spec::PixelEndian::Little) => {
+                    encode_from_la88_alpha_le(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::Argb8888, crate::common::spec::PixelEndian::Little) => {
+                    encode_from_rgba8888_alpha_le::<crate::common::color::Argb>(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels, options.quantization)
+                },
+                (crate::common::color::ColorType::Argb1555, crate::common::spec::PixelEndian::Little) => {
+                    encode_from_argb1555_alpha_le(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels)
+                },
+                (crate::common::color::ColorType::Rgba4444, crate::common::spec::PixelEndian::Little) => {
+                    encode_from_rgba4444_alpha_le(data, buf, transparent_color, options.alpha_threshold, collision_xor, num_pixels)
+                },
+                (crate::common::color::ColorType::Rgba16 | crate::common::color::ColorType::RgbaF32, _) => {
+                    let mut collisions = 0;
+
+                    crate::common::wide::for_each_narrow_block(data, color_type, num_pixels, options.srgb_transfer, |block, offset, block_pixels| {
+                        let buf = buf.add(crate::common::pixel::PIXEL_BYTES * offset);
+
+                        collisions += match spec.pixel_endian {
+                            crate::common::spec::PixelEndian::Big => encode_from_rgba8888_alpha_be::<crate::common::color::Rgb>(block, buf, transparent_color, options.alpha_threshold, collision_xor, block_pixels, options.quantization),
+                            crate::common::spec::PixelEndian::Little => encode_from_rgba8888_alpha_le::<crate::common::color::Rgb>(block, buf, transparent_color, options.alpha_threshold, collision_xor, block_pixels, options.quantization),
+                        };
+                    });
+
+                    collisions
+                },
+                _ => {
+                    // すべてのピクセルが不透明なので、エンコード後に透明色を置き換える
+                    encode_logic(data, buf, spec, options, color_type);
+                    replace_collisions(buf, spec, transparent_color, collision_xor, num_pixels)
+                },
+            }
+        }
+      }
+
+      /// エンコード済みの`num_pixels`分のピクセルのうち透明色と一致するものを`collision_xor`との排他的論理和に置き換え、置き換えたピクセル数を返します。
+      #[inline(always)]
+      unsafe fn replace_collisions(buf: *mut u8, spec: &crate::common::spec::ImageSpec, transparent_color: u16, collision_xor: u16, num_pixels: usize) -> usize {
+        let (from, to) = match spec.pixel_endian {
+            crate::common::spec::PixelEndian::Big => (transparent_color.to_be(), (transparent_color ^ collision_xor).to_be()),
+            crate::common::spec::PixelEndian::Little => (transparent_color.to_le(), (transparent_color ^ collision_xor).to_le()),
+        };
+
+        unsafe { replace_pixels(buf, from, to, num_pixels) }
+      }
   };
 }
 
@@ -116,4 +597,46 @@ mod tests {
         250, 250, 250, 250,
         255, 255, 255, 255
     ];
+
+    #[test]
+    fn yuv_coefficients_test() {
+        use crate::common::pixel::LumaCoefficients;
+        use crate::encodes::logic::YuvCoefficients;
+        use crate::encodes::yuv::YuvRange;
+
+        for (luma, (kr, kb)) in [(LumaCoefficients::Bt601, (0.299, 0.114)), (LumaCoefficients::Bt709, (0.2126, 0.0722))] {
+            for range in [YuvRange::Full, YuvRange::Limited] {
+                let coefficients = YuvCoefficients::new(luma, range);
+
+                let (y_scale, c_scale, y_offset) = match range {
+                    YuvRange::Full => (1.0, 1.0, 0.0),
+                    YuvRange::Limited => (255.0 / 219.0, 255.0 / 224.0, 16.0),
+                };
+                let kg = 1.0 - kr - kb;
+
+                // 実数のBT.601/BT.709の変換式との差は、8ビットへの丸め（0.5）と係数の丸めの誤差を合わせて 1.0 以下
+                for y in 0..=255u8 {
+                    for u in (0..=255u8).step_by(3) {
+                        for v in (0..=255u8).step_by(3) {
+                            let y_value = (y as f64 - y_offset) * y_scale;
+                            let u_value = (u as f64 - 128.0) * c_scale;
+                            let v_value = (v as f64 - 128.0) * c_scale;
+
+                            let expected = [
+                                y_value + 2.0 * (1.0 - kr) * v_value,
+                                y_value - 2.0 * kb * (1.0 - kb) / kg * u_value - 2.0 * kr * (1.0 - kr) / kg * v_value,
+                                y_value + 2.0 * (1.0 - kb) * u_value,
+                            ];
+                            let rgb = coefficients.to_rgb(y, u, v);
+
+                            for channel in 0..3 {
+                                let diff = (rgb[channel] as f64 - expected[channel].clamp(0.0, 255.0)).abs();
+                                assert!(diff <= 1.0, "{luma:?} {range:?} {y} {u} {v}: {rgb:?} {expected:?}");
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
-use crate::common::color::ColorType;
-use crate::common::pixel::{rgb_to_pixel, PIXEL_BYTES};
-use crate::encodes::logic::encode_logic_fn;
+use crate::common::color::{ChannelOrder, ColorType};
+use crate::common::pixel::{rgb332_to_pixel, rgb444_to_pixel, rgb555_to_pixel, rgb_to_pixel, rgb_to_pixel_with_quantization, swap_rb, Quantization, PIXEL_BYTES, RGB555_A_BIT};
+use crate::encodes::logic::{encode_logic_fn, DitherRow, YuvCoefficients};
 
 #[inline(always)]
 pub unsafe fn encode_from_rgb565_direct(data: *const u8, buf: *mut u8, num_pixels: usize) {
@@ -23,10 +23,59 @@ pub unsafe fn encode_from_rgb565_swap(data: *const u8, buf: *mut u8, num_pixels:
     }
 }
 
+/// 最上位ビットから詰められた1ビットの色データを白（0xFFFF）と黒（0x0000）のピクセルに展開します。
+/// 
+/// 白と黒はバイトを入れ替えても同じ値のため、エンディアンに依存しません。
+#[inline(always)]
+pub unsafe fn encode_from_mono1(data: *const u8, buf: *mut u8, num_pixels: usize) {
+    let mut buf = buf.cast::<u16>();
+
+    for i in 0..num_pixels {
+        unsafe {
+            let bit = (data.add(i / 8).read() >> (7 - i % 8)) & 1;
+            buf.write_unaligned(if bit != 0 { 0xFFFF } else { 0x0000 });
+
+            buf = buf.add(1);
+        }
+    }
+}
+
+/// `dither`の`index`ピクセル目の値を加算します。
+#[inline(always)]
+const fn dither_rgb(rgb: [u8; 3], dither: &DitherRow, index: usize) -> [u8; 3] {
+    let index = index % 8;
+
+    [
+        rgb[0].saturating_add(dither.rb[index] as u8),
+        rgb[1].saturating_add(dither.g[index] as u8),
+        rgb[2].saturating_add(dither.rb[index] as u8),
+    ]
+}
+
+/// エンコード済みの`num_pixels`分のピクセルのうち`from`と一致するものを`to`に置き換え、置き換えたピクセル数を返します。
+#[inline(always)]
+pub unsafe fn replace_pixels(buf: *mut u8, from: u16, to: u16, num_pixels: usize) -> usize {
+    let mut buf = buf.cast::<u16>();
+    let mut replaced = 0;
+
+    for _ in 0..num_pixels {
+        unsafe {
+            if buf.read_unaligned() == from {
+                buf.write_unaligned(to);
+                replaced += 1;
+            }
+
+            buf = buf.add(1);
+        }
+    }
+
+    replaced
+}
+
 macro_rules! encode_from_endian {
-    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident) => {
+    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident, $rgba8888_alpha: ident, $rgb888_dither: ident, $rgba8888_dither: ident, $l8: ident, $la88: ident, $la88_alpha: ident, $rgb555: ident, $argb1555_alpha: ident, $rgb444: ident, $rgba4444: ident, $rgba4444_alpha: ident, $rgb332: ident, $bgr565: ident, $bgr565be: ident, $yuyv: ident, $nv12: ident) => {
         #[inline(always)]
-        pub unsafe fn $rgb888(data: *const u8, buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgb888<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
             const COLOR_TYPE: ColorType = ColorType::Rgb888;
             
             let mut data = data.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
@@ -35,7 +84,7 @@ macro_rules! encode_from_endian {
             for _ in 0..num_pixels {
                 unsafe {
                     let rgb = data.read();
-                    let pixel = rgb_to_pixel(rgb).$endian_fn();
+                    let pixel = rgb_to_pixel_with_quantization(O::order(rgb), quantization).$endian_fn();
                     buf.write_unaligned(pixel);
         
                     data = data.add(1);
@@ -54,16 +103,115 @@ macro_rules! encode_from_endian {
         }
 
         #[inline(always)]
-        pub unsafe fn $rgba8888(data: *const u8, buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgba8888<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
+            const COLOR_TYPE: ColorType = ColorType::Rgba8888;
+
+            let mut data = data.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let (rgb, _) = O::unpack_rgba(data.read());
+                    let pixel = rgb_to_pixel_with_quantization(rgb, quantization).$endian_fn();
+                    buf.write_unaligned(pixel);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        /// アルファ値が`alpha_threshold`未満のピクセルを透明色に置き換え、透明色と一致する不透明なピクセルは`collision_xor`との排他的論理和に置き換えます。
+        /// 
+        /// 透明色と一致した不透明なピクセル数を返します。
+        #[inline(always)]
+        pub unsafe fn $rgba8888_alpha<O: ChannelOrder>(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize, quantization: Quantization) -> usize {
+            const COLOR_TYPE: ColorType = ColorType::Rgba8888;
+
+            let mut data = data.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
+            let mut buf = buf.cast::<u16>();
+            let mut collisions = 0;
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let (rgb, alpha) = O::unpack_rgba(data.read());
+                    let mut pixel = rgb_to_pixel_with_quantization(rgb, quantization);
+
+                    if alpha < alpha_threshold {
+                        pixel = transparent_color;
+                    } else if pixel == transparent_color {
+                        pixel ^= collision_xor;
+                        collisions += 1;
+                    }
+
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+
+            collisions
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgb888_dither<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize, dither: &DitherRow) {
+            const COLOR_TYPE: ColorType = ColorType::Rgb888;
+            
+            let mut data = data.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
+            let mut buf = buf.cast::<u16>();
+        
+            for i in 0..num_pixels {
+                unsafe {
+                    let rgb = data.read();
+                    let pixel = rgb_to_pixel(dither_rgb(O::order(rgb), dither, i)).$endian_fn();
+                    buf.write_unaligned(pixel);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        /// `dither`を加算してエンコードします。アルファ値と透明色の扱いは`encode_from_rgba8888_alpha_*`と同じです。
+        #[inline(always)]
+        pub unsafe fn $rgba8888_dither<O: ChannelOrder>(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize, dither: &DitherRow) -> usize {
             const COLOR_TYPE: ColorType = ColorType::Rgba8888;
 
             let mut data = data.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
             let mut buf = buf.cast::<u16>();
+            let mut collisions = 0;
+        
+            for i in 0..num_pixels {
+                unsafe {
+                    let (rgb, alpha) = O::unpack_rgba(data.read());
+                    let mut pixel = rgb_to_pixel(dither_rgb(rgb, dither, i));
+
+                    if alpha < alpha_threshold {
+                        pixel = transparent_color;
+                    } else if pixel == transparent_color {
+                        pixel ^= collision_xor;
+                        collisions += 1;
+                    }
+
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+
+            collisions
+        }
+        #[inline(always)]
+        pub unsafe fn $l8(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
+            let mut data = data;
+            let mut buf = buf.cast::<u16>();
         
             for _ in 0..num_pixels {
                 unsafe {
-                    let rgba = data.read();
-                    let pixel = rgb_to_pixel([rgba[0], rgba[1], rgba[2]]).$endian_fn();
+                    let l = data.read();
+                    let pixel = rgb_to_pixel_with_quantization([l, l, l], quantization).$endian_fn();
                     buf.write_unaligned(pixel);
         
                     data = data.add(1);
@@ -71,16 +219,258 @@ macro_rules! encode_from_endian {
                 }
             }
         }
+
+        #[inline(always)]
+        pub unsafe fn $la88(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+
+            let mut data = data.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let [l, _] = data.read();
+                    let pixel = rgb_to_pixel_with_quantization([l, l, l], quantization).$endian_fn();
+                    buf.write_unaligned(pixel);
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        /// アルファ値と透明色の扱いは`encode_from_rgba8888_alpha_*`と同じです。
+        #[inline(always)]
+        pub unsafe fn $la88_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize, quantization: Quantization) -> usize {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+
+            let mut data = data.cast::<[u8; COLOR_TYPE.bytes_per_pixel()]>();
+            let mut buf = buf.cast::<u16>();
+            let mut collisions = 0;
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let [l, a] = data.read();
+                    let mut pixel = rgb_to_pixel_with_quantization([l, l, l], quantization);
+
+                    if a < alpha_threshold {
+                        pixel = transparent_color;
+                    } else if pixel == transparent_color {
+                        pixel ^= collision_xor;
+                        collisions += 1;
+                    }
+
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+
+            collisions
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgb555(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = rgb555_to_pixel(data.read_unaligned());
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $argb1555_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize) -> usize {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+            let mut collisions = 0;
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let argb = data.read_unaligned();
+                    let mut pixel = rgb555_to_pixel(argb);
+
+                    // Aは 0 か 255 として閾値と比較する
+                    let a = if argb & RGB555_A_BIT != 0 { u8::MAX } else { 0 };
+
+                    if a < alpha_threshold {
+                        pixel = transparent_color;
+                    } else if pixel == transparent_color {
+                        pixel ^= collision_xor;
+                        collisions += 1;
+                    }
+
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+
+            collisions
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgb444(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let rgb = data.read_unaligned();
+                    let pixel = rgb444_to_pixel([((rgb >> 8) & 0x0F) as u8, ((rgb >> 4) & 0x0F) as u8, (rgb & 0x0F) as u8]);
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgba4444(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let rgba = data.read_unaligned();
+                    let pixel = rgb444_to_pixel([(rgba >> 12) as u8, ((rgba >> 8) & 0x0F) as u8, ((rgba >> 4) & 0x0F) as u8]);
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgba4444_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize) -> usize {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+            let mut collisions = 0;
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let rgba = data.read_unaligned();
+                    let mut pixel = rgb444_to_pixel([(rgba >> 12) as u8, ((rgba >> 8) & 0x0F) as u8, ((rgba >> 4) & 0x0F) as u8]);
+
+                    // Aはビット複製で8ビットに拡張して閾値と比較する
+                    let a = (rgba & 0x0F) as u8 * 0x11;
+
+                    if a < alpha_threshold {
+                        pixel = transparent_color;
+                    } else if pixel == transparent_color {
+                        pixel ^= collision_xor;
+                        collisions += 1;
+                    }
+
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+
+            collisions
+        }
+
+        #[inline(always)]
+        pub unsafe fn $rgb332(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data;
+            let mut buf = buf.cast::<u16>();
+
+            for _ in 0..num_pixels {
+                unsafe {
+                    buf.write_unaligned(rgb332_to_pixel(data.read()).$endian_fn());
+
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $bgr565(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = swap_rb(data.read_unaligned());
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $bgr565be(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data.cast::<u16>();
+            let mut buf = buf.cast::<u16>();
+        
+            for _ in 0..num_pixels {
+                unsafe {
+                    let pixel = swap_rb(u16::from_be(data.read_unaligned()));
+                    buf.write_unaligned(pixel.$endian_fn());
+        
+                    data = data.add(1);
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $yuyv(data: *const u8, buf: *mut u8, num_pixels: usize, coefficients: &YuvCoefficients, quantization: Quantization) {
+            let mut buf = buf.cast::<u16>();
+
+            for i in 0..num_pixels {
+                unsafe {
+                    // 2ピクセルの組でUとVを共有する
+                    let pair = data.add(4 * (i / 2));
+                    let rgb = coefficients.to_rgb(data.add(2 * i).read(), pair.add(1).read(), pair.add(3).read());
+                    buf.write_unaligned(rgb_to_pixel_with_quantization(rgb, quantization).$endian_fn());
+
+                    buf = buf.add(1);
+                }
+            }
+        }
+
+        #[inline(always)]
+        pub unsafe fn $nv12(y_data: *const u8, uv_data: *const u8, buf: *mut u8, num_pixels: usize, coefficients: &YuvCoefficients, quantization: Quantization) {
+            let mut buf = buf.cast::<u16>();
+
+            for i in 0..num_pixels {
+                unsafe {
+                    // 2ピクセルの組でUとVを共有する
+                    let uv = uv_data.add(2 * (i / 2));
+                    let rgb = coefficients.to_rgb(y_data.add(i).read(), uv.read(), uv.add(1).read());
+                    buf.write_unaligned(rgb_to_pixel_with_quantization(rgb, quantization).$endian_fn());
+
+                    buf = buf.add(1);
+                }
+            }
+        }
     };
 }
 
 encode_logic_fn!();
-encode_from_endian!("big", to_be, encode_from_rgb888_be, encode_from_rgb565_be, encode_from_rgba8888_be);
-encode_from_endian!("little", to_le, encode_from_rgb888_le, encode_from_rgb565_le, encode_from_rgba8888_le);
+encode_from_endian!("big", to_be, encode_from_rgb888_be, encode_from_rgb565_be, encode_from_rgba8888_be, encode_from_rgba8888_alpha_be, encode_from_rgb888_dither_be, encode_from_rgba8888_dither_be, encode_from_l8_be, encode_from_la88_be, encode_from_la88_alpha_be, encode_from_rgb555_be, encode_from_argb1555_alpha_be, encode_from_rgb444_be, encode_from_rgba4444_be, encode_from_rgba4444_alpha_be, encode_from_rgb332_be, encode_from_bgr565_be, encode_from_bgr565be_be, encode_from_yuyv_be, encode_from_nv12_be);
+encode_from_endian!("little", to_le, encode_from_rgb888_le, encode_from_rgb565_le, encode_from_rgba8888_le, encode_from_rgba8888_alpha_le, encode_from_rgb888_dither_le, encode_from_rgba8888_dither_le, encode_from_l8_le, encode_from_la88_le, encode_from_la88_alpha_le, encode_from_rgb555_le, encode_from_argb1555_alpha_le, encode_from_rgb444_le, encode_from_rgba4444_le, encode_from_rgba4444_alpha_le, encode_from_rgb332_le, encode_from_bgr565_le, encode_from_bgr565be_le, encode_from_yuyv_le, encode_from_nv12_le);
 
 #[cfg(test)]
 mod tests {
-    use crate::common::pixel::PIXEL_BYTES;
+    use crate::common::color::Rgb;
+    use crate::common::pixel::{rgb_to_pixel, rgb_to_pixel_with_quantization, Quantization, PIXEL_BYTES};
     use crate::encodes::logic::tests::{NUM_PIXELS, RGB888_DATA, RGB565_DATA, RGBA8888_DATA};
 
     #[test]
@@ -93,20 +483,81 @@ mod tests {
         let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
 
         unsafe {
-            super::encode_from_rgb888_be(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb888_be::<Rgb>(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
 
             super::encode_from_rgb565_be(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
             assert_eq!(a_buf, b_buf);
-            super::encode_from_rgba8888_be(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgba8888_be::<Rgb>(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
             assert_eq!(a_buf, b_buf);
 
             
-            super::encode_from_rgb888_le(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb888_le::<Rgb>(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
 
             super::encode_from_rgb565_le(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
             assert_eq!(a_buf, b_buf);
-            super::encode_from_rgba8888_le(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgba8888_le::<Rgb>(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
             assert_eq!(a_buf, b_buf);
         }
     }
+
+    #[test]
+    fn encode_rgba8888_alpha_scalar() {
+        let mut buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let transparent_color = rgb_to_pixel([255, 0, 255]);
+        let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
+
+        unsafe { super::encode_from_rgba8888_alpha_le::<Rgb>(rgba8888_ptr, buf.as_mut_ptr(), transparent_color, 128, 0, NUM_PIXELS, Quantization::Truncate); }
+
+        for (i, pixel) in buf.chunks_exact(PIXEL_BYTES).enumerate() {
+            let pixel = u16::from_le_bytes([pixel[0], pixel[1]]);
+
+            if RGBA8888_DATA[4 * i + 3] < 128 {
+                assert_eq!(pixel, transparent_color);
+            } else {
+                assert_eq!(pixel, RGB565_DATA[i]);
+            }
+        }
+
+        // 不透明なマゼンタ(9番目)は透明色と衝突する
+        let collisions = unsafe { super::encode_from_rgba8888_alpha_le::<Rgb>(rgba8888_ptr, buf.as_mut_ptr(), transparent_color, 0, 0x0020, NUM_PIXELS, Quantization::Truncate) };
+
+        assert_eq!(collisions, 1);
+        assert_eq!(u16::from_le_bytes([buf[18], buf[19]]), transparent_color ^ 0x0020);
+    }
+
+    #[test]
+    fn encode_quantization_scalar() {
+        let mut buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let rgb888_ptr = RGB888_DATA.as_ptr();
+        let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
+
+        for quantization in [Quantization::Truncate, Quantization::Round, Quantization::Nearest] {
+            let expected: Vec<u8> = RGB888_DATA.chunks_exact(3)
+                .flat_map(|rgb| rgb_to_pixel_with_quantization([rgb[0], rgb[1], rgb[2]], quantization).to_be_bytes())
+                .collect();
+
+            unsafe { super::encode_from_rgb888_be::<Rgb>(rgb888_ptr, buf.as_mut_ptr(), NUM_PIXELS, quantization); }
+            assert_eq!(buf[..], expected[..]);
+
+            unsafe { super::encode_from_rgba8888_be::<Rgb>(rgba8888_ptr, buf.as_mut_ptr(), NUM_PIXELS, quantization); }
+            assert_eq!(buf[..], expected[..]);
+
+            unsafe { super::encode_from_rgba8888_alpha_be::<Rgb>(rgba8888_ptr, buf.as_mut_ptr(), 0, 0, 0, NUM_PIXELS, quantization); }
+            assert_eq!(buf[..], expected[..]);
+        }
+    }
+
+    #[test]
+    fn replace_pixels_scalar() {
+        let mut buf = RGB565_DATA;
+
+        let replaced = unsafe { super::replace_pixels(buf.as_mut_ptr().cast::<u8>(), RGB565_DATA[3], 0xFFFF, NUM_PIXELS) };
+
+        assert_eq!(replaced, 1);
+        assert_eq!(buf[3], 0xFFFF);
+        assert_eq!(buf[..3], RGB565_DATA[..3]);
+        assert_eq!(buf[4..], RGB565_DATA[4..]);
+    }
 }
\ No newline at end of file
@@ -1,9 +1,10 @@
 #![allow(unsafe_op_in_unsafe_fn)]
 
-use crate::encodes::logic::{scalar, encode_logic_fn};
-use crate::common::color::ColorType;
-use crate::common::logic::x86_64::M256I;
-use crate::common::pixel::PIXEL_BYTES;
+use crate::encodes::logic::{scalar, encode_logic_fn, DitherRow, YuvCoefficients};
+use crate::encodes::logic::scalar::encode_from_rgb565_direct;
+use crate::common::color::{ChannelOrder, ColorType};
+use crate::common::logic::x86_64::{M128I, M256I};
+use crate::common::pixel::{Quantization, PIXEL_BYTES, PIXEL_R_MASK, PIXEL_G_MASK, PIXEL_B_MASK, PIXEL_G_LSB};
 
 const PIXEL_BLOCK_LEN: usize = 16; // u16(16 bit) * 16 = 256 bit
 
@@ -28,14 +29,208 @@ pub unsafe fn encode_from_rgb565_swap(data: *const u8, buf: *mut u8, num_pixels:
     scalar::encode_from_rgb565_swap(data, buf, remainder)
 }
 
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn replace_pixels(buf: *mut u8, from: u16, to: u16, num_pixels: usize) -> usize {
+    let mut buf = buf;
+    let mut replaced = 0;
+
+    let from_pixel = M256I::set1_epi16(from as i16);
+    let diff = M256I::set1_epi16((from ^ to) as i16);
+
+    let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+    let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+    for _ in 0..pixel_blocks {
+        let pixel = M256I::loadu_si256(buf.cast::<M256I>());
+
+        // 一致したピクセルのみ差分を反転
+        let mask = pixel.cmpeq_epi16(from_pixel);
+        (pixel ^ (mask & diff)).storeu_si256(buf.cast::<M256I>());
+
+        replaced += mask.movemask_epi8().count_ones() as usize / PIXEL_BYTES;
+        buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+    }
+
+    replaced + scalar::replace_pixels(buf, from, to, remainder)
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn encode_from_mono1(data: *const u8, buf: *mut u8, num_pixels: usize) {
+    let mut data = data;
+    let mut buf = buf;
+
+    // 各レーンが担当するビット（下位8レーンが1バイト目、上位8レーンが2バイト目）
+    let bit_mask = M256I::const_u8::<
+        0x80, 0, 0x40, 0, 0x20, 0, 0x10, 0, 0x08, 0, 0x04, 0, 0x02, 0, 0x01, 0,
+        0, 0x80, 0, 0x40, 0, 0x20, 0, 0x10, 0, 0x08, 0, 0x04, 0, 0x02, 0, 0x01,
+    >();
+
+    let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+    let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+    for _ in 0..pixel_blocks {
+        let bits = M256I::set1_epi16(u16::from_le_bytes([data.read(), data.add(1).read()]) as i16);
+        let pixel = (bits & bit_mask).cmpeq_epi16(bit_mask);
+
+        pixel.storeu_si256(buf.cast::<M256I>());
+
+        data = data.add(PIXEL_BLOCK_LEN / 8);
+        buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+    }
+
+    scalar::encode_from_mono1(data, buf, remainder)
+}
+
+/// 16ビットレーンの8ビット値を`quantization`で5ビットに量子化します。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn quantize_5(value: M256I, quantization: Quantization) -> M256I {
+    match quantization {
+        Quantization::Truncate => value.srli_epi16::<3>(),
+        Quantization::Round => value.add_epi16(M256I::set1_epi16(4)).srli_epi16::<3>().min_epi16(M256I::set1_epi16(31)),
+        // (V * 31 + 155) >> 8 は復元値が最も近い値と一致する
+        Quantization::Nearest => value.mullo_epi16(M256I::set1_epi16(31)).add_epi16(M256I::set1_epi16(155)).srli_epi16::<8>(),
+    }
+}
+
+/// 16ビットレーンの8ビット値を`quantization`で6ビットに量子化します。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn quantize_6(value: M256I, quantization: Quantization) -> M256I {
+    match quantization {
+        Quantization::Truncate => value.srli_epi16::<2>(),
+        Quantization::Round => value.add_epi16(M256I::set1_epi16(2)).srli_epi16::<2>().min_epi16(M256I::set1_epi16(63)),
+        // (V * 63 + 189) >> 8 は復元値が最も近い値と一致する
+        Quantization::Nearest => value.mullo_epi16(M256I::set1_epi16(63)).add_epi16(M256I::set1_epi16(189)).srli_epi16::<8>(),
+    }
+}
+
+/// 16ビットレーンの輝度を`quantization`で減色してピクセルに合成します。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn gray_to_pixel(value: M256I, quantization: Quantization) -> M256I {
+    let rb_pixel = quantize_5(value, quantization);
+    rb_pixel.slli_epi16::<11>() | quantize_6(value, quantization).slli_epi16::<5>() | rb_pixel
+}
+
+/// 16ビットレーンのRGB555をピクセルに変換します。最上位ビットは無視されます。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn rgb555_to_pixel(value: M256I) -> M256I {
+    const RG_MASK: M256I = unsafe { M256I::const1_u16::<{ PIXEL_R_MASK | PIXEL_G_MASK & !PIXEL_G_LSB }>() };
+    const G_LSB: M256I = unsafe { M256I::const1_u16::<PIXEL_G_LSB>() };
+    const B_MASK: M256I = unsafe { M256I::const1_u16::<PIXEL_B_MASK>() };
+
+    // RとGを1ビット左に移動し、Gの最下位ビットをGの最上位ビットで補う
+    (value.slli_epi16::<1>() & RG_MASK) | (value.srli_epi16::<4>() & G_LSB) | (value & B_MASK)
+}
+
+/// 16ビットレーンのRとBを入れ替えます。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn swap_rb_vec(value: M256I) -> M256I {
+    const G_MASK: M256I = unsafe { M256I::const1_u16::<PIXEL_G_MASK>() };
+
+    // シフトで16bitレーンからはみ出したビットは捨てられる
+    value.slli_epi16::<11>() | (value & G_MASK) | value.srli_epi16::<11>()
+}
+
+/// `coefficients`の各係数を展開します。
+///
+/// Yのオフセット以外は[`yuv_to_pixel`]の積和の組に合わせて、2つの係数を32bitレーンに並べます。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn get_yuv_coefficients(coefficients: &YuvCoefficients) -> [M256I; 5] {
+    // 下位16bitに`a`、上位16bitに`b`を並べる
+    let pair = |a: i16, b: i16| (a as u16 as i32) | ((b as i32) << 16);
+
+    [
+        M256I::set1_epi16(coefficients.y_offset),
+        M256I::set1_epi32(pair(coefficients.y, 128)),
+        M256I::set1_epi32(pair(0, coefficients.r_v)),
+        M256I::set1_epi32(pair(-coefficients.g_u, -coefficients.g_v)),
+        M256I::set1_epi32(pair(coefficients.b_u, 0)),
+    ]
+}
+
+/// (Y, 1)と(U, V)の組と係数の積和から1チャンネル分を求め、16bitレーンの 0 - 255 の値にします。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn yuv_channel(y_pairs: [M256I; 2], uv_pairs: [M256I; 2], y: M256I, chroma: M256I) -> M256I {
+    const ZERO: M256I = unsafe { M256I::const1_u16::<0>() };
+    const MAX: M256I = unsafe { M256I::const1_u16::<255>() };
+
+    let lo = y_pairs[0].madd_epi16(y).add_epi32(uv_pairs[0].madd_epi16(chroma)).srai_epi32::<8>();
+    let hi = y_pairs[1].madd_epi16(y).add_epi32(uv_pairs[1].madd_epi16(chroma)).srai_epi32::<8>();
+
+    // unpackとpackは同じ単位で動作するため、元の並びに戻る
+    lo.packs_epi32(hi).max_epi16(ZERO).min_epi16(MAX)
+}
+
+/// 16bitレーンのY, U, Vを`quantization`で減色してピクセルに合成します。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn yuv_to_pixel(y_vec: M256I, u_vec: M256I, v_vec: M256I, coefficients: &[M256I; 5], quantization: Quantization) -> M256I {
+    const CHROMA_OFFSET: M256I = unsafe { M256I::const1_u16::<128>() };
+    const ONE: M256I = unsafe { M256I::const1_u16::<1>() };
+
+    let [y_offset, y, r, g, b] = *coefficients;
+
+    let y_vec = y_vec.sub_epi16(y_offset);
+    let u_vec = u_vec.sub_epi16(CHROMA_OFFSET);
+    let v_vec = v_vec.sub_epi16(CHROMA_OFFSET);
+
+    // 積が16bitに収まらないため、組にして32bitレーンで積和を求める（(Y, 1)との積和で丸めの 128 も加算する）
+    let y_pairs = [y_vec.unpacklo_epi16(ONE), y_vec.unpackhi_epi16(ONE)];
+    let uv_pairs = [u_vec.unpacklo_epi16(v_vec), u_vec.unpackhi_epi16(v_vec)];
+
+    let r_pixel = quantize_5(yuv_channel(y_pairs, uv_pairs, y, r), quantization).slli_epi16::<11>();
+    let g_pixel = quantize_6(yuv_channel(y_pairs, uv_pairs, y, g), quantization).slli_epi16::<5>();
+    let b_pixel = quantize_5(yuv_channel(y_pairs, uv_pairs, y, b), quantization);
+
+    r_pixel | g_pixel | b_pixel
+}
+
+/// 16ビットレーンの4ビットのRGBをビット複製で拡張してピクセルに合成します。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn rgb444_to_pixel(r_vec: M256I, g_vec: M256I, b_vec: M256I) -> M256I {
+    let r_pixel = r_vec.slli_epi16::<1>() | r_vec.srli_epi16::<3>();
+    let g_pixel = g_vec.slli_epi16::<2>() | g_vec.srli_epi16::<2>();
+    let b_pixel = b_vec.slli_epi16::<1>() | b_vec.srli_epi16::<3>();
+
+    r_pixel.slli_epi16::<11>() | g_pixel.slli_epi16::<5>() | b_pixel
+}
+
+/// 16ビットレーンのRGB332をビット複製で拡張してピクセルに合成します。
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn rgb332_to_pixel(value: M256I) -> M256I {
+    const R_MASK: M256I = unsafe { M256I::const1_u16::<0x00E0>() };
+    const G_MASK: M256I = unsafe { M256I::const1_u16::<0x001C>() };
+    const B_MASK: M256I = unsafe { M256I::const1_u16::<0x0003>() };
+
+    let r_vec = (value & R_MASK).srli_epi16::<5>();
+    let g_vec = (value & G_MASK).srli_epi16::<2>();
+    let b_vec = value & B_MASK;
+
+    let r_pixel = r_vec.slli_epi16::<2>() | r_vec.srli_epi16::<1>();
+    let g_pixel = g_vec.slli_epi16::<3>() | g_vec;
+    let b_pixel = b_vec.slli_epi16::<3>() | b_vec.slli_epi16::<1>() | b_vec.srli_epi16::<1>();
+
+    r_pixel.slli_epi16::<11>() | g_pixel.slli_epi16::<5>() | b_pixel
+}
+
 macro_rules! encode_from_endian {
-    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident) => {
+    ($endian: expr, $endian_fn: ident, $rgb888: ident, $rgb565: ident, $rgba8888: ident, $rgba8888_alpha: ident, $rgb888_dither: ident, $rgba8888_dither: ident, $l8: ident, $la88: ident, $la88_alpha: ident, $rgb555: ident, $argb1555_alpha: ident, $rgb444: ident, $rgba4444: ident, $rgba4444_alpha: ident, $rgb332: ident, $bgr565: ident, $bgr565be: ident, $yuyv: ident, $nv12: ident) => {
 
         // -- rgb888 ----------------------------
 
         #[inline]
         #[target_feature(enable = "avx2")]
-        pub unsafe fn $rgb888(data: *const u8, buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgb888<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
             const COLOR_TYPE: ColorType = ColorType::Rgb888;
 
             const R_MASK_1: M256I = unsafe { M256I::const_i8::<
@@ -72,11 +267,12 @@ macro_rules! encode_from_endian {
 
             // バッファオーバーしないための前後ピクセルを加味する
             if num_pixels < PIXEL_BLOCK_LEN + 2 + 2 {
-                scalar::$rgb888(data, buf, num_pixels);
+                scalar::$rgb888::<O>(data, buf, num_pixels, quantization);
+                return;
             }
 
             // 先頭の2ピクセル先に処理する
-            scalar::$rgb888(data, buf, 2);
+            scalar::$rgb888::<O>(data, buf, 2, quantization);
             let num_pixels = num_pixels - 2;
 
             // 後半処理のため2バイトずらす
@@ -88,6 +284,10 @@ macro_rules! encode_from_endian {
             let pixel_blocks = (num_pixels - 2) / PIXEL_BLOCK_LEN;
             let remainder = num_pixels - (PIXEL_BLOCK_LEN * pixel_blocks);
         
+            // BGRの場合はRとBのマスクを入れ替える
+            let (r_mask_1, b_mask_1) = O::swap_rb(R_MASK_1, B_MASK_1);
+            let (r_mask_2, b_mask_2) = O::swap_rb(R_MASK_2, B_MASK_2);
+
             for _ in 0..pixel_blocks {
                 // 前半8ピクセル取得
                 let rgb_1 = M256I::loadu_si256(data.cast::<M256I>());
@@ -96,9 +296,9 @@ macro_rules! encode_from_endian {
                 let rgb_2 = M256I::loadu_si256(data.cast::<M256I>());
 
                 // RGBに分離
-                let mut r_pixel = rgb_1.shuffle_epi8(R_MASK_1) | rgb_2.shuffle_epi8(R_MASK_2);
+                let mut r_pixel = rgb_1.shuffle_epi8(r_mask_1) | rgb_2.shuffle_epi8(r_mask_2);
                 let mut g_pixel = rgb_1.shuffle_epi8(G_MASK_1) | rgb_2.shuffle_epi8(G_MASK_2);
-                let mut b_pixel = rgb_1.shuffle_epi8(B_MASK_1) | rgb_2.shuffle_epi8(B_MASK_2);
+                let mut b_pixel = rgb_1.shuffle_epi8(b_mask_1) | rgb_2.shuffle_epi8(b_mask_2);
 
                 // 正しい順序に並び替え
                 r_pixel = r_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
@@ -106,9 +306,9 @@ macro_rules! encode_from_endian {
                 b_pixel = b_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
 
                 // 減色 + 位置調整
-                r_pixel = r_pixel.srli_epi16::<3>().slli_epi16::<11>(); // (R >> 3) << 11
-                g_pixel = g_pixel.srli_epi16::<2>().slli_epi16::<5>();  // (G >> 2) << 5
-                b_pixel = b_pixel.srli_epi16::<3>();                    // (B >> 3)
+                r_pixel = quantize_5(r_pixel, quantization).slli_epi16::<11>();
+                g_pixel = quantize_6(g_pixel, quantization).slli_epi16::<5>();
+                b_pixel = quantize_5(b_pixel, quantization);
 
                 // ピクセルに合成
                 let pixel = (r_pixel | g_pixel | b_pixel).$endian_fn();
@@ -119,7 +319,7 @@ macro_rules! encode_from_endian {
                 buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
             }
         
-            scalar::$rgb888(data.add(4), buf, remainder)
+            scalar::$rgb888::<O>(data.add(4), buf, remainder, quantization)
         }
 
         // -- rgb565 ------------------------------
@@ -141,7 +341,7 @@ macro_rules! encode_from_endian {
 
         #[inline]
         #[target_feature(enable = "avx2")]
-        pub unsafe fn $rgba8888(data: *const u8, buf: *mut u8, num_pixels: usize) {
+        pub unsafe fn $rgba8888<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
             const COLOR_TYPE: ColorType = ColorType::Rgba8888;
 
             const R_MASK_1: M256I = unsafe { M256I::const_i8::<
@@ -182,6 +382,10 @@ macro_rules! encode_from_endian {
             let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
             let remainder = num_pixels % PIXEL_BLOCK_LEN;
         
+            // BGRの場合はRとBのマスクを入れ替える
+            let (r_mask_1, b_mask_1) = O::swap_rb(R_MASK_1, B_MASK_1);
+            let (r_mask_2, b_mask_2) = O::swap_rb(R_MASK_2, B_MASK_2);
+
             for _ in 0..pixel_blocks {
                 // 前半8ピクセル取得
                 let rgb_1 = M256I::loadu_si256(data.cast::<M256I>());
@@ -190,9 +394,9 @@ macro_rules! encode_from_endian {
                 let rgb_2 = M256I::loadu_si256(data.cast::<M256I>());
 
                 // RGBに分離
-                let mut r_pixel = rgb_1.shuffle_epi8(R_MASK_1) | rgb_2.shuffle_epi8(R_MASK_2);
+                let mut r_pixel = rgb_1.shuffle_epi8(r_mask_1) | rgb_2.shuffle_epi8(r_mask_2);
                 let mut g_pixel = rgb_1.shuffle_epi8(G_MASK_1) | rgb_2.shuffle_epi8(G_MASK_2);
-                let mut b_pixel = rgb_1.shuffle_epi8(B_MASK_1) | rgb_2.shuffle_epi8(B_MASK_2);
+                let mut b_pixel = rgb_1.shuffle_epi8(b_mask_1) | rgb_2.shuffle_epi8(b_mask_2);
 
                 // 正しい順序に並び替え
                 r_pixel = r_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
@@ -200,9 +404,9 @@ macro_rules! encode_from_endian {
                 b_pixel = b_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
 
                 // 減色 + 位置調整
-                r_pixel = r_pixel.srli_epi16::<3>().slli_epi16::<11>(); // (R >> 3) << 11
-                g_pixel = g_pixel.srli_epi16::<2>().slli_epi16::<5>();  // (G >> 2) << 5
-                b_pixel = b_pixel.srli_epi16::<3>();                    // (B >> 3)
+                r_pixel = quantize_5(r_pixel, quantization).slli_epi16::<11>();
+                g_pixel = quantize_6(g_pixel, quantization).slli_epi16::<5>();
+                b_pixel = quantize_5(b_pixel, quantization);
 
                 // ピクセルに合成
                 let pixel = (r_pixel | g_pixel | b_pixel).$endian_fn();
@@ -213,140 +417,1441 @@ macro_rules! encode_from_endian {
                 buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
             }
         
-            scalar::$rgba8888(data, buf, remainder)
+            scalar::$rgba8888::<O>(data, buf, remainder, quantization)
         }
-    };
-}
 
-encode_logic_fn!(#[target_feature(enable = "avx2")]);
-encode_from_endian!("big", be_epi16, encode_from_rgb888_be, encode_from_rgb565_be, encode_from_rgba8888_be);
-encode_from_endian!("little", le_epi16, encode_from_rgb888_le, encode_from_rgb565_le, encode_from_rgba8888_le);
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgba8888_alpha<O: ChannelOrder>(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize, quantization: Quantization) -> usize {
+            const COLOR_TYPE: ColorType = ColorType::Rgba8888;
 
-#[cfg(test)]
-mod tests {
-    use crate::common::color::ColorType;
-    use crate::common::spec::{ImageSpec, PixelEndian};
-    use crate::common::pixel::PIXEL_BYTES;
-    use crate::encodes::logic::scalar;
-    use crate::encodes::logic::tests::{NUM_PIXELS, RGB888_DATA, RGB565_DATA, RGBA8888_DATA};
+            const R_MASK_1: M256I = unsafe { M256I::const_i8::<
+                0, -1, 4, -1, 8, -1, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                0, -1, 4, -1, 8, -1, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-    #[test]
-    fn encode_logic_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
-        }
+            const G_MASK_1: M256I = unsafe { M256I::const_i8::<
+                1, -1, 5, -1, 9, -1, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                1, -1, 5, -1, 9, -1, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-        let mut a_buf = [0; NUM_PIXELS * PIXEL_BYTES];
-        let mut b_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+            const B_MASK_1: M256I = unsafe { M256I::const_i8::<
+                2, -1, 6, -1, 10, -1, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                2, -1, 6, -1, 10, -1, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-        let rgb888_ptr = RGB888_DATA.as_ptr();
-        let rgb565_ptr = RGB565_DATA.as_ptr().cast::<u8>();
-        let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
+            const A_MASK_1: M256I = unsafe { M256I::const_i8::<
+                3, -1, 7, -1, 11, -1, 15, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                3, -1, 7, -1, 11, -1, 15, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-        let mut spec = ImageSpec::with_pixel_endian(NUM_PIXELS as u16, 1, PixelEndian::Big);
+            const R_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 0, -1, 4, -1, 8, -1, 12, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 0, -1, 4, -1, 8, -1, 12, -1,
+            >() };
 
-        unsafe {
-            super::encode_logic(rgb888_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb888);
-            super::encode_from_rgb888_be(rgb888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+            const G_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 1, -1, 5, -1, 9, -1, 13, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 1, -1, 5, -1, 9, -1, 13, -1,
+            >() };
 
-            super::encode_logic(rgb565_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb565);
-            super::encode_from_rgb565_be(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+            const B_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 2, -1, 6, -1, 10, -1, 14, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 2, -1, 6, -1, 10, -1, 14, -1,
+            >() };
 
-            super::encode_logic(rgba8888_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgba8888);
-            super::encode_from_rgba8888_be(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+            const A_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 3, -1, 7, -1, 11, -1, 15, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 3, -1, 7, -1, 11, -1, 15, -1,
+            >() };
 
-            spec.pixel_endian = PixelEndian::Little;
+            const PERMUTE_IMM8: i32 = 0b_11_01_10_00;
+
+            let transparent_pixel = M256I::set1_epi16(transparent_color as i16);
+            let threshold = M256I::set1_epi16(alpha_threshold as i16);
+            let collision = M256I::set1_epi16(collision_xor as i16);
+        
+            let mut data = data;
+            let mut buf = buf;
+            let mut collisions = 0;
             
-            super::encode_logic(rgb888_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb888);
-            super::encode_from_rgb888_le(rgb888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+        
+            // BGRの場合はRとBのマスクを入れ替える
+            let (r_mask_1, b_mask_1) = O::swap_rb(R_MASK_1, B_MASK_1);
+            let (r_mask_2, b_mask_2) = O::swap_rb(R_MASK_2, B_MASK_2);
 
-            super::encode_logic(rgb565_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgb565);
-            super::encode_from_rgb565_le(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
+            for _ in 0..pixel_blocks {
+                // 前半8ピクセル取得
+                let rgba_1 = M256I::loadu_si256(data.cast::<M256I>());
+                data = data.add(8 * COLOR_TYPE.bytes_per_pixel());
+                // 後半8ピクセル取得
+                let rgba_2 = M256I::loadu_si256(data.cast::<M256I>());
 
-            super::encode_logic(rgba8888_ptr, a_buf.as_mut_ptr(), &spec, ColorType::Rgba8888);
-            super::encode_from_rgba8888_le(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
-            assert_eq!(a_buf, b_buf);
-        }
-    }
+                // RGBAに分離
+                let mut r_pixel = rgba_1.shuffle_epi8(r_mask_1) | rgba_2.shuffle_epi8(r_mask_2);
+                let mut g_pixel = rgba_1.shuffle_epi8(G_MASK_1) | rgba_2.shuffle_epi8(G_MASK_2);
+                let mut b_pixel = rgba_1.shuffle_epi8(b_mask_1) | rgba_2.shuffle_epi8(b_mask_2);
+                let mut a_pixel = rgba_1.shuffle_epi8(A_MASK_1) | rgba_2.shuffle_epi8(A_MASK_2);
 
-    #[test]
-    fn encode_rgb888_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
-        }
+                // 正しい順序に並び替え
+                r_pixel = r_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                g_pixel = g_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                b_pixel = b_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                a_pixel = a_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
 
-        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
-        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+                // 減色 + 位置調整
+                r_pixel = quantize_5(r_pixel, quantization).slli_epi16::<11>();
+                g_pixel = quantize_6(g_pixel, quantization).slli_epi16::<5>();
+                b_pixel = quantize_5(b_pixel, quantization);
 
-        let data_ptr = RGB888_DATA.as_ptr();
+                let rgb_pixel = r_pixel | g_pixel | b_pixel;
+                let transparent_mask = threshold.cmpgt_epi16(a_pixel);
 
-        unsafe {
-            scalar::encode_from_rgb888_be(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::encode_from_rgb888_be(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+                // 透明色と一致する不透明なピクセルを補正
+                let collision_mask = transparent_mask.andnot_si256(rgb_pixel.cmpeq_epi16(transparent_pixel));
+                collisions += collision_mask.movemask_epi8().count_ones() as usize / PIXEL_BYTES;
 
-        assert_eq!(scalar_buf, simd_buf);
+                // アルファ値が閾値未満のピクセルを透明色に置き換え
+                let pixel = transparent_mask.andnot_si256(rgb_pixel ^ (collision_mask & collision)) | (transparent_mask & transparent_pixel);
 
-        unsafe {
-            scalar::encode_from_rgb888_le(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::encode_from_rgb888_le(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+                
+                data = data.add(8 * COLOR_TYPE.bytes_per_pixel());
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+        
+            collisions + scalar::$rgba8888_alpha::<O>(data, buf, transparent_color, alpha_threshold, collision_xor, remainder, quantization)
         }
 
-        assert_eq!(scalar_buf, simd_buf);
-    }
+        // -- dither ------------------------------
 
-    #[test]
-    fn encode_rgb565_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
-        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgb888_dither<O: ChannelOrder>(data: *const u8, buf: *mut u8, num_pixels: usize, dither: &DitherRow) {
+            const COLOR_TYPE: ColorType = ColorType::Rgb888;
+            const MAX_VALUE: M256I = unsafe { M256I::const1_u16::<255>() };
 
-        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
-        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+            const R_MASK_1: M256I = unsafe { M256I::const_i8::<
+                4, -1, 7, -1, 10, -1, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                0, -1, 3, -1,  6, -1,  9, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-        let data_ptr = RGB565_DATA.as_ptr().cast::<u8>();
+            const G_MASK_1: M256I = unsafe { M256I::const_i8::<
+                5, -1, 8, -1, 11, -1, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                1, -1, 4, -1,  7, -1, 10, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-        unsafe {
-            scalar::encode_from_rgb565_be(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::encode_from_rgb565_be(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+            const B_MASK_1: M256I = unsafe { M256I::const_i8::<
+                6, -1, 9, -1, 12, -1, 15, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                2, -1, 5, -1,  8, -1, 11, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
 
-        assert_eq!(scalar_buf, simd_buf);
+            const R_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 4, -1, 7, -1, 10, -1, 13, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 0, -1, 3, -1,  6, -1,  9, -1,
+            >() };
 
-        unsafe {
-            scalar::encode_from_rgb565_le(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::encode_from_rgb565_le(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+            const G_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 5, -1, 8, -1, 11, -1, 14, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 1, -1, 4, -1,  7, -1, 10, -1,
+            >() };
 
-        assert_eq!(scalar_buf, simd_buf);
-    }
+            const B_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 6, -1, 9, -1, 12, -1, 15, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 2, -1, 5, -1,  8, -1, 11, -1,
+            >() };
 
-    #[test]
-    fn encode_rgba8888_x86_64_avx2() {
-        if !is_x86_feature_detected!("avx2") {
-            return;
-        }
+            const PERMUTE_IMM8: i32 = 0b_11_01_10_00;
 
-        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
-        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+            // バッファオーバーしないための前後ピクセルを加味する
+            if num_pixels < PIXEL_BLOCK_LEN + 2 + 2 {
+                scalar::$rgb888_dither::<O>(data, buf, num_pixels, dither);
+                return;
+            }
 
-        let data_ptr = RGBA8888_DATA.as_ptr();
+            // 先頭の2ピクセル先に処理する
+            scalar::$rgb888_dither::<O>(data, buf, 2, dither);
 
-        unsafe {
-            scalar::encode_from_rgba8888_be(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::encode_from_rgba8888_be(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
-        }
+            // ブロックの大きさはディザリングの周期の倍数なので、加算する値は全ブロックで共通
+            let rb_offset = M256I::loadu_si256(dither.rb.as_ptr().add(2).cast::<M256I>());
+            let g_offset = M256I::loadu_si256(dither.g.as_ptr().add(2).cast::<M256I>());
+            let num_pixels = num_pixels - 2;
 
-        assert_eq!(scalar_buf, simd_buf);
+            // 後半処理のため2バイトずらす
+            let mut data = data.add(2);
+            // 2ピクセル部分進めておく
+            let mut buf = buf.add(PIXEL_BYTES * 2);
+
+            
+            let pixel_blocks = (num_pixels - 2) / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels - (PIXEL_BLOCK_LEN * pixel_blocks);
+        
+            // BGRの場合はRとBのマスクを入れ替える
+            let (r_mask_1, b_mask_1) = O::swap_rb(R_MASK_1, B_MASK_1);
+            let (r_mask_2, b_mask_2) = O::swap_rb(R_MASK_2, B_MASK_2);
+
+            for _ in 0..pixel_blocks {
+                // 前半8ピクセル取得
+                let rgb_1 = M256I::loadu_si256(data.cast::<M256I>());
+                data = data.add(8 * COLOR_TYPE.bytes_per_pixel() ); 
+                // 後半8ピクセル取得
+                let rgb_2 = M256I::loadu_si256(data.cast::<M256I>());
+
+                // RGBに分離
+                let mut r_pixel = rgb_1.shuffle_epi8(r_mask_1) | rgb_2.shuffle_epi8(r_mask_2);
+                let mut g_pixel = rgb_1.shuffle_epi8(G_MASK_1) | rgb_2.shuffle_epi8(G_MASK_2);
+                let mut b_pixel = rgb_1.shuffle_epi8(b_mask_1) | rgb_2.shuffle_epi8(b_mask_2);
+
+                // 正しい順序に並び替え
+                r_pixel = r_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                g_pixel = g_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                b_pixel = b_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+
+                // ディザリングの値を加算
+                r_pixel = r_pixel.add_epi16(rb_offset).min_epi16(MAX_VALUE);
+                g_pixel = g_pixel.add_epi16(g_offset).min_epi16(MAX_VALUE);
+                b_pixel = b_pixel.add_epi16(rb_offset).min_epi16(MAX_VALUE);
+
+                // 減色 + 位置調整
+                r_pixel = r_pixel.srli_epi16::<3>().slli_epi16::<11>(); // (R >> 3) << 11
+                g_pixel = g_pixel.srli_epi16::<2>().slli_epi16::<5>();  // (G >> 2) << 5
+                b_pixel = b_pixel.srli_epi16::<3>();                    // (B >> 3)
+
+                // ピクセルに合成
+                let pixel = (r_pixel | g_pixel | b_pixel).$endian_fn();
+    
+                pixel.storeu_si256(buf.cast::<M256I>());
+                
+                data = data.add(8 * COLOR_TYPE.bytes_per_pixel());
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+        
+            scalar::$rgb888_dither::<O>(data.add(4), buf, remainder, &dither.shifted(2 + PIXEL_BLOCK_LEN * pixel_blocks))
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgba8888_dither<O: ChannelOrder>(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize, dither: &DitherRow) -> usize {
+            const COLOR_TYPE: ColorType = ColorType::Rgba8888;
+            const MAX_VALUE: M256I = unsafe { M256I::const1_u16::<255>() };
+
+            const R_MASK_1: M256I = unsafe { M256I::const_i8::<
+                0, -1, 4, -1, 8, -1, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                0, -1, 4, -1, 8, -1, 12, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
+
+            const G_MASK_1: M256I = unsafe { M256I::const_i8::<
+                1, -1, 5, -1, 9, -1, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                1, -1, 5, -1, 9, -1, 13, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
+
+            const B_MASK_1: M256I = unsafe { M256I::const_i8::<
+                2, -1, 6, -1, 10, -1, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                2, -1, 6, -1, 10, -1, 14, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
+
+            const A_MASK_1: M256I = unsafe { M256I::const_i8::<
+                3, -1, 7, -1, 11, -1, 15, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+                3, -1, 7, -1, 11, -1, 15, -1, -1, -1, -1, -1, -1, -1, -1, -1,
+            >() };
+
+            const R_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 0, -1, 4, -1, 8, -1, 12, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 0, -1, 4, -1, 8, -1, 12, -1,
+            >() };
+
+            const G_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 1, -1, 5, -1, 9, -1, 13, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 1, -1, 5, -1, 9, -1, 13, -1,
+            >() };
+
+            const B_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 2, -1, 6, -1, 10, -1, 14, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 2, -1, 6, -1, 10, -1, 14, -1,
+            >() };
+
+            const A_MASK_2: M256I = unsafe { M256I::const_i8::<
+                -1, -1, -1, -1, -1, -1, -1, -1, 3, -1, 7, -1, 11, -1, 15, -1,
+                -1, -1, -1, -1, -1, -1, -1, -1, 3, -1, 7, -1, 11, -1, 15, -1,
+            >() };
+
+            const PERMUTE_IMM8: i32 = 0b_11_01_10_00;
+
+            let transparent_pixel = M256I::set1_epi16(transparent_color as i16);
+            let threshold = M256I::set1_epi16(alpha_threshold as i16);
+            let collision = M256I::set1_epi16(collision_xor as i16);
+
+            // ブロックの大きさはディザリングの周期の倍数なので、加算する値は全ブロックで共通
+            let rb_offset = M256I::loadu_si256(dither.rb.as_ptr().cast::<M256I>());
+            let g_offset = M256I::loadu_si256(dither.g.as_ptr().cast::<M256I>());
+        
+            let mut data = data;
+            let mut buf = buf;
+            let mut collisions = 0;
+            
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+        
+            // BGRの場合はRとBのマスクを入れ替える
+            let (r_mask_1, b_mask_1) = O::swap_rb(R_MASK_1, B_MASK_1);
+            let (r_mask_2, b_mask_2) = O::swap_rb(R_MASK_2, B_MASK_2);
+
+            for _ in 0..pixel_blocks {
+                // 前半8ピクセル取得
+                let rgba_1 = M256I::loadu_si256(data.cast::<M256I>());
+                data = data.add(8 * COLOR_TYPE.bytes_per_pixel());
+                // 後半8ピクセル取得
+                let rgba_2 = M256I::loadu_si256(data.cast::<M256I>());
+
+                // RGBAに分離
+                let mut r_pixel = rgba_1.shuffle_epi8(r_mask_1) | rgba_2.shuffle_epi8(r_mask_2);
+                let mut g_pixel = rgba_1.shuffle_epi8(G_MASK_1) | rgba_2.shuffle_epi8(G_MASK_2);
+                let mut b_pixel = rgba_1.shuffle_epi8(b_mask_1) | rgba_2.shuffle_epi8(b_mask_2);
+                let mut a_pixel = rgba_1.shuffle_epi8(A_MASK_1) | rgba_2.shuffle_epi8(A_MASK_2);
+
+                // 正しい順序に並び替え
+                r_pixel = r_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                g_pixel = g_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                b_pixel = b_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+                a_pixel = a_pixel.permute4x64_epi64::<PERMUTE_IMM8>();
+
+                // ディザリングの値を加算
+                r_pixel = r_pixel.add_epi16(rb_offset).min_epi16(MAX_VALUE);
+                g_pixel = g_pixel.add_epi16(g_offset).min_epi16(MAX_VALUE);
+                b_pixel = b_pixel.add_epi16(rb_offset).min_epi16(MAX_VALUE);
+
+                // 減色 + 位置調整
+                r_pixel = r_pixel.srli_epi16::<3>().slli_epi16::<11>(); // (R >> 3) << 11
+                g_pixel = g_pixel.srli_epi16::<2>().slli_epi16::<5>();  // (G >> 2) << 5
+                b_pixel = b_pixel.srli_epi16::<3>();                    // (B >> 3)
+
+                let rgb_pixel = r_pixel | g_pixel | b_pixel;
+                let transparent_mask = threshold.cmpgt_epi16(a_pixel);
+
+                // 透明色と一致する不透明なピクセルを補正
+                let collision_mask = transparent_mask.andnot_si256(rgb_pixel.cmpeq_epi16(transparent_pixel));
+                collisions += collision_mask.movemask_epi8().count_ones() as usize / PIXEL_BYTES;
+
+                // アルファ値が閾値未満のピクセルを透明色に置き換え
+                let pixel = transparent_mask.andnot_si256(rgb_pixel ^ (collision_mask & collision)) | (transparent_mask & transparent_pixel);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+                
+                data = data.add(8 * COLOR_TYPE.bytes_per_pixel());
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+        
+            collisions + scalar::$rgba8888_dither::<O>(data, buf, transparent_color, alpha_threshold, collision_xor, remainder, &dither.shifted(PIXEL_BLOCK_LEN * pixel_blocks))
+        }
+
+        // -- gray --------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $l8(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
+            const COLOR_TYPE: ColorType = ColorType::L8;
+
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let l_pixel = M256I::cvtepu8_epi16(M128I::loadu_si128(data.cast::<M128I>()));
+
+                let pixel = gray_to_pixel(l_pixel, quantization).$endian_fn();
+
+                pixel.storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$l8(data, buf, remainder, quantization)
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $la88(data: *const u8, buf: *mut u8, num_pixels: usize, quantization: Quantization) {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+            const L_MASK: M256I = unsafe { M256I::const1_u16::<0x00FF>() };
+
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let l_pixel = M256I::loadu_si256(data.cast::<M256I>()) & L_MASK;
+
+                let pixel = gray_to_pixel(l_pixel, quantization).$endian_fn();
+
+                pixel.storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$la88(data, buf, remainder, quantization)
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $la88_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize, quantization: Quantization) -> usize {
+            const COLOR_TYPE: ColorType = ColorType::La88;
+            const L_MASK: M256I = unsafe { M256I::const1_u16::<0x00FF>() };
+
+            let transparent_pixel = M256I::set1_epi16(transparent_color as i16);
+            let threshold = M256I::set1_epi16(alpha_threshold as i16);
+            let collision = M256I::set1_epi16(collision_xor as i16);
+
+            let mut data = data;
+            let mut buf = buf;
+            let mut collisions = 0;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let la = M256I::loadu_si256(data.cast::<M256I>());
+
+                // 輝度とアルファに分離
+                let l_pixel = la & L_MASK;
+                let a_pixel = la.srli_epi16::<8>();
+
+                let gray_pixel = gray_to_pixel(l_pixel, quantization);
+                let transparent_mask = threshold.cmpgt_epi16(a_pixel);
+
+                // 透明色と一致する不透明なピクセルを補正
+                let collision_mask = transparent_mask.andnot_si256(gray_pixel.cmpeq_epi16(transparent_pixel));
+                collisions += collision_mask.movemask_epi8().count_ones() as usize / PIXEL_BYTES;
+
+                // アルファ値が閾値未満のピクセルを透明色に置き換え
+                let pixel = transparent_mask.andnot_si256(gray_pixel ^ (collision_mask & collision)) | (transparent_mask & transparent_pixel);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            collisions + scalar::$la88_alpha(data, buf, transparent_color, alpha_threshold, collision_xor, remainder, quantization)
+        }
+
+        // -- rgb555 ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgb555(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let rgb = M256I::loadu_si256(data.cast::<M256I>());
+
+                rgb555_to_pixel(rgb).$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgb555(data, buf, remainder)
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $argb1555_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize) -> usize {
+            const ZERO: M256I = unsafe { M256I::const1_u16::<0>() };
+            const ALPHA_MASK: M256I = unsafe { M256I::const1_u16::<0x00FF>() };
+
+            let transparent_pixel = M256I::set1_epi16(transparent_color as i16);
+            let threshold = M256I::set1_epi16(alpha_threshold as i16);
+            let collision = M256I::set1_epi16(collision_xor as i16);
+
+            let mut data = data;
+            let mut buf = buf;
+            let mut collisions = 0;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let argb = M256I::loadu_si256(data.cast::<M256I>());
+
+                // Aが 1 のレーンは符号ビットが立つので、0 か 255 のアルファに展開
+                let a_pixel = ZERO.cmpgt_epi16(argb) & ALPHA_MASK;
+
+                let rgb_pixel = rgb555_to_pixel(argb);
+                let transparent_mask = threshold.cmpgt_epi16(a_pixel);
+
+                // 透明色と一致する不透明なピクセルを補正
+                let collision_mask = transparent_mask.andnot_si256(rgb_pixel.cmpeq_epi16(transparent_pixel));
+                collisions += collision_mask.movemask_epi8().count_ones() as usize / PIXEL_BYTES;
+
+                // アルファ値が閾値未満のピクセルを透明色に置き換え
+                let pixel = transparent_mask.andnot_si256(rgb_pixel ^ (collision_mask & collision)) | (transparent_mask & transparent_pixel);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            collisions + scalar::$argb1555_alpha(data, buf, transparent_color, alpha_threshold, collision_xor, remainder)
+        }
+
+        // -- rgb444 ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgb444(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            const NIBBLE_MASK: M256I = unsafe { M256I::const1_u16::<0x000F>() };
+
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let rgb = M256I::loadu_si256(data.cast::<M256I>());
+
+                let pixel = rgb444_to_pixel(rgb.srli_epi16::<8>() & NIBBLE_MASK, rgb.srli_epi16::<4>() & NIBBLE_MASK, rgb & NIBBLE_MASK);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgb444(data, buf, remainder)
+        }
+
+        // -- rgba4444 ----------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgba4444(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            const NIBBLE_MASK: M256I = unsafe { M256I::const1_u16::<0x000F>() };
+
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let rgba = M256I::loadu_si256(data.cast::<M256I>());
+
+                let pixel = rgb444_to_pixel(rgba.srli_epi16::<12>(), rgba.srli_epi16::<8>() & NIBBLE_MASK, rgba.srli_epi16::<4>() & NIBBLE_MASK);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgba4444(data, buf, remainder)
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgba4444_alpha(data: *const u8, buf: *mut u8, transparent_color: u16, alpha_threshold: u8, collision_xor: u16, num_pixels: usize) -> usize {
+            const NIBBLE_MASK: M256I = unsafe { M256I::const1_u16::<0x000F>() };
+
+            let transparent_pixel = M256I::set1_epi16(transparent_color as i16);
+            let threshold = M256I::set1_epi16(alpha_threshold as i16);
+            let collision = M256I::set1_epi16(collision_xor as i16);
+
+            let mut data = data;
+            let mut buf = buf;
+            let mut collisions = 0;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let rgba = M256I::loadu_si256(data.cast::<M256I>());
+
+                // Aはビット複製で8ビットに拡張
+                let a_vec = rgba & NIBBLE_MASK;
+                let a_pixel = a_vec.slli_epi16::<4>() | a_vec;
+
+                let rgb_pixel = rgb444_to_pixel(rgba.srli_epi16::<12>(), rgba.srli_epi16::<8>() & NIBBLE_MASK, rgba.srli_epi16::<4>() & NIBBLE_MASK);
+                let transparent_mask = threshold.cmpgt_epi16(a_pixel);
+
+                // 透明色と一致する不透明なピクセルを補正
+                let collision_mask = transparent_mask.andnot_si256(rgb_pixel.cmpeq_epi16(transparent_pixel));
+                collisions += collision_mask.movemask_epi8().count_ones() as usize / PIXEL_BYTES;
+
+                // アルファ値が閾値未満のピクセルを透明色に置き換え
+                let pixel = transparent_mask.andnot_si256(rgb_pixel ^ (collision_mask & collision)) | (transparent_mask & transparent_pixel);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            collisions + scalar::$rgba4444_alpha(data, buf, transparent_color, alpha_threshold, collision_xor, remainder)
+        }
+
+        // -- rgb332 ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $rgb332(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            const COLOR_TYPE: ColorType = ColorType::Rgb332;
+
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let rgb = M256I::cvtepu8_epi16(M128I::loadu_si128(data.cast::<M128I>()));
+
+                let pixel = rgb332_to_pixel(rgb);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * COLOR_TYPE.bytes_per_pixel());
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$rgb332(data, buf, remainder)
+        }
+
+        // -- bgr565 ------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $bgr565(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let bgr = M256I::loadu_si256(data.cast::<M256I>());
+
+                swap_rb_vec(bgr).$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$bgr565(data, buf, remainder)
+        }
+
+        // -- bgr565be ----------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $bgr565be(data: *const u8, buf: *mut u8, num_pixels: usize) {
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            for _ in 0..pixel_blocks {
+                // ビッグエンディアンの16ピクセル取得
+                let bgr = M256I::loadu_si256(data.cast::<M256I>()).be_epi16();
+
+                swap_rb_vec(bgr).$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$bgr565be(data, buf, remainder)
+        }
+
+        // -- yuv ---------------------------------
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $yuyv(data: *const u8, buf: *mut u8, num_pixels: usize, coefficients: &YuvCoefficients, quantization: Quantization) {
+            const Y_MASK: M256I = unsafe { M256I::const1_u16::<0x00FF>() };
+            const U_SHUFFLE: M256I = unsafe { M256I::const_i8::<
+                1, -1, 1, -1, 5, -1, 5, -1, 9, -1, 9, -1, 13, -1, 13, -1,
+                1, -1, 1, -1, 5, -1, 5, -1, 9, -1, 9, -1, 13, -1, 13, -1,
+            >() };
+            const V_SHUFFLE: M256I = unsafe { M256I::const_i8::<
+                3, -1, 3, -1, 7, -1, 7, -1, 11, -1, 11, -1, 15, -1, 15, -1,
+                3, -1, 3, -1, 7, -1, 7, -1, 11, -1, 11, -1, 15, -1, 15, -1,
+            >() };
+
+            let mut data = data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            let coefficients_vec = get_yuv_coefficients(coefficients);
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let yuyv = M256I::loadu_si256(data.cast::<M256I>());
+
+                // 各ピクセルの位置にUとVを複製
+                let pixel = yuv_to_pixel(yuyv & Y_MASK, yuyv.shuffle_epi8(U_SHUFFLE), yuyv.shuffle_epi8(V_SHUFFLE), &coefficients_vec, quantization);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                data = data.add(PIXEL_BLOCK_LEN * 2);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$yuyv(data, buf, remainder, coefficients, quantization)
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn $nv12(y_data: *const u8, uv_data: *const u8, buf: *mut u8, num_pixels: usize, coefficients: &YuvCoefficients, quantization: Quantization) {
+            const U_SHUFFLE: M256I = unsafe { M256I::const_i8::<
+                0, 1, 0, 1, 4, 5, 4, 5, 8, 9, 8, 9, 12, 13, 12, 13,
+                0, 1, 0, 1, 4, 5, 4, 5, 8, 9, 8, 9, 12, 13, 12, 13,
+            >() };
+            const V_SHUFFLE: M256I = unsafe { M256I::const_i8::<
+                2, 3, 2, 3, 6, 7, 6, 7, 10, 11, 10, 11, 14, 15, 14, 15,
+                2, 3, 2, 3, 6, 7, 6, 7, 10, 11, 10, 11, 14, 15, 14, 15,
+            >() };
+
+            let mut y_data = y_data;
+            let mut uv_data = uv_data;
+            let mut buf = buf;
+
+            let pixel_blocks = num_pixels / PIXEL_BLOCK_LEN;
+            let remainder = num_pixels % PIXEL_BLOCK_LEN;
+
+            let coefficients_vec = get_yuv_coefficients(coefficients);
+
+            for _ in 0..pixel_blocks {
+                // 16ピクセル取得
+                let y_vec = M256I::cvtepu8_epi16(M128I::loadu_si128(y_data.cast::<M128I>()));
+                let uv_vec = M256I::cvtepu8_epi16(M128I::loadu_si128(uv_data.cast::<M128I>()));
+
+                // 各ピクセルの位置にUとVを複製
+                let pixel = yuv_to_pixel(y_vec, uv_vec.shuffle_epi8(U_SHUFFLE), uv_vec.shuffle_epi8(V_SHUFFLE), &coefficients_vec, quantization);
+
+                pixel.$endian_fn().storeu_si256(buf.cast::<M256I>());
+
+                y_data = y_data.add(PIXEL_BLOCK_LEN);
+                uv_data = uv_data.add(PIXEL_BLOCK_LEN);
+                buf = buf.add(PIXEL_BLOCK_LEN * PIXEL_BYTES);
+            }
+
+            scalar::$nv12(y_data, uv_data, buf, remainder, coefficients, quantization)
+        }
+    };
+}
+
+encode_logic_fn!(#[target_feature(enable = "avx2")]);
+encode_from_endian!("big", be_epi16, encode_from_rgb888_be, encode_from_rgb565_be, encode_from_rgba8888_be, encode_from_rgba8888_alpha_be, encode_from_rgb888_dither_be, encode_from_rgba8888_dither_be, encode_from_l8_be, encode_from_la88_be, encode_from_la88_alpha_be, encode_from_rgb555_be, encode_from_argb1555_alpha_be, encode_from_rgb444_be, encode_from_rgba4444_be, encode_from_rgba4444_alpha_be, encode_from_rgb332_be, encode_from_bgr565_be, encode_from_bgr565be_be, encode_from_yuyv_be, encode_from_nv12_be);
+encode_from_endian!("little", le_epi16, encode_from_rgb888_le, encode_from_rgb565_le, encode_from_rgba8888_le, encode_from_rgba8888_alpha_le, encode_from_rgb888_dither_le, encode_from_rgba8888_dither_le, encode_from_l8_le, encode_from_la88_le, encode_from_la88_alpha_le, encode_from_rgb555_le, encode_from_argb1555_alpha_le, encode_from_rgb444_le, encode_from_rgba4444_le, encode_from_rgba4444_alpha_le, encode_from_rgb332_le, encode_from_bgr565_le, encode_from_bgr565be_le, encode_from_yuyv_le, encode_from_nv12_le);
+
+#[cfg(test)]
+mod tests {
+    use crate::common::color::{Argb, Bgr, ColorType, Rgb};
+    use crate::common::spec::{ImageSpec, PixelEndian};
+    use crate::common::pixel::{rgb_to_pixel_with_quantization, LumaCoefficients, Quantization, PIXEL_BYTES};
+    use crate::encodes::logic::scalar;
+    use crate::encodes::logic::{DitherRow, YuvCoefficients};
+    use crate::encodes::yuv::YuvRange;
+    use crate::encodes::options::{Dither, EncodeOptions};
+    use crate::encodes::logic::tests::{NUM_PIXELS, RGB888_DATA, RGB565_DATA, RGBA8888_DATA};
+
+    #[test]
+    fn encode_logic_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut a_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut b_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let rgb888_ptr = RGB888_DATA.as_ptr();
+        let rgb565_ptr = RGB565_DATA.as_ptr().cast::<u8>();
+        let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
+
+        let mut spec = ImageSpec::with_pixel_endian(NUM_PIXELS as u16, 1, PixelEndian::Big);
+        let options = EncodeOptions::new();
+
+        unsafe {
+            super::encode_logic(rgb888_ptr, a_buf.as_mut_ptr(), &spec, &options, ColorType::Rgb888);
+            super::encode_from_rgb888_be::<Rgb>(rgb888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            assert_eq!(a_buf, b_buf);
+
+            super::encode_logic(rgb565_ptr, a_buf.as_mut_ptr(), &spec, &options, ColorType::Rgb565);
+            super::encode_from_rgb565_be(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+
+            super::encode_logic(rgba8888_ptr, a_buf.as_mut_ptr(), &spec, &options, ColorType::Rgba8888);
+            super::encode_from_rgba8888_be::<Rgb>(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            assert_eq!(a_buf, b_buf);
+
+            spec.pixel_endian = PixelEndian::Little;
+            
+            super::encode_logic(rgb888_ptr, a_buf.as_mut_ptr(), &spec, &options, ColorType::Rgb888);
+            super::encode_from_rgb888_le::<Rgb>(rgb888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            assert_eq!(a_buf, b_buf);
+
+            super::encode_logic(rgb565_ptr, a_buf.as_mut_ptr(), &spec, &options, ColorType::Rgb565);
+            super::encode_from_rgb565_le(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(a_buf, b_buf);
+
+            super::encode_logic(rgba8888_ptr, a_buf.as_mut_ptr(), &spec, &options, ColorType::Rgba8888);
+            super::encode_from_rgba8888_le::<Rgb>(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            assert_eq!(a_buf, b_buf);
+        }
+    }
+
+    #[test]
+    fn encode_rgb888_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let data_ptr = RGB888_DATA.as_ptr();
+
+        unsafe {
+            scalar::encode_from_rgb888_be::<Rgb>(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            super::encode_from_rgb888_be::<Rgb>(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+
+        unsafe {
+            scalar::encode_from_rgb888_le::<Rgb>(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            super::encode_from_rgb888_le::<Rgb>(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+    }
+
+    #[test]
+    fn encode_rgb565_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let data_ptr = RGB565_DATA.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::encode_from_rgb565_be(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb565_be(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+
+        unsafe {
+            scalar::encode_from_rgb565_le(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb565_le(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+    }
+
+    #[test]
+    fn encode_rgba8888_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let data_ptr = RGBA8888_DATA.as_ptr();
+
+        unsafe {
+            scalar::encode_from_rgba8888_be::<Rgb>(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            super::encode_from_rgba8888_be::<Rgb>(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+
+        unsafe {
+            scalar::encode_from_rgba8888_le::<Rgb>(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            super::encode_from_rgba8888_le::<Rgb>(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+        }
+
+        assert_eq!(scalar_buf, simd_buf);
+    }
+
+    #[test]
+    fn encode_rgba8888_alpha_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let data_ptr = RGBA8888_DATA.as_ptr();
+        let transparent_color = RGB565_DATA[9];
+
+        for alpha_threshold in [0, 1, 128, 255] {
+            unsafe {
+                let scalar_collisions = scalar::encode_from_rgba8888_alpha_be::<Rgb>(data_ptr, scalar_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, Quantization::Truncate);
+                let simd_collisions = super::encode_from_rgba8888_alpha_be::<Rgb>(data_ptr, simd_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, Quantization::Truncate);
+                assert_eq!(scalar_collisions, simd_collisions);
+            }
+
+            assert_eq!(scalar_buf, simd_buf);
+
+            unsafe {
+                let scalar_collisions = scalar::encode_from_rgba8888_alpha_le::<Rgb>(data_ptr, scalar_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, Quantization::Truncate);
+                let simd_collisions = super::encode_from_rgba8888_alpha_le::<Rgb>(data_ptr, simd_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, Quantization::Truncate);
+                assert_eq!(scalar_collisions, simd_collisions);
+            }
+
+            assert_eq!(scalar_buf, simd_buf);
+        }
+    }
+
+    #[test]
+    fn encode_quantization_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        // Rごとに全GBの組み合わせをエンコードして、全2^24色を確認する
+        const NUM_COLORS: usize = 1 << 16;
+
+        let mut rgb888: Vec<u8> = (0..NUM_COLORS).flat_map(|i| [0, (i >> 8) as u8, i as u8]).collect();
+        let mut rgba8888: Vec<u8> = (0..NUM_COLORS).flat_map(|i| [0, (i >> 8) as u8, i as u8, 255]).collect();
+        let mut simd_buf = vec![0u16; NUM_COLORS];
+
+        for quantization in [Quantization::Truncate, Quantization::Round, Quantization::Nearest] {
+            let rb: [u16; 256] = ::core::array::from_fn(|v| quantization.quantize(v as u8, 5) as u16);
+            let g: [u16; 256] = ::core::array::from_fn(|v| quantization.quantize(v as u8, 6) as u16);
+            let gb: Vec<u16> = (0..NUM_COLORS).map(|i| (g[i >> 8] << 5) | rb[i & 0xFF]).collect();
+
+            for (r, &r_quantized) in rb.iter().enumerate() {
+                rgb888.iter_mut().step_by(3).for_each(|v| *v = r as u8);
+                rgba8888.iter_mut().step_by(4).for_each(|v| *v = r as u8);
+                let expected: Vec<u16> = gb.iter().map(|&gb| ((r_quantized << 11) | gb).to_le()).collect();
+
+                let simd_buf_ptr = simd_buf.as_mut_ptr().cast::<u8>();
+
+                unsafe {
+                    super::encode_from_rgb888_le::<Rgb>(rgb888.as_ptr(), simd_buf_ptr, NUM_COLORS, quantization);
+                    assert_eq!(simd_buf, expected);
+                }
+
+                // RGBA8888は同じ量子化処理を使用するため、Rを間引いて確認する
+                if r % 15 != 0 {
+                    continue;
+                }
+
+                unsafe {
+                    super::encode_from_rgba8888_le::<Rgb>(rgba8888.as_ptr(), simd_buf_ptr, NUM_COLORS, quantization);
+                    assert_eq!(simd_buf, expected);
+
+                    super::encode_from_rgba8888_alpha_le::<Rgb>(rgba8888.as_ptr(), simd_buf_ptr, 0, 128, 0, NUM_COLORS, quantization);
+                    assert_eq!(simd_buf, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encode_bgr_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut rgb_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let bgr888: Vec<u8> = RGB888_DATA.chunks_exact(3).flat_map(|rgb| [rgb[2], rgb[1], rgb[0]]).collect();
+        let bgra8888: Vec<u8> = RGBA8888_DATA.chunks_exact(4).flat_map(|rgba| [rgba[2], rgba[1], rgba[0], rgba[3]]).collect();
+        let transparent_color = RGB565_DATA[9];
+        let dither = DitherRow::new(Dither::Bayer4x4, 1);
+
+        unsafe {
+            scalar::encode_from_rgb888_be::<Bgr>(bgr888.as_ptr(), scalar_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Nearest);
+            super::encode_from_rgb888_be::<Bgr>(bgr888.as_ptr(), simd_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Nearest);
+            super::encode_from_rgb888_be::<Rgb>(RGB888_DATA.as_ptr(), rgb_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Nearest);
+            assert_eq!(scalar_buf, simd_buf);
+            assert_eq!(rgb_buf, simd_buf);
+
+            scalar::encode_from_rgba8888_le::<Bgr>(bgra8888.as_ptr(), scalar_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            super::encode_from_rgba8888_le::<Bgr>(bgra8888.as_ptr(), simd_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            super::encode_from_rgba8888_le::<Rgb>(RGBA8888_DATA.as_ptr(), rgb_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
+            assert_eq!(scalar_buf, simd_buf);
+            assert_eq!(rgb_buf, simd_buf);
+
+            let scalar_collisions = scalar::encode_from_rgba8888_alpha_le::<Bgr>(bgra8888.as_ptr(), scalar_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, Quantization::Truncate);
+            let simd_collisions = super::encode_from_rgba8888_alpha_le::<Bgr>(bgra8888.as_ptr(), simd_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, Quantization::Truncate);
+            assert_eq!(scalar_collisions, simd_collisions);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::encode_from_rgb888_dither_be::<Bgr>(bgr888.as_ptr(), scalar_buf.as_mut_ptr(), NUM_PIXELS, &dither);
+            super::encode_from_rgb888_dither_be::<Bgr>(bgr888.as_ptr(), simd_buf.as_mut_ptr(), NUM_PIXELS, &dither);
+            assert_eq!(scalar_buf, simd_buf);
+
+            let scalar_collisions = scalar::encode_from_rgba8888_dither_le::<Bgr>(bgra8888.as_ptr(), scalar_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, &dither);
+            let simd_collisions = super::encode_from_rgba8888_dither_le::<Bgr>(bgra8888.as_ptr(), simd_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, &dither);
+            assert_eq!(scalar_collisions, simd_collisions);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+    }
+
+    #[test]
+    fn encode_mono1_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let data: [u8; 3] = [0b1010_0110, 0b1111_0000, 0b0101_0000];
+
+        unsafe {
+            scalar::encode_from_mono1(data.as_ptr(), scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_mono1(data.as_ptr(), simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        for (i, pixel) in simd_buf.chunks_exact(PIXEL_BYTES).enumerate() {
+            let expected = if data[i / 8] & (0x80 >> (i % 8)) != 0 { 0xFFFF } else { 0x0000 };
+            assert_eq!(u16::from_ne_bytes([pixel[0], pixel[1]]), expected);
+        }
+    }
+
+    #[test]
+    fn encode_rgb332_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; 256 * PIXEL_BYTES];
+        let mut simd_buf = [0; 256 * PIXEL_BYTES];
+
+        let data: [u8; 256] = ::core::array::from_fn(|i| i as u8);
+
+        unsafe {
+            scalar::encode_from_rgb332_be(data.as_ptr(), scalar_buf.as_mut_ptr(), data.len());
+            super::encode_from_rgb332_be(data.as_ptr(), simd_buf.as_mut_ptr(), data.len());
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::encode_from_rgb332_le(data.as_ptr(), scalar_buf.as_mut_ptr(), data.len());
+            super::encode_from_rgb332_le(data.as_ptr(), simd_buf.as_mut_ptr(), data.len());
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // ビット複製で拡張した値を切り捨てで減色したものと同じ
+        for (pixel, &rgb332) in simd_buf.chunks_exact(PIXEL_BYTES).zip(&data) {
+            let r = (rgb332 >> 5) << 5;
+            let g = ((rgb332 >> 2) & 0x07) << 5;
+            let b = (rgb332 & 0x03) << 6;
+            let expand = |value: u8, bits: usize| (0..8).step_by(bits).fold(0, |acc, shift| acc | (value >> shift));
+            let expected = rgb_to_pixel_with_quantization([expand(r, 3), expand(g, 3), expand(b, 2)], Quantization::Truncate);
+            assert_eq!(u16::from_le_bytes([pixel[0], pixel[1]]), expected);
+        }
+    }
+
+    #[test]
+    fn encode_bgr565_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let bgr_data = RGB565_DATA.map(|pixel| ((pixel & 0x001F) << 11) | (pixel & 0x07E0) | ((pixel & 0xF800) >> 11));
+        let bgr_ptr = bgr_data.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::encode_from_bgr565_le(bgr_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_bgr565_le(bgr_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::encode_from_bgr565_be(bgr_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_bgr565_be(bgr_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // RとBを入れ替えると元のピクセルに戻る
+        for (pixel, &rgb565) in simd_buf.chunks_exact(PIXEL_BYTES).zip(&RGB565_DATA) {
+            assert_eq!(u16::from_be_bytes([pixel[0], pixel[1]]), rgb565);
+        }
+    }
+
+    #[test]
+    fn encode_bgr565be_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let bgr_data = RGB565_DATA.map(|pixel| (((pixel & 0x001F) << 11) | (pixel & 0x07E0) | ((pixel & 0xF800) >> 11)).to_be());
+        let bgr_ptr = bgr_data.as_ptr().cast::<u8>();
+
+        unsafe {
+            scalar::encode_from_bgr565be_le(bgr_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_bgr565be_le(bgr_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            scalar::encode_from_bgr565be_be(bgr_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_bgr565be_be(bgr_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+
+        // RとBを入れ替えると元のピクセルに戻る
+        for (pixel, &rgb565) in simd_buf.chunks_exact(PIXEL_BYTES).zip(&RGB565_DATA) {
+            assert_eq!(u16::from_be_bytes([pixel[0], pixel[1]]), rgb565);
+        }
+    }
+
+    #[test]
+    fn encode_yuv_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        // 奇数ピクセルで端数処理も確認する
+        const YUV_PIXELS: usize = 2 * NUM_PIXELS - 1;
+
+        let mut scalar_buf = [0; YUV_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; YUV_PIXELS * PIXEL_BYTES];
+
+        // 範囲外で飽和する値を含める
+        let yuyv_data: [u8; 4 * NUM_PIXELS] = ::core::array::from_fn(|i| match i % 7 { 0 => 0, 1 => 255, _ => (i * 73 + 11) as u8 });
+        let y_data: [u8; 2 * NUM_PIXELS] = ::core::array::from_fn(|i| yuyv_data[2 * i]);
+        let uv_data: [u8; 2 * NUM_PIXELS] = ::core::array::from_fn(|i| yuyv_data[4 * (i / 2) + 1 + 2 * (i % 2)]);
+
+        let yuyv_ptr = yuyv_data.as_ptr();
+        let y_ptr = y_data.as_ptr();
+        let uv_ptr = uv_data.as_ptr();
+
+        let le_spec = ImageSpec::with_pixel_endian(YUV_PIXELS as u16, 1, PixelEndian::Little);
+
+        for luma in [LumaCoefficients::Bt601, LumaCoefficients::Bt709] {
+            for range in [YuvRange::Full, YuvRange::Limited] {
+                let coefficients = YuvCoefficients::new(luma, range);
+
+                for quantization in [Quantization::Truncate, Quantization::Round, Quantization::Nearest] {
+                    unsafe {
+                        scalar::encode_from_yuyv_be(yuyv_ptr, scalar_buf.as_mut_ptr(), YUV_PIXELS, &coefficients, quantization);
+                        super::encode_from_yuyv_be(yuyv_ptr, simd_buf.as_mut_ptr(), YUV_PIXELS, &coefficients, quantization);
+                        assert_eq!(scalar_buf, simd_buf);
+
+                        // NV12は同じY, U, VのYUYVと一致する
+                        super::encode_from_nv12_be(y_ptr, uv_ptr, simd_buf.as_mut_ptr(), YUV_PIXELS, &coefficients, quantization);
+                        assert_eq!(scalar_buf, simd_buf);
+
+                        scalar::encode_from_yuyv_le(yuyv_ptr, scalar_buf.as_mut_ptr(), YUV_PIXELS, &coefficients, quantization);
+                        super::encode_yuyv_logic(yuyv_ptr, simd_buf.as_mut_ptr(), &le_spec, &coefficients, quantization, YUV_PIXELS);
+                        assert_eq!(scalar_buf, simd_buf);
+
+                        scalar::encode_from_nv12_le(y_ptr, uv_ptr, scalar_buf.as_mut_ptr(), YUV_PIXELS, &coefficients, quantization);
+                        super::encode_nv12_logic(y_ptr, uv_ptr, simd_buf.as_mut_ptr(), &le_spec, &coefficients, quantization, YUV_PIXELS);
+                        assert_eq!(scalar_buf, simd_buf);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encode_rgb444_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        // RGBA8888の各チャンネルの上位4ビット
+        let mut rgba_data = [0u16; NUM_PIXELS];
+        for (rgba4444, rgba) in rgba_data.iter_mut().zip(RGBA8888_DATA.chunks_exact(4)) {
+            *rgba4444 = rgba.iter().fold(0, |acc, &value| (acc << 4) | (value as u16 >> 4));
+        }
+        let rgb_data = rgba_data.map(|rgba| rgba >> 4);
+
+        let rgba_ptr = rgba_data.as_ptr().cast::<u8>();
+        let rgb_ptr = rgb_data.as_ptr().cast::<u8>();
+
+        // 不透明な赤（2番目）は透明色と衝突する
+        let transparent_color = 0xF800;
+
+        unsafe {
+            scalar::encode_from_rgb444_be(rgb_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb444_be(rgb_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            super::encode_from_rgba4444_be(rgba_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            // ビット複製で拡張した値を切り捨てで減色したものと同じ
+            for (pixel, rgba) in simd_buf.chunks_exact(PIXEL_BYTES).zip(RGBA8888_DATA.chunks_exact(4)) {
+                let expand = |value: u8| (value & 0xF0) | (value >> 4);
+                let expected = rgb_to_pixel_with_quantization([expand(rgba[0]), expand(rgba[1]), expand(rgba[2])], Quantization::Truncate);
+                assert_eq!(u16::from_be_bytes([pixel[0], pixel[1]]), expected);
+            }
+
+            for (alpha_threshold, collision_xor) in [(128, 0), (0, 0x0020), (128, 0x0020)] {
+                let scalar_collisions = scalar::encode_from_rgba4444_alpha_le(rgba_ptr, scalar_buf.as_mut_ptr(), transparent_color, alpha_threshold, collision_xor, NUM_PIXELS);
+                let simd_collisions = super::encode_from_rgba4444_alpha_le(rgba_ptr, simd_buf.as_mut_ptr(), transparent_color, alpha_threshold, collision_xor, NUM_PIXELS);
+                assert_eq!((scalar_collisions, scalar_buf), (simd_collisions, simd_buf));
+            }
+        }
+    }
+
+    #[test]
+    fn encode_rgb555_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        // RGB565からGの最下位ビットを除き、偶数番目のピクセルのAを 1 にする
+        let mut argb_data = [0u16; NUM_PIXELS];
+        for (i, (argb, &pixel)) in argb_data.iter_mut().zip(&RGB565_DATA).enumerate() {
+            *argb = ((pixel >> 1) & 0x7FE0) | (pixel & 0x001F) | if i % 2 == 0 { 0x8000 } else { 0 };
+        }
+        let argb_ptr = argb_data.as_ptr().cast::<u8>();
+
+        let transparent_color = RGB565_DATA[4];
+
+        unsafe {
+            scalar::encode_from_rgb555_be(argb_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb555_be(argb_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            assert_eq!(scalar_buf, simd_buf);
+
+            // Gの最下位ビットはGの最上位ビットで補われる
+            for (pixel, &rgb565) in simd_buf.chunks_exact(PIXEL_BYTES).zip(&RGB565_DATA) {
+                let expected = (rgb565 & !0x0020) | ((rgb565 >> 5) & 0x0020);
+                assert_eq!(u16::from_be_bytes([pixel[0], pixel[1]]), expected);
+            }
+
+            for (alpha_threshold, collision_xor) in [(128, 0), (0, 0x0020), (128, 0x0020)] {
+                let scalar_collisions = scalar::encode_from_argb1555_alpha_le(argb_ptr, scalar_buf.as_mut_ptr(), transparent_color, alpha_threshold, collision_xor, NUM_PIXELS);
+                let simd_collisions = super::encode_from_argb1555_alpha_le(argb_ptr, simd_buf.as_mut_ptr(), transparent_color, alpha_threshold, collision_xor, NUM_PIXELS);
+                assert_eq!((scalar_collisions, scalar_buf), (simd_collisions, simd_buf));
+            }
+        }
+    }
+
+    #[test]
+    fn encode_argb_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut rgb_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        // RGBA8888をネイティブエンディアンの0xAARRGGBBに並べ替える
+        let mut argb_data = [0; 4 * NUM_PIXELS];
+        for (argb, rgba) in argb_data.chunks_exact_mut(4).zip(RGBA8888_DATA.chunks_exact(4)) {
+            argb.copy_from_slice(&u32::from_be_bytes([rgba[3], rgba[0], rgba[1], rgba[2]]).to_ne_bytes());
+        }
+
+        let transparent_color = RGB565_DATA[9];
+        let dither = DitherRow::new(Dither::Bayer4x4, 1);
+
+        unsafe {
+            scalar::encode_from_rgba8888_le::<Argb>(argb_data.as_ptr(), scalar_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Round);
+            super::encode_from_rgba8888_le::<Argb>(argb_data.as_ptr(), simd_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Round);
+            super::encode_from_rgba8888_le::<Rgb>(RGBA8888_DATA.as_ptr(), rgb_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Round);
+            assert_eq!(scalar_buf, simd_buf);
+            assert_eq!(simd_buf, rgb_buf);
+
+            let scalar_collisions = scalar::encode_from_rgba8888_alpha_be::<Argb>(argb_data.as_ptr(), scalar_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, Quantization::Truncate);
+            let simd_collisions = super::encode_from_rgba8888_alpha_be::<Argb>(argb_data.as_ptr(), simd_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, Quantization::Truncate);
+            let rgb_collisions = super::encode_from_rgba8888_alpha_be::<Rgb>(RGBA8888_DATA.as_ptr(), rgb_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, Quantization::Truncate);
+            assert_eq!((scalar_collisions, scalar_buf), (simd_collisions, simd_buf));
+            assert_eq!((simd_collisions, simd_buf), (rgb_collisions, rgb_buf));
+
+            let scalar_collisions = scalar::encode_from_rgba8888_dither_le::<Argb>(argb_data.as_ptr(), scalar_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, &dither);
+            let simd_collisions = super::encode_from_rgba8888_dither_le::<Argb>(argb_data.as_ptr(), simd_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, &dither);
+            let rgb_collisions = super::encode_from_rgba8888_dither_le::<Rgb>(RGBA8888_DATA.as_ptr(), rgb_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, &dither);
+            assert_eq!((scalar_collisions, scalar_buf), (simd_collisions, simd_buf));
+            assert_eq!((simd_collisions, simd_buf), (rgb_collisions, rgb_buf));
+        }
+    }
+
+    #[test]
+    fn encode_gray_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        // RGB888のRを輝度、RGBA8888のアルファ値をそのまま使用する
+        let l8_data: [u8; NUM_PIXELS] = ::core::array::from_fn(|i| RGB888_DATA[3 * i]);
+        let la88_data: [u8; 2 * NUM_PIXELS] = ::core::array::from_fn(|i| if i % 2 == 0 { RGB888_DATA[3 * (i / 2)] } else { RGBA8888_DATA[4 * (i / 2) + 3] });
+        let transparent_color = RGB565_DATA[15];
+
+        for quantization in [Quantization::Truncate, Quantization::Round, Quantization::Nearest] {
+            unsafe {
+                scalar::encode_from_l8_be(l8_data.as_ptr(), scalar_buf.as_mut_ptr(), NUM_PIXELS, quantization);
+                super::encode_from_l8_be(l8_data.as_ptr(), simd_buf.as_mut_ptr(), NUM_PIXELS, quantization);
+                assert_eq!(scalar_buf, simd_buf);
+
+                for (pixel, &l) in simd_buf.chunks_exact(PIXEL_BYTES).zip(l8_data.iter()) {
+                    assert_eq!(u16::from_be_bytes([pixel[0], pixel[1]]), rgb_to_pixel_with_quantization([l, l, l], quantization));
+                }
+
+                scalar::encode_from_la88_le(la88_data.as_ptr(), scalar_buf.as_mut_ptr(), NUM_PIXELS, quantization);
+                super::encode_from_la88_le(la88_data.as_ptr(), simd_buf.as_mut_ptr(), NUM_PIXELS, quantization);
+                assert_eq!(scalar_buf, simd_buf);
+
+                for alpha_threshold in [0, 1, 128, 255] {
+                    let scalar_collisions = scalar::encode_from_la88_alpha_be(la88_data.as_ptr(), scalar_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, quantization);
+                    let simd_collisions = super::encode_from_la88_alpha_be(la88_data.as_ptr(), simd_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, quantization);
+                    assert_eq!(scalar_collisions, simd_collisions);
+                    assert_eq!(scalar_buf, simd_buf);
+
+                    let scalar_collisions = scalar::encode_from_la88_alpha_le(la88_data.as_ptr(), scalar_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, quantization);
+                    let simd_collisions = super::encode_from_la88_alpha_le(la88_data.as_ptr(), simd_buf.as_mut_ptr(), transparent_color, alpha_threshold, 0x0020, NUM_PIXELS, quantization);
+                    assert_eq!(scalar_collisions, simd_collisions);
+                    assert_eq!(scalar_buf, simd_buf);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn replace_pixels_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        for from in [RGB565_DATA[0], RGB565_DATA[19]] {
+            let mut scalar_buf = RGB565_DATA;
+            let mut simd_buf = RGB565_DATA;
+
+            let (scalar_replaced, simd_replaced) = unsafe {
+                (
+                    scalar::replace_pixels(scalar_buf.as_mut_ptr().cast::<u8>(), from, 0x1234, NUM_PIXELS),
+                    super::replace_pixels(simd_buf.as_mut_ptr().cast::<u8>(), from, 0x1234, NUM_PIXELS),
+                )
+            };
+
+            assert_eq!(scalar_replaced, simd_replaced);
+            assert_eq!(scalar_buf, simd_buf);
+        }
+    }
+
+    #[test]
+    fn encode_collision_free_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let rgb888_ptr = RGB888_DATA.as_ptr();
+        let rgb565_ptr = RGB565_DATA.as_ptr().cast::<u8>();
+        let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
+
+        let options = EncodeOptions::new();
+
+        for pixel_endian in [PixelEndian::Big, PixelEndian::Little] {
+            let spec = ImageSpec { transparent_color: Some(RGB565_DATA[9]), ..ImageSpec::with_pixel_endian(NUM_PIXELS as u16, 1, pixel_endian) };
+
+            for (data_ptr, color_type) in [(rgb888_ptr, ColorType::Rgb888), (rgb565_ptr, ColorType::Rgb565), (rgba8888_ptr, ColorType::Rgba8888)] {
+                let (scalar_adjusted, simd_adjusted) = unsafe {
+                    (
+                        scalar::encode_collision_free_logic(data_ptr, scalar_buf.as_mut_ptr(), &spec, &options, color_type),
+                        super::encode_collision_free_logic(data_ptr, simd_buf.as_mut_ptr(), &spec, &options, color_type),
+                    )
+                };
+
+                assert_eq!(scalar_adjusted, simd_adjusted);
+                assert_eq!(scalar_buf, simd_buf);
+            }
+        }
+    }
+
+    #[test]
+    fn encode_dither_x86_64_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        let mut scalar_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+        let mut simd_buf = [0; NUM_PIXELS * PIXEL_BYTES];
+
+        let rgb888_ptr = RGB888_DATA.as_ptr();
+        let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
+        let transparent_color = RGB565_DATA[9];
+
+        for (dither, y) in [(Dither::Bayer4x4, 0), (Dither::Bayer4x4, 3), (Dither::Bayer8x8, 5)] {
+            for shift in [0, 3] {
+                let dither = DitherRow::new(dither, y).shifted(shift);
+
+                unsafe {
+                    scalar::encode_from_rgb888_dither_be::<Rgb>(rgb888_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, &dither);
+                    super::encode_from_rgb888_dither_be::<Rgb>(rgb888_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, &dither);
+                    assert_eq!(scalar_buf, simd_buf);
+
+                    scalar::encode_from_rgb888_dither_le::<Rgb>(rgb888_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS, &dither);
+                    super::encode_from_rgb888_dither_le::<Rgb>(rgb888_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS, &dither);
+                    assert_eq!(scalar_buf, simd_buf);
+
+                    let scalar_collisions = scalar::encode_from_rgba8888_dither_be::<Rgb>(rgba8888_ptr, scalar_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, &dither);
+                    let simd_collisions = super::encode_from_rgba8888_dither_be::<Rgb>(rgba8888_ptr, simd_buf.as_mut_ptr(), transparent_color, 128, 0x0020, NUM_PIXELS, &dither);
+                    assert_eq!(scalar_collisions, simd_collisions);
+                    assert_eq!(scalar_buf, simd_buf);
+
+                    let scalar_collisions = scalar::encode_from_rgba8888_dither_le::<Rgb>(rgba8888_ptr, scalar_buf.as_mut_ptr(), transparent_color, 0, 0x0020, NUM_PIXELS, &dither);
+                    let simd_collisions = super::encode_from_rgba8888_dither_le::<Rgb>(rgba8888_ptr, simd_buf.as_mut_ptr(), transparent_color, 0, 0x0020, NUM_PIXELS, &dither);
+                    assert_eq!(scalar_collisions, simd_collisions);
+                    assert_eq!(scalar_buf, simd_buf);
+                }
+            }
+        }
+
+        // 行単位のディスパッチも一致する
+        let options = EncodeOptions { dither: Dither::Bayer8x8, ..EncodeOptions::new() };
+        let spec = ImageSpec::with_pixel_endian(NUM_PIXELS as u16 / 2, 2, PixelEndian::Big);
 
         unsafe {
-            scalar::encode_from_rgba8888_le(data_ptr, scalar_buf.as_mut_ptr(), NUM_PIXELS);
-            super::encode_from_rgba8888_le(data_ptr, simd_buf.as_mut_ptr(), NUM_PIXELS);
+            scalar::encode_logic(rgb888_ptr, scalar_buf.as_mut_ptr(), &spec, &options, ColorType::Rgb888);
+            super::encode_logic(rgb888_ptr, simd_buf.as_mut_ptr(), &spec, &options, ColorType::Rgb888);
         }
 
         assert_eq!(scalar_buf, simd_buf);
@@ -366,19 +1871,19 @@ mod tests {
         let rgba8888_ptr  = RGBA8888_DATA.as_ptr();
 
         unsafe {
-            super::encode_from_rgb888_be(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb888_be::<Rgb>(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
 
             super::encode_from_rgb565_be(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
             assert_eq!(a_buf, b_buf);
-            super::encode_from_rgba8888_be(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgba8888_be::<Rgb>(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
             assert_eq!(a_buf, b_buf);
 
             
-            super::encode_from_rgb888_le(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgb888_le::<Rgb>(rgb888_ptr, a_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
 
             super::encode_from_rgb565_le(rgb565_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
             assert_eq!(a_buf, b_buf);
-            super::encode_from_rgba8888_le(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS);
+            super::encode_from_rgba8888_le::<Rgb>(rgba8888_ptr, b_buf.as_mut_ptr(), NUM_PIXELS, Quantization::Truncate);
             assert_eq!(a_buf, b_buf);
         }
     }
@@ -3,12 +3,12 @@
 #[cfg(any(test, target_feature = "avx2"))]
 mod avx2;
 #[cfg(target_feature = "avx2")]
-pub use avx2::encode_logic;
+pub use avx2::{encode_logic, encode_pixels_logic, encode_dither_logic, encode_collision_free_logic, encode_yuyv_logic, encode_nv12_logic};
 
 #[cfg(any(test, all(not(target_feature = "avx2"), target_feature = "ssse3")))]
 mod ssse3;
 #[cfg(all(not(target_feature = "avx2"), target_feature = "ssse3"))]
-pub use ssse3::encode_logic;
+pub use ssse3::{encode_logic, encode_pixels_logic, encode_dither_logic, encode_collision_free_logic, encode_yuyv_logic, encode_nv12_logic};
 
 #[cfg(all(not(target_feature = "avx2"), not(target_feature = "ssse3")))]
-pub use crate::encodes::logic::scalar::encode_logic;
+pub use crate::encodes::logic::scalar::{encode_logic, encode_pixels_logic, encode_dither_logic, encode_collision_free_logic, encode_yuyv_logic, encode_nv12_logic};
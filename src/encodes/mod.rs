@@ -1,9 +1,14 @@
 mod logic;
+pub mod options;
+pub mod transparent;
+pub mod diffusion;
+pub mod yuv;
 
 use crate::common::color::ColorType;
 use crate::common::header::{ImageHeader, CURRENT_VARSION, FLAG_USE_TRANSPARENT_BIT, HEADER_SIZE, SIGNATURE_U32_NE};
 use crate::common::spec::ImageSpec;
 use crate::common::pixel::PIXEL_BYTES;
+use crate::encodes::options::{Dither, EncodeOptions};
 use crate::error::{Error, Result};
 
 /// `spec`からエンコードに必要なバイト数を取得します。
@@ -28,6 +33,9 @@ pub const fn encoded_size(spec: &ImageSpec) -> usize {
 
 /// `data`と`spec`、`color_type`からLimg形式データをエンコードし、`buf`に書き込みます。
 /// 
+/// `color_type`が`ColorType::Rgba8888`などのアルファ付きの色データタイプで`spec.transparent_color`が指定されている場合、
+/// アルファ値が[`EncodeOptions::DEFAULT_ALPHA_THRESHOLD`]未満のピクセルは透明色になります。
+/// 
 /// エラーではなかった場合、書き込まれたバイト数を返します。
 /// 
 /// # Errors
@@ -59,12 +67,53 @@ pub const fn encoded_size(spec: &ImageSpec) -> usize {
 /// ```
 #[inline]
 pub fn encode(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType) -> Result<usize> {
+    encode_with_options(data, buf, spec, color_type, &EncodeOptions::new())
+}
+
+/// `data`と`spec`、`color_type`から`options`を使用してLimg形式データをエンコードし、`buf`に書き込みます。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+/// 
+/// `data`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{ColorType, EncodeOptions, ImageSpec, decode, encode_with_options, encoded_size, rgb_to_pixel};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = [
+///     255, 0, 0, 255,
+///     0, 255, 0, 100,
+/// ];
+/// 
+/// let spec = ImageSpec::with_transparent_color(2, 1, rgb_to_pixel([255, 0, 255]));
+/// let options = EncodeOptions { alpha_threshold: 200, ..EncodeOptions::new() };
+/// 
+/// let mut buf = vec![0u8; encoded_size(&spec)];
+/// encode_with_options(&data, &mut buf, &spec, ColorType::Rgba8888, &options)?;
+/// 
+/// let mut rgba = [0u8; 8];
+/// decode(&buf, &mut rgba, ColorType::Rgba8888)?;
+/// 
+/// assert_eq!(rgba[3], 255);
+/// assert_eq!(rgba[7], 0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_with_options(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, options: &EncodeOptions) -> Result<usize> {
     let num_pixels = spec.width as usize * spec.height as usize;
 
     if num_pixels == 0 {
         return Err(Error::ZeroImageDimensions);
     }
-    if data.len() < color_type.bytes_per_pixel() * num_pixels {
+    if data.len() < color_type.bytes_per_row(spec.width as usize) * spec.height as usize {
         return Err(Error::InputBufferTooSmall);
     }
     if buf.len() < HEADER_SIZE + PIXEL_BYTES * num_pixels {
@@ -75,7 +124,7 @@ pub fn encode(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorTy
 
     unsafe {
         written_size += encode_header_unchecked(buf.get_unchecked_mut(..HEADER_SIZE), spec);
-        written_size += encode_data_unchecked(data, buf.get_unchecked_mut(HEADER_SIZE..), spec, color_type);
+        written_size += encode_data_unchecked(data, buf.get_unchecked_mut(HEADER_SIZE..), spec, options, color_type);
     }
 
     debug_assert_eq!(written_size, encoded_size(spec));
@@ -151,6 +200,9 @@ unsafe fn encode_header_unchecked(buf: &mut [u8], spec: &ImageSpec) -> usize {
 
 /// `data`と`spec`、`color_type`から色データをエンコードし、`buf`に書き込みます。
 /// 
+/// `color_type`が`ColorType::Rgba8888`などのアルファ付きの色データタイプで`spec.transparent_color`が指定されている場合、
+/// アルファ値が[`EncodeOptions::DEFAULT_ALPHA_THRESHOLD`]未満のピクセルは透明色になります。
+/// 
 /// エラーではなかった場合、書き込まれたバイト数を返します。
 /// 
 /// # Errors
@@ -180,9 +232,40 @@ unsafe fn encode_header_unchecked(buf: &mut [u8], spec: &ImageSpec) -> usize {
 /// ```
 #[inline]
 pub fn encode_data(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType) -> Result<usize> {
+    encode_data_with_options(data, buf, spec, color_type, &EncodeOptions::new())
+}
+
+/// `data`と`spec`、`color_type`から`options`を使用して色データをエンコードし、`buf`に書き込みます。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{ColorType, EncodeOptions, ImageSpec, encode_data_with_options, rgb_to_pixel};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let transparent_color = rgb_to_pixel([255, 0, 255]);
+/// let spec = ImageSpec::with_transparent_color(1, 1, transparent_color);
+/// let options = EncodeOptions { alpha_threshold: 200, ..EncodeOptions::new() };
+/// 
+/// let mut buf = [0u8; 2];
+/// encode_data_with_options(&[0, 255, 0, 100], &mut buf, &spec, ColorType::Rgba8888, &options)?;
+/// 
+/// assert_eq!(buf, transparent_color.to_le_bytes());
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_data_with_options(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, options: &EncodeOptions) -> Result<usize> {
     let num_pixels = spec.num_pixels();
 
-    if data.len() < color_type.bytes_per_pixel() * num_pixels {
+    if data.len() < color_type.bytes_per_row(spec.width as usize) * spec.height as usize {
         return Err(Error::InputBufferTooSmall);
     }
 
@@ -191,11 +274,360 @@ pub fn encode_data(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: Co
     }
 
     unsafe {
-        Ok(encode_data_unchecked(data, buf, spec, color_type))
+        Ok(encode_data_unchecked(data, buf, spec, options, color_type))
+    }
+}
+
+/// `data`と`spec`、`color_type`から`options`を使用してLimg形式データをエンコードし、`buf`に書き込みます。
+/// 
+/// 減色によって`spec.transparent_color`と同じ値になる不透明なピクセルは、
+/// Gの最下位ビットを反転した値に置き換えられるため、デコード時に透明になりません。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数と置き換えられたピクセル数を返します。
+/// 
+/// # Errors
+/// 
+/// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+/// 
+/// `data`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{ColorType, EncodeOptions, ImageSpec, decode, encode_collision_free, encoded_size, rgb_to_pixel};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = ImageSpec::with_transparent_color(2, 1, rgb_to_pixel([255, 0, 255]));
+/// let data = [
+///     255, 0, 255,
+///     255, 1, 254,
+/// ];
+/// 
+/// let mut buf = vec![0u8; encoded_size(&spec)];
+/// let (_, adjusted_pixels) = encode_collision_free(&data, &mut buf, &spec, ColorType::Rgb888, &EncodeOptions::new())?;
+/// assert_eq!(adjusted_pixels, 2);
+/// 
+/// let mut rgba = [0u8; 8];
+/// decode(&buf, &mut rgba, ColorType::Rgba8888)?;
+/// assert_eq!([rgba[3], rgba[7]], [255, 255]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_collision_free(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, options: &EncodeOptions) -> Result<(usize, usize)> {
+    let num_pixels = spec.num_pixels();
+
+    if num_pixels == 0 {
+        return Err(Error::ZeroImageDimensions);
+    }
+    if buf.len() < HEADER_SIZE + PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    unsafe {
+        let (data_size, adjusted_pixels) = encode_data_collision_free(data, buf.get_unchecked_mut(HEADER_SIZE..), spec, color_type, options)?;
+        let written_size = encode_header_unchecked(buf.get_unchecked_mut(..HEADER_SIZE), spec) + data_size;
+
+        debug_assert_eq!(written_size, encoded_size(spec));
+
+        Ok((written_size, adjusted_pixels))
     }
 }
 
+/// `data`と`spec`、`color_type`から`options`を使用して色データをエンコードし、`buf`に書き込みます。
+/// 
+/// 減色によって`spec.transparent_color`と同じ値になる不透明なピクセルは、
+/// Gの最下位ビットを反転した値に置き換えられるため、デコード時に透明になりません。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数と置き換えられたピクセル数を返します。
+/// 
+/// # Errors
+/// 
+/// `data`の長さが（色バイト数 * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{ColorType, EncodeOptions, ImageSpec, encode_data_collision_free, rgb_to_pixel};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let transparent_color = rgb_to_pixel([255, 0, 255]);
+/// let spec = ImageSpec::with_transparent_color(1, 1, transparent_color);
+/// 
+/// let mut buf = [0u8; 2];
+/// let (_, adjusted_pixels) = encode_data_collision_free(&[255, 0, 255], &mut buf, &spec, ColorType::Rgb888, &EncodeOptions::new())?;
+/// 
+/// assert_eq!(adjusted_pixels, 1);
+/// assert_eq!(buf, rgb_to_pixel([255, 4, 255]).to_le_bytes());
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_data_collision_free(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType, options: &EncodeOptions) -> Result<(usize, usize)> {
+    let num_pixels = spec.num_pixels();
+
+    if data.len() < color_type.bytes_per_row(spec.width as usize) * spec.height as usize {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    if buf.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    let adjusted_pixels = unsafe {
+        logic::encode_collision_free_logic(data.as_ptr(), buf.as_mut_ptr(), spec, options, color_type)
+    };
+
+    Ok((PIXEL_BYTES * num_pixels, adjusted_pixels))
+}
+
+/// 1行あたり`stride`バイトの`data`の`(x, y)`ピクセル位置から`spec`の大きさの領域を切り出し、Limg形式データにエンコードして`buf`に書き込みます。
+/// 
+/// 各行は`data`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から読み込まれます。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `data`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use limg_core::{ImageSpec, encode_strided, encoded_size};
+/// # use limg_core::ColorType;
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 640x480のRGBA8888キャンバス
+/// let canvas = std::fs::read("canvas.bin")?;
+/// let stride = ColorType::Rgba8888.bytes_per_pixel() * 640;
+/// 
+/// // (100, 50)から64x64の領域をエンコード
+/// let spec = ImageSpec::new(64, 64);
+/// let mut buf = vec![0u8; encoded_size(&spec)];
+/// 
+/// encode_strided(&canvas, &mut buf, &spec, stride, 100, 50, ColorType::Rgba8888)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn encode_strided(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, x: usize, y: usize, color_type: ColorType) -> Result<usize> {
+    encode_strided_with_options(data, buf, spec, stride, x, y, color_type, &EncodeOptions::new())
+}
+
+/// 1行あたり`stride`バイトの`data`の`(x, y)`ピクセル位置から`spec`の大きさの領域を切り出し、`options`を使用してLimg形式データにエンコードして`buf`に書き込みます。
+/// 
+/// 各行は`data`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から読み込まれます。
+/// 順序ディザリングのしきい値は`data`上のピクセル位置で決まるため、領域を分けてエンコードしても画像全体をエンコードした場合と同じ結果になります。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `data`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use limg_core::{Dither, EncodeOptions, ImageSpec, encode_strided_with_options, encoded_size};
+/// # use limg_core::ColorType;
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 640x480のRGBA8888キャンバス
+/// let canvas = std::fs::read("canvas.bin")?;
+/// let stride = ColorType::Rgba8888.bytes_per_pixel() * 640;
+/// 
+/// // (100, 50)から64x64の領域をエンコード
+/// let spec = ImageSpec::new(64, 64);
+/// let mut buf = vec![0u8; encoded_size(&spec)];
+/// 
+/// let options = EncodeOptions { dither: Dither::Bayer4x4, ..EncodeOptions::new() };
+/// encode_strided_with_options(&canvas, &mut buf, &spec, stride, 100, 50, ColorType::Rgba8888, &options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn encode_strided_with_options(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, x: usize, y: usize, color_type: ColorType, options: &EncodeOptions) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if num_pixels == 0 {
+        return Err(Error::ZeroImageDimensions);
+    }
+    if buf.len() < HEADER_SIZE + PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    let mut written_size = 0;
+
+    unsafe {
+        written_size += encode_data_strided_with_options(data, buf.get_unchecked_mut(HEADER_SIZE..), spec, stride, x, y, color_type, options)?;
+        written_size += encode_header_unchecked(buf.get_unchecked_mut(..HEADER_SIZE), spec);
+    }
+
+    debug_assert_eq!(written_size, encoded_size(spec));
+
+    Ok(written_size)
+}
+
+/// 1行あたり`stride`バイトの`data`の`(x, y)`ピクセル位置から`spec`の大きさの領域を切り出し、色データをエンコードして`buf`に書き込みます。
+/// 
+/// 各行は`data`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から読み込まれます。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `data`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{ImageSpec, encode_data_strided, rgb_to_pixel, ColorType};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 3x2のRGB888バッファから右下の2x1を切り出す
+/// let data = [
+///     0, 0, 0,  0, 0, 0,    0, 0, 0,
+///     0, 0, 0,  255, 0, 0,  0, 0, 255,
+/// ];
+/// 
+/// let spec = ImageSpec::new(2, 1);
+/// let mut buf = [0u8; 4];
+/// 
+/// encode_data_strided(&data, &mut buf, &spec, 9, 1, 1, ColorType::Rgb888)?;
+/// 
+/// assert_eq!(buf[..2], rgb_to_pixel([255, 0, 0]).to_le_bytes());
+/// assert_eq!(buf[2..], rgb_to_pixel([0, 0, 255]).to_le_bytes());
+/// # Ok(())
+/// # }
+/// ```
+#[inline]
+pub fn encode_data_strided(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, x: usize, y: usize, color_type: ColorType) -> Result<usize> {
+    encode_data_strided_with_options(data, buf, spec, stride, x, y, color_type, &EncodeOptions::new())
+}
+
+/// 1行あたり`stride`バイトの`data`の`(x, y)`ピクセル位置から`spec`の大きさの領域を切り出し、`options`を使用して色データをエンコードして`buf`に書き込みます。
+/// 
+/// 各行は`data`の（`stride` * (`y` + 行番号) + 色バイト数 * `x`）バイト目から読み込まれます。
+/// 順序ディザリングのしきい値は`data`上のピクセル位置で決まるため、領域を分けてエンコードしても画像全体をエンコードした場合と同じ結果になります。
+/// 
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+/// 
+/// # Errors
+/// 
+/// `stride`が（色バイト数 * (`x` + 幅)）未満の場合、`Error::InvalidStride`を返します。
+/// 
+/// `color_type`が`ColorType::Mono1`の場合、`Error::UnsupportedColorType`を返します。
+/// 
+/// `data`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+/// 
+/// `buf`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+/// 
+/// # Examples
+/// 
+/// ```
+/// use limg_core::{EncodeOptions, ImageSpec, encode_data_strided_with_options, rgb_to_pixel, ColorType};
+/// 
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 3x2のRGB888バッファから右下の2x1を切り出す
+/// let data = [
+///     0, 0, 0,  0, 0, 0,    0, 0, 0,
+///     0, 0, 0,  255, 0, 0,  0, 0, 255,
+/// ];
+/// 
+/// let spec = ImageSpec::new(2, 1);
+/// let mut buf = [0u8; 4];
+/// 
+/// encode_data_strided_with_options(&data, &mut buf, &spec, 9, 1, 1, ColorType::Rgb888, &EncodeOptions::new())?;
+/// 
+/// assert_eq!(buf[..2], rgb_to_pixel([255, 0, 0]).to_le_bytes());
+/// assert_eq!(buf[2..], rgb_to_pixel([0, 0, 255]).to_le_bytes());
+/// # Ok(())
+/// # }
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn encode_data_strided_with_options(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, x: usize, y: usize, color_type: ColorType, options: &EncodeOptions) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if !color_type.is_byte_addressable() {
+        return Err(Error::UnsupportedColorType);
+    }
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    if buf.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    if num_pixels == 0 {
+        return Ok(0);
+    }
+
+    let width = spec.width as usize;
+
+    let row_end = x.checked_mul(bytes_per_pixel)
+        .and_then(|offset| offset.checked_add(bytes_per_pixel * width))
+        .ok_or(Error::InvalidStride)?;
+
+    if stride < row_end {
+        return Err(Error::InvalidStride);
+    }
+
+    let required_size = y.checked_add(spec.height as usize - 1)
+        .and_then(|last_row| last_row.checked_mul(stride))
+        .and_then(|offset| offset.checked_add(row_end))
+        .ok_or(Error::InputBufferTooSmall)?;
+
+    if data.len() < required_size {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    let dither = options.dither != Dither::None && logic::is_dither_target(color_type);
+
+    unsafe {
+        let data = data.as_ptr().add(stride * y + bytes_per_pixel * x);
+
+        for row in 0..spec.height as usize {
+            let data = data.add(stride * row);
+            let buf = buf.as_mut_ptr().add(PIXEL_BYTES * width * row);
+
+            if dither {
+                logic::encode_dither_logic(data, buf, spec, options, color_type, width, x, y + row, 0);
+            } else {
+                logic::encode_pixels_logic(data, buf, spec, options, color_type, width);
+            }
+        }
+    }
+
+    Ok(PIXEL_BYTES * num_pixels)
+}
+
 #[inline(always)]
-unsafe fn encode_data_unchecked(data: &[u8], buf: &mut [u8], spec: &ImageSpec, color_type: ColorType) -> usize {
-    unsafe { logic::encode_logic(data.as_ptr(), buf.as_mut_ptr(), spec, color_type) }
+unsafe fn encode_data_unchecked(data: &[u8], buf: &mut [u8], spec: &ImageSpec, options: &EncodeOptions, color_type: ColorType) -> usize {
+    unsafe { logic::encode_logic(data.as_ptr(), buf.as_mut_ptr(), spec, options, color_type) }
 }
@@ -0,0 +1,124 @@
+use crate::common::pixel::Quantization;
+
+/// エンコード時に適用する順序ディザリング
+///
+/// RGB565への減色で発生するバンディングを、ピクセル位置に応じたしきい値で目立たなくします。
+/// ファイル形式には影響しません。
+///
+/// [`DecodeOptions::dither`](crate::DecodeOptions::dither)と[`DecodeOptions::mono_dither`](crate::DecodeOptions::mono_dither)として、
+/// `ColorType::Rgb332`と`ColorType::Mono1`へのデコードにも使用します。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dither {
+    /// ディザリングしない
+    #[default]
+    None,
+
+    /// 4x4のBayer行列
+    Bayer4x4,
+
+    /// 8x8のBayer行列
+    Bayer8x8,
+}
+
+impl Dither {
+    /// `(x, y)`のピクセル位置のしきい値を 0 - 255 の範囲で返します。
+    ///
+    /// `Dither::None`の場合は常に 128 です。
+    pub(crate) const fn threshold(self, x: usize, y: usize) -> u8 {
+        // 行列の各要素を区間の中央に割り当てる
+        match self {
+            Dither::None => 128,
+            Dither::Bayer4x4 => BAYER_4X4[y % 4][x % 4] * 16 + 8,
+            Dither::Bayer8x8 => BAYER_8X8[y % 8][x % 8] * 4 + 2,
+        }
+    }
+}
+
+/// 4x4のBayer行列
+pub(crate) const BAYER_4X4: [[u8; 4]; 4] = [
+    [ 0,  8,  2, 10],
+    [12,  4, 14,  6],
+    [ 3, 11,  1,  9],
+    [15,  7, 13,  5],
+];
+
+/// 8x8のBayer行列
+pub(crate) const BAYER_8X8: [[u8; 8]; 8] = [
+    [ 0, 32,  8, 40,  2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44,  4, 36, 14, 46,  6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [ 3, 35, 11, 43,  1, 33,  9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47,  7, 39, 13, 45,  5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// エンコード時のオプション
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{Dither, EncodeOptions};
+///
+/// let options = EncodeOptions {
+///     dither: Dither::Bayer4x4,
+///     ..EncodeOptions::new()
+/// };
+///
+/// assert_eq!(options.alpha_threshold, EncodeOptions::DEFAULT_ALPHA_THRESHOLD);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// 透明色として扱うアルファ値の閾値
+    ///
+    /// `ColorType::Rgba8888`などのアルファ付きの色データからエンコードし、`ImageSpec::transparent_color`が指定されている場合、
+    /// アルファ値がこの値未満のピクセルは透明色になります。
+    ///
+    /// 0 の場合、透明色には置き換えられません。
+    pub alpha_threshold: u8,
+
+    /// 順序ディザリングの種類
+    ///
+    /// `ColorType::Rgb888`、`ColorType::Rgba8888`、`ColorType::Bgr888`、`ColorType::Bgra8888`および`ColorType::RgbF32`などのチャンネルあたり8ビットを超える色データタイプからのエンコードに適用されます。
+    /// しきい値の位置は画像の左上を原点とします。
+    pub dither: Dither,
+
+    /// 減色時の量子化方法
+    ///
+    /// `ColorType::Rgb888`などのチャンネルあたり8ビットの色データタイプからのエンコードに適用されます。
+    /// 順序ディザリングを適用する場合、しきい値が丸めを兼ねるため無視されます。
+    pub quantization: Quantization,
+
+    /// sRGBの伝達関数を適用するかどうか
+    ///
+    /// `ColorType::RgbF32`と`ColorType::RgbaF32`からのエンコードで、`true`の場合はRGBを線形値として扱ってsRGBに変換し、
+    /// `false`の場合は 0.0 - 1.0 をそのまま 0 - 255 に対応させます。
+    pub srgb_transfer: bool,
+}
+
+impl EncodeOptions {
+    /// `alpha_threshold`の既定値
+    pub const DEFAULT_ALPHA_THRESHOLD: u8 = 128;
+
+    /// 既定値の`EncodeOptions`を作成します。
+    ///
+    /// `alpha_threshold`は[`DEFAULT_ALPHA_THRESHOLD`](Self::DEFAULT_ALPHA_THRESHOLD)、`dither`は`Dither::None`、
+    /// `quantization`は`Quantization::Truncate`、`srgb_transfer`は`true`になります。
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            alpha_threshold: Self::DEFAULT_ALPHA_THRESHOLD,
+            dither: Dither::None,
+            quantization: Quantization::Truncate,
+            srgb_transfer: true,
+        }
+    }
+}
+
+impl Default for EncodeOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,105 @@
+use crate::common::color::ColorType;
+use crate::common::pixel::{rgb_to_pixel_with_quantization, PIXEL_B_MASK, PIXEL_G_MASK, PIXEL_R_MASK};
+use crate::common::spec::ImageSpec;
+use crate::encodes::options::EncodeOptions;
+use crate::error::{Error, Result};
+
+/// RGB565の全色数
+const NUM_COLORS: usize = 1 << 16;
+
+/// 使用済みの色を1色1ビットで記録する集合
+struct ColorSet([u32; NUM_COLORS / 32]);
+
+impl ColorSet {
+    #[inline(always)]
+    const fn new() -> Self {
+        Self([0; NUM_COLORS / 32])
+    }
+
+    #[inline(always)]
+    fn insert(&mut self, pixel: u16) {
+        self.0[(pixel >> 5) as usize] |= 1 << (pixel & 31);
+    }
+
+    #[inline(always)]
+    fn contains(&self, pixel: u16) -> bool {
+        self.0[(pixel >> 5) as usize] & (1 << (pixel & 31)) != 0
+    }
+
+    /// 未使用の色のうち最も小さい値を返します。
+    #[inline]
+    fn first_unused(&self) -> Option<u16> {
+        self.0.iter()
+            .position(|&bits| bits != u32::MAX)
+            .map(|index| ((index << 5) as u32 | self.0[index].trailing_ones()) as u16)
+    }
+}
+
+/// `data`のRGBA8888色データから不透明なピクセルで使用されていない色を探し、透明色に指定した`ImageSpec`を作成します。
+///
+/// アルファ値が`options.alpha_threshold`未満のピクセルは透明として扱われ、使用済みの色に含まれません。
+/// 使用済みの色は`options.quantization`で減色して判定します。
+///
+/// 透明色はマゼンタ（R = 255, B = 255）でGが小さいものから優先して選ばれます。
+/// それらがすべて使用されている場合は、未使用の色のうち最も小さい値が選ばれます。
+///
+/// `pixel_endian`は`PixelEndian::Little`になります。
+///
+/// # Errors
+///
+/// `width`か`height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+///
+/// `data`の長さが（RGBA8888の色バイト数 * 総ピクセル数）未満の場合、`Error::InputBufferTooSmall`を返します。
+///
+/// 不透明なピクセルですべての色が使用されている場合、`Error::NoAvailableTransparentColor`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{select_transparent_color, rgb_to_pixel, EncodeOptions};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = [
+///     255, 0, 255, 255,
+///     0, 0, 0, 0,
+/// ];
+///
+/// let spec = select_transparent_color(&data, 2, 1, &EncodeOptions::new())?;
+///
+/// // マゼンタは不透明なピクセルで使用されている
+/// assert_eq!(spec.transparent_color, Some(rgb_to_pixel([255, 4, 255])));
+/// # Ok(())
+/// # }
+/// ```
+pub fn select_transparent_color(data: &[u8], width: u16, height: u16, options: &EncodeOptions) -> Result<ImageSpec> {
+    const COLOR_TYPE: ColorType = ColorType::Rgba8888;
+
+    let num_pixels = width as usize * height as usize;
+
+    if num_pixels == 0 {
+        return Err(Error::ZeroImageDimensions);
+    }
+
+    if data.len() < COLOR_TYPE.bytes_per_pixel() * num_pixels {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    let mut used_colors = ColorSet::new();
+
+    for rgba in data.chunks_exact(COLOR_TYPE.bytes_per_pixel()).take(num_pixels) {
+        if rgba[3] >= options.alpha_threshold {
+            used_colors.insert(rgb_to_pixel_with_quantization([rgba[0], rgba[1], rgba[2]], options.quantization));
+        }
+    }
+
+    // マゼンタに近い色から優先して探す
+    let magenta = (0..=(PIXEL_G_MASK >> 5))
+        .map(|g| PIXEL_R_MASK | (g << 5) | PIXEL_B_MASK)
+        .find(|&pixel| !used_colors.contains(pixel));
+
+    let transparent_color = magenta
+        .or_else(|| used_colors.first_unused())
+        .ok_or(Error::NoAvailableTransparentColor)?;
+
+    Ok(ImageSpec::with_transparent_color(width, height, transparent_color))
+}
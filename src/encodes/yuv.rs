@@ -0,0 +1,321 @@
+use crate::common::header::HEADER_SIZE;
+use crate::common::pixel::{LumaCoefficients, Quantization, PIXEL_BYTES};
+use crate::common::spec::ImageSpec;
+use crate::encodes::{encode_header, logic};
+use crate::encodes::logic::YuvCoefficients;
+use crate::error::{Error, Result};
+
+/// YUVの値の範囲
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum YuvRange {
+    /// フルレンジ（Y, U, Vとも 0 - 255）
+    Full,
+
+    /// リミテッドレンジ（Yは 16 - 235、UとVは 16 - 240）
+    ///
+    /// 範囲外の値も変換されますが、RGBは 0 - 255 に丸められます。
+    #[default]
+    Limited,
+}
+
+/// YUVからRGB565への変換設定
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{LumaCoefficients, Quantization, YuvConversion, YuvRange};
+///
+/// let conversion = YuvConversion {
+///     quantization: Quantization::Nearest,
+///     ..YuvConversion::new(LumaCoefficients::Bt709, YuvRange::Full)
+/// };
+///
+/// assert_eq!(conversion.range, YuvRange::Full);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct YuvConversion {
+    /// 変換係数
+    ///
+    /// `LumaCoefficients::Bt601`はITU-R BT.601、`LumaCoefficients::Bt709`はITU-R BT.709の係数で変換します。
+    pub coefficients: LumaCoefficients,
+
+    /// 値の範囲
+    pub range: YuvRange,
+
+    /// 減色時の量子化方法
+    pub quantization: Quantization,
+}
+
+impl YuvConversion {
+    /// `coefficients`と`range`を指定して`YuvConversion`を作成します。
+    ///
+    /// `quantization`は`Quantization::Truncate`になります。
+    #[inline]
+    pub const fn new(coefficients: LumaCoefficients, range: YuvRange) -> Self {
+        Self { coefficients, range, quantization: Quantization::Truncate }
+    }
+}
+
+impl Default for YuvConversion {
+    /// BT.601のリミテッドレンジで変換する`YuvConversion`を作成します。
+    #[inline]
+    fn default() -> Self {
+        Self::new(LumaCoefficients::Bt601, YuvRange::Limited)
+    }
+}
+
+/// 1行あたり`stride`バイトの`rows`行分のデータに必要なバイト数を返します。
+///
+/// `stride`が1行分の`row_size`バイト未満の場合、`Error::InvalidStride`を返します。
+#[inline(always)]
+fn plane_size(stride: usize, row_size: usize, rows: usize) -> Result<usize> {
+    if stride < row_size {
+        return Err(Error::InvalidStride);
+    }
+
+    (rows - 1).checked_mul(stride)
+        .and_then(|offset| offset.checked_add(row_size))
+        .ok_or(Error::InputBufferTooSmall)
+}
+
+/// 1行あたり`stride`バイトのYUYV（YUV 4:2:2）形式の`data`と`spec`からLimg形式データをエンコードし、`buf`に書き込みます。
+///
+/// 各行は`[y0: u8, u: u8, y1: u8, v: u8]`の2ピクセル単位で並び、幅が奇数の場合も最後の組まで読み込まれます。
+/// UとVは同じ組の2ピクセルで共有されます。
+///
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+///
+/// `stride`が（4 * 幅の半分（切り上げ））未満の場合、`Error::InvalidStride`を返します。
+///
+/// `data`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{ImageSpec, YuvConversion, encode_yuyv, encoded_size};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 640x480のカメラフレーム
+/// let frame = std::fs::read("frame.yuyv")?;
+///
+/// let spec = ImageSpec::new(640, 480);
+/// let mut buf = vec![0u8; encoded_size(&spec)];
+///
+/// encode_yuyv(&frame, &mut buf, &spec, 640 * 2, &YuvConversion::default())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_yuyv(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, conversion: &YuvConversion) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if num_pixels == 0 {
+        return Err(Error::ZeroImageDimensions);
+    }
+    if buf.len() < HEADER_SIZE + PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    let (header_buf, data_buf) = buf.split_at_mut(HEADER_SIZE);
+
+    let written_size = encode_data_yuyv(data, data_buf, spec, stride, conversion)?;
+    let written_size = encode_header(header_buf, spec)? + written_size;
+
+    debug_assert_eq!(written_size, HEADER_SIZE + PIXEL_BYTES * num_pixels);
+
+    Ok(written_size)
+}
+
+/// 1行あたり`stride`バイトのYUYV（YUV 4:2:2）形式の`data`と`spec`から色データをエンコードし、`buf`に書き込みます。
+///
+/// 各行は`[y0: u8, u: u8, y1: u8, v: u8]`の2ピクセル単位で並び、幅が奇数の場合も最後の組まで読み込まれます。
+/// UとVは同じ組の2ピクセルで共有されます。
+///
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `stride`が（4 * 幅の半分（切り上げ））未満の場合、`Error::InvalidStride`を返します。
+///
+/// `data`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{ImageSpec, LumaCoefficients, YuvConversion, YuvRange, encode_data_yuyv, rgb_to_pixel, PIXEL_BYTES};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 白と黒の2ピクセル
+/// let data = [235, 128, 16, 128];
+///
+/// let spec = ImageSpec::new(2, 1);
+/// let mut buf = [0u8; PIXEL_BYTES * 2];
+///
+/// let conversion = YuvConversion::new(LumaCoefficients::Bt601, YuvRange::Limited);
+/// encode_data_yuyv(&data, &mut buf, &spec, data.len(), &conversion)?;
+///
+/// assert_eq!(buf[..2], rgb_to_pixel([255, 255, 255]).to_le_bytes());
+/// assert_eq!(buf[2..], rgb_to_pixel([0, 0, 0]).to_le_bytes());
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_data_yuyv(data: &[u8], buf: &mut [u8], spec: &ImageSpec, stride: usize, conversion: &YuvConversion) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if buf.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    if num_pixels == 0 {
+        return Ok(0);
+    }
+
+    let width = spec.width as usize;
+    let height = spec.height as usize;
+
+    if data.len() < plane_size(stride, 4 * width.div_ceil(2), height)? {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    let coefficients = YuvCoefficients::new(conversion.coefficients, conversion.range);
+
+    for row in 0..height {
+        unsafe {
+            let data = data.as_ptr().add(stride * row);
+            let buf = buf.as_mut_ptr().add(PIXEL_BYTES * width * row);
+            logic::encode_yuyv_logic(data, buf, spec, &coefficients, conversion.quantization, width);
+        }
+    }
+
+    Ok(PIXEL_BYTES * num_pixels)
+}
+
+/// 1行あたり`y_stride`バイトのY平面`y_plane`と、1行あたり`uv_stride`バイトのUV平面`uv_plane`からなるNV12（YUV 4:2:0）形式のデータと
+/// `spec`からLimg形式データをエンコードし、`buf`に書き込みます。
+///
+/// UV平面は幅と高さが半分（切り上げ）で、各要素は`[u: u8, v: u8]`です。UとVは縦横2x2のピクセルで共有されます。
+///
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `spec.width`か`spec.height`が 0 の場合、`Error::ZeroImageDimensions`を返します。
+///
+/// `y_stride`が幅未満か、`uv_stride`が（2 * 幅の半分（切り上げ））未満の場合、`Error::InvalidStride`を返します。
+///
+/// `y_plane`か`uv_plane`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（[`HEADER_SIZE`] + [`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use limg_core::{ImageSpec, LumaCoefficients, YuvConversion, YuvRange, encode_nv12, encoded_size};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 1280x720のカメラフレーム（Y平面の直後にUV平面）
+/// let frame = std::fs::read("frame.nv12")?;
+/// let (y_plane, uv_plane) = frame.split_at(1280 * 720);
+///
+/// let spec = ImageSpec::new(1280, 720);
+/// let mut buf = vec![0u8; encoded_size(&spec)];
+///
+/// let conversion = YuvConversion::new(LumaCoefficients::Bt709, YuvRange::Limited);
+/// encode_nv12(y_plane, uv_plane, &mut buf, &spec, 1280, 1280, &conversion)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_nv12(y_plane: &[u8], uv_plane: &[u8], buf: &mut [u8], spec: &ImageSpec, y_stride: usize, uv_stride: usize, conversion: &YuvConversion) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if num_pixels == 0 {
+        return Err(Error::ZeroImageDimensions);
+    }
+    if buf.len() < HEADER_SIZE + PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    let (header_buf, data_buf) = buf.split_at_mut(HEADER_SIZE);
+
+    let written_size = encode_data_nv12(y_plane, uv_plane, data_buf, spec, y_stride, uv_stride, conversion)?;
+    let written_size = encode_header(header_buf, spec)? + written_size;
+
+    debug_assert_eq!(written_size, HEADER_SIZE + PIXEL_BYTES * num_pixels);
+
+    Ok(written_size)
+}
+
+/// 1行あたり`y_stride`バイトのY平面`y_plane`と、1行あたり`uv_stride`バイトのUV平面`uv_plane`からなるNV12（YUV 4:2:0）形式のデータと
+/// `spec`から色データをエンコードし、`buf`に書き込みます。
+///
+/// UV平面は幅と高さが半分（切り上げ）で、各要素は`[u: u8, v: u8]`です。UとVは縦横2x2のピクセルで共有されます。
+///
+/// エラーではなかった場合、書き込まれたバイト数を返します。
+///
+/// # Errors
+///
+/// `y_stride`が幅未満か、`uv_stride`が（2 * 幅の半分（切り上げ））未満の場合、`Error::InvalidStride`を返します。
+///
+/// `y_plane`か`uv_plane`に最終行が収まらない場合、`Error::InputBufferTooSmall`を返します。
+///
+/// `buf`の長さが（[`PIXEL_BYTES`] * 総ピクセル数）未満の場合、`Error::OutputBufferTooSmall`を返します。
+///
+/// # Examples
+///
+/// ```
+/// use limg_core::{ImageSpec, YuvConversion, encode_data_nv12, PIXEL_BYTES};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // 2x2のグレー
+/// let y_plane = [126; 4];
+/// let uv_plane = [128, 128];
+///
+/// let spec = ImageSpec::new(2, 2);
+/// let mut buf = [0u8; PIXEL_BYTES * 4];
+///
+/// encode_data_nv12(&y_plane, &uv_plane, &mut buf, &spec, 2, 2, &YuvConversion::default())?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_data_nv12(y_plane: &[u8], uv_plane: &[u8], buf: &mut [u8], spec: &ImageSpec, y_stride: usize, uv_stride: usize, conversion: &YuvConversion) -> Result<usize> {
+    let num_pixels = spec.num_pixels();
+
+    if buf.len() < PIXEL_BYTES * num_pixels {
+        return Err(Error::OutputBufferTooSmall);
+    }
+
+    if num_pixels == 0 {
+        return Ok(0);
+    }
+
+    let width = spec.width as usize;
+    let height = spec.height as usize;
+
+    if y_plane.len() < plane_size(y_stride, width, height)? {
+        return Err(Error::InputBufferTooSmall);
+    }
+    if uv_plane.len() < plane_size(uv_stride, 2 * width.div_ceil(2), height.div_ceil(2))? {
+        return Err(Error::InputBufferTooSmall);
+    }
+
+    let coefficients = YuvCoefficients::new(conversion.coefficients, conversion.range);
+
+    for row in 0..height {
+        unsafe {
+            let y_data = y_plane.as_ptr().add(y_stride * row);
+            // UV平面の1行は2行分のピクセルで共有する
+            let uv_data = uv_plane.as_ptr().add(uv_stride * (row / 2));
+            let buf = buf.as_mut_ptr().add(PIXEL_BYTES * width * row);
+            logic::encode_nv12_logic(y_data, uv_data, buf, spec, &coefficients, conversion.quantization, width);
+        }
+    }
+
+    Ok(PIXEL_BYTES * num_pixels)
+}
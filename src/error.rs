@@ -20,6 +20,31 @@ pub enum Error {
     /// 
     /// デコード時に発生する可能性があります。
     UnsupportedFormat,
+
+    /// 指定された矩形が画像の範囲外です。
+    /// 
+    /// 部分デコード時に発生する可能性があります。
+    RectOutOfBounds,
+
+    /// ストライドが1行分のバイト数より小さいです。
+    /// 
+    /// ストライドを指定したエンコードおよびデコード時に発生する可能性があります。
+    InvalidStride,
+
+    /// 透明色に使用できる未使用の色がありません。
+    /// 
+    /// 透明色の自動選択時に発生する可能性があります。
+    NoAvailableTransparentColor,
+
+    /// 作業バッファの長さが足りません。
+    /// 
+    /// 誤差拡散でのエンコード時に発生する可能性があります。
+    ScratchBufferTooSmall,
+
+    /// 色データタイプがサポートされていません。
+    /// 
+    /// ピクセル位置を指定するエンコードおよびデコードで`ColorType::Mono1`を指定した場合に発生します。
+    UnsupportedColorType,
 }
 
 impl fmt::Display for Error {
@@ -29,6 +54,11 @@ impl fmt::Display for Error {
             Error::InputBufferTooSmall => write!(f, "Input buffer is too small"),
             Error::OutputBufferTooSmall => write!(f, "Output buffer is too small"),
             Error::UnsupportedFormat => write!(f, "Unsupported image format or header"),
+            Error::RectOutOfBounds => write!(f, "Rectangle is out of image bounds"),
+            Error::InvalidStride => write!(f, "Stride is smaller than row size"),
+            Error::NoAvailableTransparentColor => write!(f, "No unused color is available for the transparent color"),
+            Error::ScratchBufferTooSmall => write!(f, "Scratch buffer is too small"),
+            Error::UnsupportedColorType => write!(f, "Color type is not supported by this function"),
         }
     }
 }
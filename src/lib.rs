@@ -9,9 +9,21 @@ mod error;
 pub use common::color::ColorType;
 pub use common::header::{HEADER_SIZE, CURRENT_VARSION};
 pub use common::spec::{ImageSpec, PixelEndian};
-pub use common::pixel::{pixel_to_rgb, rgb_to_pixel, PIXEL_BYTES};
+pub use common::pixel::{pixel_to_rgb, rgb_to_pixel, rgb_to_pixel_with_quantization, LumaCoefficients, Quantization, PIXEL_BYTES};
 
-pub use encodes::{encode, encode_header, encode_data, encoded_size};
-pub use decodes::{decode, decode_header, decode_data, decoded_size};
+pub use encodes::{encode, encode_with_options, encode_collision_free, encode_header, encode_data, encode_data_with_options, encode_data_collision_free, encode_strided, encode_strided_with_options, encode_data_strided, encode_data_strided_with_options, encoded_size};
+pub use encodes::options::{Dither, EncodeOptions};
+pub use encodes::transparent::select_transparent_color;
+pub use encodes::diffusion::{DiffusionKernel, ErrorDiffusion, diffusion_scratch_len, encode_error_diffused, encode_data_error_diffused};
+pub use encodes::yuv::{YuvConversion, YuvRange, encode_yuyv, encode_data_yuyv, encode_nv12, encode_data_nv12};
+pub use decodes::{decode, decode_with_options, decode_header, decode_data, decode_data_with_options, decode_rect, decode_rect_with_options, decode_strided, decode_strided_with_options, decode_data_strided, decode_data_strided_with_options, decoded_size};
+pub use decodes::options::DecodeOptions;
+pub use decodes::view::{LimgView, Rows};
+pub use decodes::incremental::IncrementalDecoder;
+pub use decodes::chunked::ChunkedDecoder;
+pub use decodes::orientation::{Orientation, decode_oriented, decode_oriented_with_options, decode_data_oriented, decode_data_oriented_with_options};
+pub use decodes::blit::{blit, blit_with_options};
+pub use decodes::mono::{decode_mono_error_diffused, decode_data_mono_error_diffused};
+pub use decodes::tensor::{TensorLayout, TensorNormalization, tensor_len, decode_tensor, decode_data_tensor, decode_tensor_f32, decode_data_tensor_f32};
 
 pub use error::{Result, Error};
\ No newline at end of file
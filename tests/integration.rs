@@ -1,10 +1,16 @@
-use limg_core::{ColorType, HEADER_SIZE};
-use limg_core::{decode, decode_data, decode_header, decoded_size};
-use limg_core::{encode, encode_data, encode_header, encoded_size};
+use limg_core::{ChunkedDecoder, ColorType, Error, ImageSpec, IncrementalDecoder, LimgView, Orientation, PixelEndian, HEADER_SIZE};
+use limg_core::{blit, blit_with_options, pixel_to_rgb, rgb_to_pixel, rgb_to_pixel_with_quantization, select_transparent_color, Dither, EncodeOptions, Quantization, PIXEL_BYTES};
+use limg_core::{diffusion_scratch_len, encode_data_error_diffused, encode_error_diffused, DiffusionKernel, ErrorDiffusion};
+use limg_core::{decode_data_mono_error_diffused, decode_mono_error_diffused};
+use limg_core::{encode_data_nv12, encode_data_yuyv, encode_nv12, encode_yuyv, YuvConversion, YuvRange};
+use limg_core::{DecodeOptions, LumaCoefficients};
+use limg_core::{decode_data_tensor, decode_data_tensor_f32, decode_tensor, decode_tensor_f32, tensor_len, TensorLayout, TensorNormalization};
+use limg_core::{decode, decode_with_options, decode_data, decode_data_with_options, decode_data_strided, decode_header, decode_oriented, decode_oriented_with_options, decode_rect, decode_rect_with_options, decode_strided, decode_strided_with_options, decoded_size};
+use limg_core::{encode, encode_collision_free, encode_data, encode_data_collision_free, encode_data_strided, encode_data_strided_with_options, encode_data_with_options, encode_header, encode_strided, encode_with_options, encoded_size};
 
 fn encode_decode_test(color_type: ColorType) {
     let mut encode_buf = vec![0u8; 512 * 512 * 4];
-    let mut decode_buf = vec![0u8; 512 * 512 * 4];
+    let mut decode_buf = vec![0u8; 512 * 512 * color_type.bytes_per_pixel().max(4)];
 
     let dir = std::fs::read_dir("tests/limg").unwrap();
 
@@ -63,6 +69,51 @@ fn limg_rgba8888_test() {
     encode_decode_test(ColorType::Rgba8888);
 }
 
+#[test]
+fn limg_bgr888_test() {
+    encode_decode_test(ColorType::Bgr888);
+}
+
+#[test]
+fn limg_bgra8888_test() {
+    encode_decode_test(ColorType::Bgra8888);
+}
+
+#[test]
+fn limg_bgr565_test() {
+    encode_decode_test(ColorType::Bgr565);
+}
+
+#[test]
+fn limg_rgb565be_test() {
+    encode_decode_test(ColorType::Rgb565Be);
+}
+
+#[test]
+fn limg_bgr565be_test() {
+    encode_decode_test(ColorType::Bgr565Be);
+}
+
+#[test]
+fn limg_rgb16_test() {
+    encode_decode_test(ColorType::Rgb16);
+}
+
+#[test]
+fn limg_rgba16_test() {
+    encode_decode_test(ColorType::Rgba16);
+}
+
+#[test]
+fn limg_rgbf32_test() {
+    encode_decode_test(ColorType::RgbF32);
+}
+
+#[test]
+fn limg_rgbaf32_test() {
+    encode_decode_test(ColorType::RgbaF32);
+}
+
 #[test]
 fn limg_rgb888_header_data_test() {
     encode_decode_header_data_test(ColorType::Rgb888);
@@ -76,4 +127,2148 @@ fn limg_rgb565_header_data_test() {
 #[test]
 fn limg_rgba8888_header_data_test() {
     encode_decode_header_data_test(ColorType::Rgba8888);
-}
\ No newline at end of file
+}
+
+#[test]
+fn limg_view_test() {
+    let dir = std::fs::read_dir("tests/limg").unwrap();
+
+    for item in dir.into_iter() {
+        let path = item.unwrap().path();
+
+        let data = std::fs::read(path).unwrap();
+
+        let view = LimgView::new(&data).unwrap();
+        let spec = *view.spec();
+
+        let mut rgb565_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb565)];
+        let mut rgba8888_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        decode(&data, &mut rgb565_buf, ColorType::Rgb565).unwrap();
+        decode(&data, &mut rgba8888_buf, ColorType::Rgba8888).unwrap();
+
+        for y in 0..spec.height {
+            for x in 0..spec.width {
+                let i = y as usize * spec.width as usize + x as usize;
+                let pixel = u16::from_ne_bytes([rgb565_buf[i * 2], rgb565_buf[i * 2 + 1]]);
+                let rgba = &rgba8888_buf[i * 4..i * 4 + 4];
+
+                assert_eq!(view.pixel(x, y), pixel);
+                assert_eq!(view.rgb(x, y), rgba[..3]);
+                assert_eq!(view.is_transparent(x, y), rgba[3] == 0);
+            }
+        }
+
+        assert_eq!(view.get_pixel(spec.width, 0), None);
+        assert_eq!(view.get_pixel(0, spec.height), None);
+
+        let rows: Vec<&[u8]> = view.rows().collect();
+        assert_eq!(rows.len(), spec.height as usize);
+        assert_eq!(rows.concat(), data[HEADER_SIZE..]);
+        assert_eq!(rows[rows.len() - 1], view.row(spec.height - 1));
+    }
+}
+
+#[test]
+fn limg_view_too_small_test() {
+    let data = std::fs::read("tests/limg/red_le.limg").unwrap();
+
+    assert!(matches!(LimgView::new(&data[..data.len() - 1]), Err(Error::InputBufferTooSmall)));
+    assert!(matches!(LimgView::new(&data[..HEADER_SIZE - 1]), Err(Error::InputBufferTooSmall)));
+}
+
+fn incremental_decode_test(color_type: ColorType) {
+    let dir = std::fs::read_dir("tests/limg").unwrap();
+
+    for item in dir.into_iter() {
+        let path = item.unwrap().path();
+
+        let data = std::fs::read(path).unwrap();
+
+        let spec = decode_header(&data).unwrap();
+        let mut decode_buf = vec![0u8; decoded_size(&spec, color_type)];
+        decode(&data, &mut decode_buf, color_type).unwrap();
+
+        let row_size = color_type.bytes_per_pixel() * spec.width as usize;
+
+        for chunk_size in [1, 3, 7, 64, data.len()] {
+            let mut row_buf = vec![0u8; row_size];
+            let mut rows_buf = vec![0u8; decode_buf.len()];
+            let mut next_y = 0;
+
+            let mut decoder = IncrementalDecoder::new(&mut row_buf, color_type);
+
+            for chunk in data.chunks(chunk_size) {
+                let consumed = decoder.feed(chunk, |y, row| {
+                    assert_eq!(y, next_y);
+                    next_y += 1;
+                    rows_buf[y as usize * row_size..(y as usize + 1) * row_size].copy_from_slice(row);
+                }).unwrap();
+                assert_eq!(consumed, chunk.len());
+            }
+
+            assert!(decoder.is_finished());
+            assert_eq!(decoder.spec(), Some(&spec));
+            assert_eq!(decoder.feed(&[0; 4], |_, _| unreachable!()).unwrap(), 0);
+            assert_eq!(rows_buf, decode_buf);
+        }
+    }
+}
+
+#[test]
+fn limg_rgb888_incremental_test() {
+    incremental_decode_test(ColorType::Rgb888);
+}
+
+#[test]
+fn limg_rgb565_incremental_test() {
+    incremental_decode_test(ColorType::Rgb565);
+}
+
+#[test]
+fn limg_rgba8888_incremental_test() {
+    incremental_decode_test(ColorType::Rgba8888);
+}
+
+#[test]
+fn incremental_dither_test() {
+    let spec = ImageSpec::new(37, 23);
+    let rgb: Vec<u8> = (0..3 * spec.num_pixels()).map(|i| (i * 53 + i / 7) as u8).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+
+    let options = [
+        DecodeOptions { dither: Dither::Bayer4x4, ..DecodeOptions::new() },
+        DecodeOptions { dither: Dither::Bayer8x8, ..DecodeOptions::new() },
+        DecodeOptions { luma: LumaCoefficients::Bt709, ..DecodeOptions::new() },
+    ];
+
+    for color_type in [ColorType::Rgb332, ColorType::L8, ColorType::La88] {
+        for options in &options {
+            let mut expected = vec![0u8; decoded_size(&spec, color_type)];
+            decode_with_options(&data, &mut expected, color_type, options).unwrap();
+
+            let row_size = color_type.bytes_per_pixel() * spec.width as usize;
+
+            // ピクセルや行の途中で分割されても、しきい値は画像上の位置で決まる
+            for chunk_size in [1, 3, 7, 64, data.len()] {
+                let mut row_buf = vec![0u8; row_size];
+                let mut rows_buf = Vec::with_capacity(expected.len());
+
+                let mut decoder = IncrementalDecoder::new_with_options(&mut row_buf, color_type, options);
+
+                for chunk in data.chunks(chunk_size) {
+                    decoder.feed(chunk, |_, row| rows_buf.extend_from_slice(row)).unwrap();
+                }
+
+                assert_eq!(rows_buf, expected, "{color_type:?} {options:?} {chunk_size}");
+            }
+        }
+    }
+}
+
+#[test]
+fn limg_incremental_error_test() {
+    let data = std::fs::read("tests/limg/red_le.limg").unwrap();
+
+    let mut row_buf = [0u8; 1];
+    let mut decoder = IncrementalDecoder::new(&mut row_buf, ColorType::Rgb888);
+    assert!(matches!(decoder.feed(&data, |_, _| ()), Err(Error::OutputBufferTooSmall)));
+
+    let mut row_buf = [0u8; 0];
+    let mut decoder = IncrementalDecoder::new(&mut row_buf, ColorType::Rgb888);
+    assert!(matches!(decoder.feed(&[0; HEADER_SIZE], |_, _| ()), Err(Error::UnsupportedFormat)));
+}
+
+fn chunked_decode_test(color_type: ColorType) {
+    let dir = std::fs::read_dir("tests/limg").unwrap();
+
+    for item in dir.into_iter() {
+        let path = item.unwrap().path();
+
+        let data = std::fs::read(path).unwrap();
+
+        let spec = decode_header(&data).unwrap();
+        let mut decode_buf = vec![0u8; decoded_size(&spec, color_type)];
+        decode(&data, &mut decode_buf, color_type).unwrap();
+
+        for chunk_size in [1, 5, 100, 4096] {
+            let mut chunk_buf = vec![0u8; chunk_size];
+            let mut chunks_buf = Vec::with_capacity(decode_buf.len());
+
+            let mut decoder = ChunkedDecoder::new(&data, color_type).unwrap();
+
+            if chunk_size < color_type.bytes_per_pixel() {
+                assert!(matches!(decoder.decode_next(&mut chunk_buf), Err(Error::OutputBufferTooSmall)));
+                continue;
+            }
+
+            while !decoder.is_finished() {
+                let written_size = decoder.decode_next(&mut chunk_buf).unwrap();
+                chunks_buf.extend_from_slice(&chunk_buf[..written_size]);
+                assert_eq!(decoder.decoded_pixels() * color_type.bytes_per_pixel(), chunks_buf.len());
+            }
+
+            assert_eq!(decoder.remaining_pixels(), 0);
+            assert_eq!(decoder.decode_next(&mut chunk_buf).unwrap(), 0);
+            assert_eq!(chunks_buf, decode_buf);
+        }
+    }
+}
+
+#[test]
+fn limg_rgb888_chunked_test() {
+    chunked_decode_test(ColorType::Rgb888);
+}
+
+#[test]
+fn limg_rgb565_chunked_test() {
+    chunked_decode_test(ColorType::Rgb565);
+}
+
+#[test]
+fn limg_rgba8888_chunked_test() {
+    chunked_decode_test(ColorType::Rgba8888);
+}
+
+#[test]
+fn chunked_dither_test() {
+    let spec = ImageSpec::new(37, 23);
+    let rgb: Vec<u8> = (0..3 * spec.num_pixels()).map(|i| (i * 53 + i / 7) as u8).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+
+    for color_type in [ColorType::Rgb332, ColorType::Rgb565] {
+        for dither in [Dither::Bayer4x4, Dither::Bayer8x8] {
+            let options = DecodeOptions { dither, ..DecodeOptions::new() };
+
+            let mut expected = vec![0u8; decoded_size(&spec, color_type)];
+            decode_with_options(&data, &mut expected, color_type, &options).unwrap();
+
+            // 行の途中で分割されても、しきい値は画像上の位置で決まる
+            for chunk_size in [2, 10, 100, 4096] {
+                let mut chunk_buf = vec![0u8; chunk_size];
+                let mut chunks_buf = Vec::with_capacity(expected.len());
+
+                let mut decoder = ChunkedDecoder::new_with_options(&data, color_type, &options).unwrap();
+
+                while !decoder.is_finished() {
+                    let written_size = decoder.decode_next(&mut chunk_buf).unwrap();
+                    chunks_buf.extend_from_slice(&chunk_buf[..written_size]);
+                }
+
+                assert_eq!(chunks_buf, expected, "{color_type:?} {dither:?} {chunk_size}");
+            }
+        }
+    }
+}
+
+fn decode_rect_test(color_type: ColorType) {
+    let dir = std::fs::read_dir("tests/limg").unwrap();
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    for item in dir.into_iter() {
+        let path = item.unwrap().path();
+
+        let data = std::fs::read(path).unwrap();
+
+        let spec = decode_header(&data).unwrap();
+        let mut decode_buf = vec![0u8; decoded_size(&spec, color_type)];
+        decode(&data, &mut decode_buf, color_type).unwrap();
+
+        let rects = [
+            (0, 0, spec.width, spec.height),
+            (1, 2, spec.width - 1, spec.height - 2),
+            (spec.width / 3, spec.height / 4, spec.width / 2, spec.height / 2),
+            (spec.width - 1, spec.height - 1, 1, 1),
+        ];
+
+        for (x, y, width, height) in rects {
+            let mut rect_buf = vec![0u8; bytes_per_pixel * width as usize * height as usize];
+
+            let (rect_spec, written_size) = decode_rect(&data, &mut rect_buf, x, y, width, height, color_type).unwrap();
+            assert_eq!(rect_spec, spec);
+            assert_eq!(written_size, rect_buf.len());
+
+            let row_size = bytes_per_pixel * width as usize;
+            for (row, rect_row) in rect_buf.chunks_exact(row_size).enumerate() {
+                let start = bytes_per_pixel * ((y as usize + row) * spec.width as usize + x as usize);
+                assert_eq!(rect_row, &decode_buf[start..start + row_size]);
+            }
+        }
+
+        let mut rect_buf = vec![0u8; decode_buf.len()];
+        assert!(matches!(decode_rect(&data, &mut rect_buf, 1, 0, spec.width, 1, color_type), Err(Error::RectOutOfBounds)));
+        assert!(matches!(decode_rect(&data, &mut rect_buf, 0, spec.height, 1, 1, color_type), Err(Error::RectOutOfBounds)));
+        assert!(matches!(decode_rect(&data, &mut rect_buf, u16::MAX, 0, 1, 1, color_type), Err(Error::RectOutOfBounds)));
+        assert!(matches!(decode_rect(&data, &mut rect_buf, 0, 0, 0, 1, color_type), Err(Error::ZeroImageDimensions)));
+        assert!(matches!(decode_rect(&data, &mut rect_buf[..1], 0, 0, 1, 1, color_type), Err(Error::OutputBufferTooSmall)));
+    }
+}
+
+#[test]
+fn limg_rgb888_rect_test() {
+    decode_rect_test(ColorType::Rgb888);
+}
+
+#[test]
+fn limg_rgb565_rect_test() {
+    decode_rect_test(ColorType::Rgb565);
+}
+
+#[test]
+fn limg_rgba8888_rect_test() {
+    decode_rect_test(ColorType::Rgba8888);
+}
+
+fn decode_strided_test(color_type: ColorType) {
+    let dir = std::fs::read_dir("tests/limg").unwrap();
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    for item in dir.into_iter() {
+        let path = item.unwrap().path();
+
+        let data = std::fs::read(path).unwrap();
+
+        let spec = decode_header(&data).unwrap();
+        let mut decode_buf = vec![0u8; decoded_size(&spec, color_type)];
+        decode(&data, &mut decode_buf, color_type).unwrap();
+
+        let row_size = bytes_per_pixel * spec.width as usize;
+
+        for (x, y, padding) in [(0, 0, 0), (3, 2, 5), (0, 7, 1)] {
+            let stride = bytes_per_pixel * x + row_size + padding;
+            let required_size = stride * (y + spec.height as usize - 1) + bytes_per_pixel * x + row_size;
+            let mut strided_buf = vec![0xAAu8; required_size];
+
+            let (_, written_size) = decode_strided(&data, &mut strided_buf, stride, x, y, color_type).unwrap();
+            assert_eq!(written_size, decode_buf.len());
+
+            for (i, &b) in strided_buf.iter().enumerate() {
+                let (row, column) = (i / stride, i % stride);
+                let inside = row >= y && column >= bytes_per_pixel * x && column < bytes_per_pixel * x + row_size;
+
+                if inside {
+                    assert_eq!(b, decode_buf[(row - y) * row_size + column - bytes_per_pixel * x]);
+                } else {
+                    assert_eq!(b, 0xAA);
+                }
+            }
+
+            let mut data_strided_buf = vec![0xAAu8; required_size];
+            decode_data_strided(&data[HEADER_SIZE..], &mut data_strided_buf, &spec, stride, x, y, color_type).unwrap();
+            assert_eq!(data_strided_buf, strided_buf);
+
+            assert!(matches!(decode_strided(&data, &mut strided_buf[..required_size - 1], stride, x, y, color_type), Err(Error::OutputBufferTooSmall)));
+            assert!(matches!(decode_strided(&data, &mut strided_buf, bytes_per_pixel * x + row_size - 1, x, y, color_type), Err(Error::InvalidStride)));
+        }
+    }
+}
+
+#[test]
+fn limg_rgb888_strided_test() {
+    decode_strided_test(ColorType::Rgb888);
+}
+
+#[test]
+fn limg_rgb565_strided_test() {
+    decode_strided_test(ColorType::Rgb565);
+}
+
+#[test]
+fn limg_rgba8888_strided_test() {
+    decode_strided_test(ColorType::Rgba8888);
+}
+
+#[test]
+fn decode_rect_dither_test() {
+    let spec = ImageSpec::new(37, 23);
+    let rgb: Vec<u8> = (0..3 * spec.num_pixels()).map(|i| (i * 53 + i / 7) as u8).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+
+    let width = spec.width as usize;
+
+    for dither in [Dither::Bayer4x4, Dither::Bayer8x8] {
+        let options = DecodeOptions { dither, ..DecodeOptions::new() };
+
+        let mut expected = vec![0u8; decoded_size(&spec, ColorType::Rgb332)];
+        decode_with_options(&data, &mut expected, ColorType::Rgb332, &options).unwrap();
+
+        // しきい値は画像上の位置で決まるため、全体をデコードした結果の同じ領域と一致する
+        for (x, y, rect_width, rect_height) in [(0, 0, 37, 23), (5, 3, 20, 9), (13, 7, 8, 1)] {
+            let mut buf = vec![0u8; rect_width * rect_height];
+            decode_rect_with_options(&data, &mut buf, x as u16, y as u16, rect_width as u16, rect_height as u16, ColorType::Rgb332, &options).unwrap();
+
+            for (row, rect_row) in buf.chunks_exact(rect_width).enumerate() {
+                let start = width * (y + row) + x;
+                assert_eq!(rect_row, &expected[start..start + rect_width], "{dither:?} {x} {y} {row}");
+            }
+        }
+
+        // しきい値は出力先の位置で決まるため、周期の倍数だけずらした位置では全体をデコードした結果と一致する
+        let (x, y, stride) = (8, 16, width + 11);
+        let mut buf = vec![0u8; stride * (y + spec.height as usize)];
+        decode_strided_with_options(&data, &mut buf, stride, x, y, ColorType::Rgb332, &options).unwrap();
+
+        for (row, expected_row) in expected.chunks_exact(width).enumerate() {
+            let start = stride * (y + row) + x;
+            assert_eq!(&buf[start..start + width], expected_row, "{dither:?} {row}");
+        }
+    }
+}
+
+fn encode_strided_test(color_type: ColorType) {
+    let dir = std::fs::read_dir("tests/limg").unwrap();
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    for item in dir.into_iter() {
+        let path = item.unwrap().path();
+
+        let data = std::fs::read(path).unwrap();
+
+        let spec = decode_header(&data).unwrap();
+        let mut decode_buf = vec![0u8; decoded_size(&spec, color_type)];
+        decode(&data, &mut decode_buf, color_type).unwrap();
+
+        let row_size = bytes_per_pixel * spec.width as usize;
+
+        for (x, y, padding) in [(0, 0, 0), (3, 2, 5), (0, 7, 1)] {
+            let stride = bytes_per_pixel * x + row_size + padding;
+            let required_size = stride * (y + spec.height as usize - 1) + bytes_per_pixel * x + row_size;
+
+            let mut canvas = vec![0x55u8; required_size];
+            for (row, decoded_row) in decode_buf.chunks_exact(row_size).enumerate() {
+                let start = stride * (y + row) + bytes_per_pixel * x;
+                canvas[start..start + row_size].copy_from_slice(decoded_row);
+            }
+
+            let mut encode_buf = vec![0u8; encoded_size(&spec)];
+            let encoded_size = encode_strided(&canvas, &mut encode_buf, &spec, stride, x, y, color_type).unwrap();
+            assert_eq!(encode_buf[..encoded_size], data);
+
+            let mut data_buf = vec![0u8; encoded_size - HEADER_SIZE];
+            encode_data_strided(&canvas, &mut data_buf, &spec, stride, x, y, color_type).unwrap();
+            assert_eq!(data_buf, data[HEADER_SIZE..]);
+
+            assert!(matches!(encode_strided(&canvas[..required_size - 1], &mut encode_buf, &spec, stride, x, y, color_type), Err(Error::InputBufferTooSmall)));
+            assert!(matches!(encode_strided(&canvas, &mut encode_buf, &spec, bytes_per_pixel * x + row_size - 1, x, y, color_type), Err(Error::InvalidStride)));
+        }
+    }
+}
+
+#[test]
+fn limg_rgb888_encode_strided_test() {
+    encode_strided_test(ColorType::Rgb888);
+}
+
+#[test]
+fn limg_rgb565_encode_strided_test() {
+    encode_strided_test(ColorType::Rgb565);
+}
+
+#[test]
+fn limg_rgba8888_encode_strided_test() {
+    encode_strided_test(ColorType::Rgba8888);
+}
+
+#[test]
+fn encode_strided_dither_test() {
+    // 全体をエンコードした結果の一部と、領域ごとに分けてエンコードした結果が一致する
+    let canvas_spec = ImageSpec::new(19, 13);
+    let canvas_width = canvas_spec.width as usize;
+
+    for color_type in [ColorType::Rgb888, ColorType::Rgba8888, ColorType::Rgba16] {
+        let bytes_per_pixel = color_type.bytes_per_pixel();
+        let canvas: Vec<u8> = (0..bytes_per_pixel * canvas_spec.num_pixels()).map(|i| (i * 37 + i / 5) as u8).collect();
+        let stride = bytes_per_pixel * canvas_width;
+
+        for dither in [Dither::Bayer4x4, Dither::Bayer8x8] {
+            let options = EncodeOptions { dither, ..EncodeOptions::new() };
+
+            let mut expected = vec![0u8; PIXEL_BYTES * canvas_spec.num_pixels()];
+            encode_data_with_options(&canvas, &mut expected, &canvas_spec, color_type, &options).unwrap();
+
+            for (x, y, width, height) in [(0, 0, 19, 5), (0, 5, 19, 3), (3, 8, 16, 5), (5, 1, 8, 4), (4, 2, 3, 11), (1, 0, 18, 13)] {
+                let spec = ImageSpec::new(width, height);
+                let mut buf = vec![0u8; PIXEL_BYTES * spec.num_pixels()];
+                encode_data_strided_with_options(&canvas, &mut buf, &spec, stride, x, y, color_type, &options).unwrap();
+
+                let row_size = PIXEL_BYTES * width as usize;
+
+                for (row, buf_row) in buf.chunks_exact(row_size).enumerate() {
+                    let start = PIXEL_BYTES * (canvas_width * (y + row) + x);
+                    assert_eq!(buf_row, &expected[start..start + row_size], "{color_type:?} {dither:?} ({x}, {y}) {row}");
+                }
+            }
+        }
+    }
+}
+
+/// `tests/limg`の画像に加えて、幅と高さが異なる画像を返す
+fn test_images() -> Vec<Vec<u8>> {
+    let mut images: Vec<Vec<u8>> = std::fs::read_dir("tests/limg").unwrap()
+        .map(|item| std::fs::read(item.unwrap().path()).unwrap())
+        .collect();
+
+    for (width, height) in [(37, 5), (5, 37)] {
+        let spec = ImageSpec::with_pixel_endian(width, height, PixelEndian::Big);
+        let rgb: Vec<u8> = (0..spec.num_pixels() * 3).map(|i| (i * 7) as u8).collect();
+
+        let mut data = vec![0u8; encoded_size(&spec)];
+        encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+        images.push(data);
+    }
+
+    images
+}
+
+fn decode_oriented_test(color_type: ColorType) {
+
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    let orientations = [
+        Orientation::Normal,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::FlipHorizontal,
+        Orientation::FlipVertical,
+    ];
+
+    for data in test_images() {
+        let spec = decode_header(&data).unwrap();
+        let mut decode_buf = vec![0u8; decoded_size(&spec, color_type)];
+        decode(&data, &mut decode_buf, color_type).unwrap();
+
+        let (width, height) = (spec.width as usize, spec.height as usize);
+
+        for orientation in orientations {
+            let mut oriented_buf = vec![0u8; decode_buf.len()];
+            let (oriented_spec, written_size) = decode_oriented(&data, &mut oriented_buf, color_type, orientation).unwrap();
+
+            assert_eq!(written_size, decode_buf.len());
+            assert_eq!(oriented_spec, orientation.oriented_spec(&spec));
+
+            let out_width = oriented_spec.width as usize;
+
+            for y in 0..height {
+                for x in 0..width {
+                    let (out_x, out_y) = match orientation {
+                        Orientation::Normal => (x, y),
+                        Orientation::Rotate90 => (height - 1 - y, x),
+                        Orientation::Rotate180 => (width - 1 - x, height - 1 - y),
+                        Orientation::Rotate270 => (y, width - 1 - x),
+                        Orientation::FlipHorizontal => (width - 1 - x, y),
+                        Orientation::FlipVertical => (x, height - 1 - y),
+                    };
+
+                    let src = bytes_per_pixel * (y * width + x);
+                    let dst = bytes_per_pixel * (out_y * out_width + out_x);
+                    assert_eq!(oriented_buf[dst..dst + bytes_per_pixel], decode_buf[src..src + bytes_per_pixel]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn limg_rgb888_oriented_test() {
+    decode_oriented_test(ColorType::Rgb888);
+}
+
+#[test]
+fn limg_rgb565_oriented_test() {
+    decode_oriented_test(ColorType::Rgb565);
+}
+
+#[test]
+fn limg_rgba8888_oriented_test() {
+    decode_oriented_test(ColorType::Rgba8888);
+}
+
+#[test]
+fn oriented_dither_test() {
+    let spec = ImageSpec::new(37, 23);
+    let rgb: Vec<u8> = (0..3 * spec.num_pixels()).map(|i| (i * 53 + i / 7) as u8).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+
+    let orientations = [
+        Orientation::Normal,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::FlipHorizontal,
+        Orientation::FlipVertical,
+    ];
+
+    for orientation in orientations {
+        // 変換後の画像をRGB565で可逆に作り直す
+        let mut rgb565_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb565)];
+        let (oriented_spec, _) = decode_oriented(&data, &mut rgb565_buf, ColorType::Rgb565, orientation).unwrap();
+        let mut oriented_data = vec![0u8; encoded_size(&oriented_spec)];
+        encode(&rgb565_buf, &mut oriented_data, &oriented_spec, ColorType::Rgb565).unwrap();
+
+        for dither in [Dither::Bayer4x4, Dither::Bayer8x8] {
+            let options = DecodeOptions { dither, ..DecodeOptions::new() };
+
+            // しきい値は変換後の位置で決まるため、変換後の画像をデコードした結果と一致する
+            let mut expected = vec![0u8; decoded_size(&oriented_spec, ColorType::Rgb332)];
+            decode_with_options(&oriented_data, &mut expected, ColorType::Rgb332, &options).unwrap();
+
+            let mut buf = vec![0u8; expected.len()];
+            decode_oriented_with_options(&data, &mut buf, ColorType::Rgb332, orientation, &options).unwrap();
+            assert_eq!(buf, expected, "{orientation:?} {dither:?}");
+        }
+    }
+}
+
+fn blit_test(color_type: ColorType) {
+    let bytes_per_pixel = color_type.bytes_per_pixel();
+
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(40, 9, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 3 == 0 { [255, 0, 255] } else { [i as u8, 0, 0] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+        let mut decode_buf = vec![0u8; decoded_size(&spec, color_type)];
+        decode(&data, &mut decode_buf, color_type).unwrap();
+
+        let (width, height) = (spec.width as i32, spec.height as i32);
+        let (dst_width, dst_height) = (50, 45);
+        let padding_size = bytes_per_pixel - 1;
+        let dst_stride = bytes_per_pixel * dst_width as usize + padding_size;
+
+        for (x, y) in [(0, 0), (-7, -3), (20, 30), (-1000, 0), (dst_width - 1, dst_height - 1)] {
+            let mut dst = vec![0xAAu8; dst_stride * dst_height as usize];
+
+            blit(&data, &mut dst, dst_stride, x, y, color_type).unwrap();
+
+            for dy in 0..dst_height {
+                for dx in 0..dst_width {
+                    let (sx, sy) = (dx - x, dy - y);
+                    let d = dst_stride * dy as usize + bytes_per_pixel * dx as usize;
+                    let pixel = &dst[d..d + bytes_per_pixel];
+
+                    if sx < 0 || sy < 0 || sx >= width || sy >= height {
+                        assert!(pixel.iter().all(|&b| b == 0xAA));
+                        continue;
+                    }
+
+                    let s = (sy * width + sx) as usize;
+                    let raw = [data[HEADER_SIZE + s * 2], data[HEADER_SIZE + s * 2 + 1]];
+                    let value = match spec.pixel_endian {
+                        PixelEndian::Big => u16::from_be_bytes(raw),
+                        PixelEndian::Little => u16::from_le_bytes(raw),
+                    };
+
+                    if spec.transparent_color == Some(value) {
+                        assert!(pixel.iter().all(|&b| b == 0xAA));
+                    } else {
+                        assert_eq!(pixel, &decode_buf[s * bytes_per_pixel..(s + 1) * bytes_per_pixel]);
+                    }
+                }
+            }
+
+            for row in 0..dst_height as usize {
+                let padding = dst_stride * row + bytes_per_pixel * dst_width as usize;
+                assert!(dst[padding..padding + padding_size].iter().all(|&b| b == 0xAA));
+            }
+        }
+
+        let mut dst = vec![0u8; 16];
+        assert!(matches!(blit(&data, &mut dst, bytes_per_pixel - 1, 0, 0, color_type), Err(Error::InvalidStride)));
+    }
+}
+
+#[test]
+fn limg_rgb888_blit_test() {
+    blit_test(ColorType::Rgb888);
+}
+
+#[test]
+fn limg_rgb565_blit_test() {
+    blit_test(ColorType::Rgb565);
+}
+
+#[test]
+fn limg_rgba8888_blit_test() {
+    blit_test(ColorType::Rgba8888);
+}
+
+#[test]
+fn limg_bgra8888_blit_test() {
+    blit_test(ColorType::Bgra8888);
+}
+
+#[test]
+fn limg_argb8888_blit_test() {
+    blit_test(ColorType::Argb8888);
+}
+
+#[test]
+fn limg_xrgb8888_blit_test() {
+    blit_test(ColorType::Xrgb8888);
+}
+
+#[test]
+fn limg_rgb555_blit_test() {
+    blit_test(ColorType::Rgb555);
+}
+
+#[test]
+fn limg_argb1555_blit_test() {
+    blit_test(ColorType::Argb1555);
+}
+
+#[test]
+fn limg_rgb444_blit_test() {
+    blit_test(ColorType::Rgb444);
+}
+
+#[test]
+fn limg_rgba4444_blit_test() {
+    blit_test(ColorType::Rgba4444);
+}
+
+#[test]
+fn limg_rgb332_blit_test() {
+    blit_test(ColorType::Rgb332);
+}
+
+#[test]
+fn limg_bgr565_blit_test() {
+    blit_test(ColorType::Bgr565);
+}
+
+#[test]
+fn limg_rgb565be_blit_test() {
+    blit_test(ColorType::Rgb565Be);
+}
+
+#[test]
+fn limg_bgr565be_blit_test() {
+    blit_test(ColorType::Bgr565Be);
+}
+
+#[test]
+fn limg_rgba16_blit_test() {
+    blit_test(ColorType::Rgba16);
+}
+
+#[test]
+fn limg_rgbaf32_blit_test() {
+    blit_test(ColorType::RgbaF32);
+}
+
+#[test]
+fn limg_l8_blit_test() {
+    blit_test(ColorType::L8);
+}
+
+#[test]
+fn limg_la88_blit_test() {
+    blit_test(ColorType::La88);
+}
+
+#[test]
+fn blit_dither_test() {
+    let spec = ImageSpec::with_transparent_color(37, 23, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 5 == 0 { [255, 0, 255] } else { [(i * 53) as u8, (i * 7 + 40) as u8, (i / 3) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+
+    let (width, height) = (spec.width as i32, spec.height as i32);
+    let (dst_width, dst_height) = (50, 45);
+    let padding = 16;
+
+    for dither in [Dither::Bayer4x4, Dither::Bayer8x8] {
+        let options = DecodeOptions { dither, ..DecodeOptions::new() };
+
+        for (x, y) in [(0, 0), (-7, -3), (20, 30), (5, 11)] {
+            let mut dst = vec![0xAAu8; dst_width * dst_height];
+            blit_with_options(&data, &mut dst, dst_width, x, y, ColorType::Rgb332, &options).unwrap();
+
+            // しきい値は描画先の位置で決まるため、周期の倍数だけずらした位置にデコードした結果と一致する
+            let stride = dst_width + 2 * padding;
+            let mut expected = vec![0u8; stride * (dst_height + 2 * padding)];
+            decode_strided_with_options(&data, &mut expected, stride, (x + padding as i32) as usize, (y + padding as i32) as usize, ColorType::Rgb332, &options).unwrap();
+
+            for (i, &value) in dst.iter().enumerate() {
+                let (dst_x, dst_y) = ((i % dst_width) as i32, (i / dst_width) as i32);
+                let (src_x, src_y) = (dst_x - x, dst_y - y);
+
+                let inside = (0..width).contains(&src_x) && (0..height).contains(&src_y);
+                let transparent = inside && (src_y * width + src_x) % 5 == 0;
+
+                if inside && !transparent {
+                    assert_eq!(value, expected[stride * (dst_y as usize + padding) + dst_x as usize + padding], "{dither:?} {x} {y} {i}");
+                } else {
+                    assert_eq!(value, 0xAA, "{dither:?} {x} {y} {i}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn encode_alpha_threshold_test() {
+    let transparent_color = rgb_to_pixel([255, 0, 255]);
+    let spec = ImageSpec::with_transparent_color(37, 5, transparent_color);
+
+    let rgba: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| [i as u8, (i * 3) as u8, 0, (i * 7) as u8]).collect();
+
+    for alpha_threshold in [0, 1, 128, 255] {
+        let mut options = EncodeOptions::new();
+        options.alpha_threshold = alpha_threshold;
+
+        let mut data = vec![0u8; encoded_size(&spec)];
+        encode_with_options(&rgba, &mut data, &spec, ColorType::Rgba8888, &options).unwrap();
+
+        let mut decode_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        decode(&data, &mut decode_buf, ColorType::Rgba8888).unwrap();
+
+        let mut data_buf = vec![0u8; PIXEL_BYTES * spec.num_pixels()];
+        encode_data_with_options(&rgba, &mut data_buf, &spec, ColorType::Rgba8888, &options).unwrap();
+        assert_eq!(data[HEADER_SIZE..], data_buf[..]);
+
+        for (src, decoded) in rgba.chunks_exact(4).zip(decode_buf.chunks_exact(4)) {
+            if src[3] < alpha_threshold {
+                assert_eq!(decoded[3], 0);
+            } else {
+                assert_eq!(decoded[3], 255);
+                assert_eq!(rgb_to_pixel([decoded[0], decoded[1], decoded[2]]), rgb_to_pixel([src[0], src[1], src[2]]));
+            }
+        }
+    }
+
+    // 透明色が指定されていない場合はアルファ値を無視する
+    let spec = ImageSpec::new(37, 5);
+    let mut alpha_data = vec![0u8; encoded_size(&spec)];
+    let mut rgb_data = vec![0u8; encoded_size(&spec)];
+
+    let rgb: Vec<u8> = rgba.chunks_exact(4).flat_map(|rgba| [rgba[0], rgba[1], rgba[2]]).collect();
+
+    encode(&rgba, &mut alpha_data, &spec, ColorType::Rgba8888).unwrap();
+    encode(&rgb, &mut rgb_data, &spec, ColorType::Rgb888).unwrap();
+
+    assert_eq!(alpha_data, rgb_data);
+}
+
+#[test]
+fn select_transparent_color_test() {
+    let options = EncodeOptions::new();
+
+    // すべての色を使用した256x256の画像
+    let mut rgba: Vec<u8> = (0..=u16::MAX).flat_map(|pixel| {
+        let [r, g, b] = pixel_to_rgb(pixel);
+        [r, g, b, 255]
+    }).collect();
+
+    assert!(matches!(select_transparent_color(&rgba, 256, 256, &options), Err(Error::NoAvailableTransparentColor)));
+
+    // 透明なピクセルの色は使用済みに含まれない
+    let unused = rgb_to_pixel([100, 100, 100]) as usize;
+    rgba[unused * 4 + 3] = 0;
+
+    let spec = select_transparent_color(&rgba, 256, 256, &options).unwrap();
+    assert_eq!(spec.transparent_color, Some(unused as u16));
+    assert_eq!((spec.width, spec.height), (256, 256));
+
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgba, &mut data, &spec, ColorType::Rgba8888).unwrap();
+
+    let mut decode_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+    decode(&data, &mut decode_buf, ColorType::Rgba8888).unwrap();
+
+    for (src, decoded) in rgba.chunks_exact(4).zip(decode_buf.chunks_exact(4)) {
+        assert_eq!(src, decoded);
+    }
+
+    // マゼンタが使用されている場合は次の候補を選ぶ
+    let rgba = [255, 0, 255, 255, 255, 4, 255, 255, 255, 8, 255, 127];
+    let spec = select_transparent_color(&rgba, 3, 1, &options).unwrap();
+    assert_eq!(spec.transparent_color, Some(rgb_to_pixel([255, 8, 255])));
+
+    assert!(matches!(select_transparent_color(&rgba, 4, 1, &options), Err(Error::InputBufferTooSmall)));
+    assert!(matches!(select_transparent_color(&rgba, 0, 1, &options), Err(Error::ZeroImageDimensions)));
+}
+
+#[test]
+fn encode_collision_free_test() {
+    let transparent_color = rgb_to_pixel([255, 0, 255]);
+
+    for pixel_endian in [PixelEndian::Big, PixelEndian::Little] {
+        let spec = ImageSpec { transparent_color: Some(transparent_color), ..ImageSpec::with_pixel_endian(37, 5, pixel_endian) };
+        let num_pixels = spec.num_pixels();
+
+        // 3ピクセルに1つが減色で透明色と一致し、そのうち半分は透明
+        let rgba: Vec<u8> = (0..num_pixels).flat_map(|i| {
+            if i % 3 == 0 {
+                [255, (i % 4) as u8, 250, if i % 2 == 0 { 255 } else { 0 }]
+            } else {
+                [i as u8, 100, 0, 255]
+            }
+        }).collect();
+        let rgb: Vec<u8> = rgba.chunks_exact(4).flat_map(|rgba| [rgba[0], rgba[1], rgba[2]]).collect();
+        let rgb565: Vec<u8> = rgba.chunks_exact(4).flat_map(|rgba| rgb_to_pixel([rgba[0], rgba[1], rgba[2]]).to_ne_bytes()).collect();
+
+        let collisions = (0..num_pixels).filter(|i| i % 3 == 0).count();
+        let opaque_collisions = (0..num_pixels).filter(|i| i % 6 == 0).count();
+
+        for (data, color_type, expected) in [
+            (&rgb, ColorType::Rgb888, collisions),
+            (&rgb565, ColorType::Rgb565, collisions),
+            (&rgba, ColorType::Rgba8888, opaque_collisions),
+        ] {
+            let mut buf = vec![0u8; encoded_size(&spec)];
+            let (written_size, adjusted_pixels) = encode_collision_free(data, &mut buf, &spec, color_type, &EncodeOptions::new()).unwrap();
+
+            assert_eq!(written_size, encoded_size(&spec));
+            assert_eq!(adjusted_pixels, expected);
+
+            let mut data_buf = vec![0u8; PIXEL_BYTES * num_pixels];
+            let (_, data_adjusted_pixels) = encode_data_collision_free(data, &mut data_buf, &spec, color_type, &EncodeOptions::new()).unwrap();
+            assert_eq!(data_adjusted_pixels, expected);
+            assert_eq!(buf[HEADER_SIZE..], data_buf[..]);
+
+            let mut decode_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+            decode(&buf, &mut decode_buf, ColorType::Rgba8888).unwrap();
+
+            for (i, (src, decoded)) in rgba.chunks_exact(4).zip(decode_buf.chunks_exact(4)).enumerate() {
+                let transparent = color_type == ColorType::Rgba8888 && src[3] == 0;
+                assert_eq!(decoded[3], if transparent { 0 } else { 255 }, "pixel {i}");
+            }
+        }
+    }
+
+    // 透明色が指定されていない場合は通常のエンコードと同じ
+    let spec = ImageSpec::new(2, 1);
+    let mut a_buf = vec![0u8; encoded_size(&spec)];
+    let mut b_buf = vec![0u8; encoded_size(&spec)];
+
+    let (_, adjusted_pixels) = encode_collision_free(&[255, 0, 255, 1, 2, 3], &mut a_buf, &spec, ColorType::Rgb888, &EncodeOptions::new()).unwrap();
+    encode(&[255, 0, 255, 1, 2, 3], &mut b_buf, &spec, ColorType::Rgb888).unwrap();
+
+    assert_eq!(adjusted_pixels, 0);
+    assert_eq!(a_buf, b_buf);
+}
+
+#[test]
+fn encode_dither_test() {
+    for (dither, size) in [(Dither::Bayer4x4, 4u16), (Dither::Bayer8x8, 8u16)] {
+        let options = EncodeOptions { dither, ..EncodeOptions::new() };
+
+        // 1周期分の平均は切り捨て前の値と一致する
+        for value in [3u8, 50, 103, 200, 248] {
+            for pixel_endian in [PixelEndian::Big, PixelEndian::Little] {
+                let spec = ImageSpec::with_pixel_endian(size * 5, size * 2, pixel_endian);
+                let rgb = vec![value; 3 * spec.num_pixels()];
+                let rgba: Vec<u8> = rgb.chunks_exact(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect();
+
+                let mut rgb_data = vec![0u8; encoded_size(&spec)];
+                let mut rgba_data = vec![0u8; encoded_size(&spec)];
+                encode_with_options(&rgb, &mut rgb_data, &spec, ColorType::Rgb888, &options).unwrap();
+                encode_with_options(&rgba, &mut rgba_data, &spec, ColorType::Rgba8888, &options).unwrap();
+                assert_eq!(rgb_data, rgba_data);
+
+                let mut pixels = vec![0u8; decoded_size(&spec, ColorType::Rgb565)];
+                decode(&rgb_data, &mut pixels, ColorType::Rgb565).unwrap();
+                let pixels: Vec<u16> = pixels.chunks_exact(2).map(|pixel| u16::from_ne_bytes([pixel[0], pixel[1]])).collect();
+
+                let (mut r_sum, mut g_sum, mut b_sum) = (0, 0, 0);
+                for y in 0..size as usize {
+                    for x in 0..size as usize {
+                        let pixel = pixels[y * spec.width as usize + x] as u32;
+                        r_sum += pixel >> 11;
+                        g_sum += (pixel >> 5) & 0x3F;
+                        b_sum += pixel & 0x1F;
+                    }
+                }
+
+                let num_cells = (size as u32) * (size as u32);
+                assert_eq!(r_sum * 8, value as u32 * num_cells);
+                assert_eq!(g_sum * 4, value as u32 * num_cells);
+                assert_eq!(b_sum * 8, value as u32 * num_cells);
+            }
+        }
+    }
+
+    // ディザリングしない場合は通常のエンコードと同じ
+    let spec = ImageSpec::new(37, 5);
+    let rgb: Vec<u8> = (0..3 * spec.num_pixels()).map(|i| i as u8).collect();
+
+    let mut a_buf = vec![0u8; encoded_size(&spec)];
+    let mut b_buf = vec![0u8; encoded_size(&spec)];
+
+    encode_with_options(&rgb, &mut a_buf, &spec, ColorType::Rgb888, &EncodeOptions { dither: Dither::None, ..EncodeOptions::new() }).unwrap();
+    encode(&rgb, &mut b_buf, &spec, ColorType::Rgb888).unwrap();
+    assert_eq!(a_buf, b_buf);
+
+    // ディザリングしても不透明なピクセルは透明色にならない
+    let transparent_color = rgb_to_pixel([255, 0, 255]);
+    let spec = ImageSpec::with_transparent_color(37, 5, transparent_color);
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| [248 + (i % 8) as u8, (i % 3) as u8, 250]).collect();
+    let options = EncodeOptions { dither: Dither::Bayer4x4, ..EncodeOptions::new() };
+
+    encode_with_options(&rgb, &mut a_buf, &spec, ColorType::Rgb888, &options).unwrap();
+    let (_, adjusted_pixels) = encode_collision_free(&rgb, &mut b_buf, &spec, ColorType::Rgb888, &options).unwrap();
+
+    let collisions = a_buf[HEADER_SIZE..].chunks_exact(2).filter(|pixel| u16::from_le_bytes([pixel[0], pixel[1]]) == transparent_color).count();
+    assert!(collisions > 0);
+    assert_eq!(adjusted_pixels, collisions);
+    assert!(b_buf[HEADER_SIZE..].chunks_exact(2).all(|pixel| u16::from_le_bytes([pixel[0], pixel[1]]) != transparent_color));
+}
+
+#[test]
+fn encode_error_diffused_test() {
+    let kernels = [DiffusionKernel::FloydSteinberg, DiffusionKernel::Atkinson];
+
+    for kernel in kernels {
+        for serpentine in [false, true] {
+            let diffusion = ErrorDiffusion { kernel, serpentine };
+
+            // 平坦な画像の平均値はおおよそ元の値と一致する
+            for value in [3u8, 50, 100, 203, 250] {
+                let spec = ImageSpec::new(64, 32);
+                let rgb = vec![value; 3 * spec.num_pixels()];
+
+                let mut data = vec![0u8; encoded_size(&spec)];
+                let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+                let written_size = encode_error_diffused(&rgb, &mut data, &spec, ColorType::Rgb888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+                assert_eq!(written_size, encoded_size(&spec));
+
+                let mut pixels = vec![0u8; decoded_size(&spec, ColorType::Rgb888)];
+                decode(&data, &mut pixels, ColorType::Rgb888).unwrap();
+
+                let tolerance = match kernel {
+                    DiffusionKernel::FloydSteinberg => 0.5,
+                    // Atkinsonは誤差の一部を捨てるため、量子化幅の半分まで許容する
+                    DiffusionKernel::Atkinson => 4.5,
+                };
+
+                for channel in 0..3 {
+                    let sum: u32 = pixels.iter().skip(channel).step_by(3).map(|&v| v as u32).sum();
+                    let mean = sum as f64 / spec.num_pixels() as f64;
+                    assert!((mean - value as f64).abs() < tolerance, "{kernel:?} {serpentine} {value} {channel} {mean}");
+                }
+            }
+
+            // RGB888とRGBA8888の結果は一致し、エンディアンはバイト順のみ異なる
+            let rgb: Vec<u8> = (0..3 * 37 * 5).map(|i| (i * 7) as u8).collect();
+            let rgba: Vec<u8> = rgb.chunks_exact(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect();
+
+            let be_spec = ImageSpec::with_pixel_endian(37, 5, PixelEndian::Big);
+            let le_spec = ImageSpec::with_pixel_endian(37, 5, PixelEndian::Little);
+            let mut scratch = vec![0i16; diffusion_scratch_len(37)];
+
+            let mut rgb_buf = vec![0u8; PIXEL_BYTES * le_spec.num_pixels()];
+            let mut rgba_buf = vec![0u8; PIXEL_BYTES * le_spec.num_pixels()];
+            let mut be_buf = vec![0u8; PIXEL_BYTES * be_spec.num_pixels()];
+            encode_data_error_diffused(&rgb, &mut rgb_buf, &le_spec, ColorType::Rgb888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+            encode_data_error_diffused(&rgba, &mut rgba_buf, &le_spec, ColorType::Rgba8888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+            encode_data_error_diffused(&rgb, &mut be_buf, &be_spec, ColorType::Rgb888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+            assert_eq!(rgb_buf, rgba_buf);
+
+            for (le, be) in rgb_buf.chunks_exact(2).zip(be_buf.chunks_exact(2)) {
+                assert_eq!(le, [be[1], be[0]]);
+            }
+        }
+    }
+
+    let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+
+    // 透明なピクセルは透明色になる
+    let transparent_color = rgb_to_pixel([255, 0, 255]);
+    let spec = ImageSpec::with_transparent_color(9, 3, transparent_color);
+    let rgba: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| [120, 60, 30, if i % 2 == 0 { 0 } else { 255 }]).collect();
+
+    let mut buf = vec![0u8; PIXEL_BYTES * spec.num_pixels()];
+    let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+    encode_data_error_diffused(&rgba, &mut buf, &spec, ColorType::Rgba8888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+
+    for (i, pixel) in buf.chunks_exact(2).enumerate() {
+        assert_eq!(u16::from_le_bytes([pixel[0], pixel[1]]) == transparent_color, i % 2 == 0);
+    }
+
+    // RGB565からは通常のエンコードと同じ
+    let spec = ImageSpec::new(9, 3);
+    let rgb565: Vec<u8> = (0..PIXEL_BYTES * spec.num_pixels()).map(|i| (i * 13) as u8).collect();
+    let mut a_buf = vec![0u8; encoded_size(&spec)];
+    let mut b_buf = vec![0u8; encoded_size(&spec)];
+    encode_error_diffused(&rgb565, &mut a_buf, &spec, ColorType::Rgb565, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+    encode(&rgb565, &mut b_buf, &spec, ColorType::Rgb565).unwrap();
+    assert_eq!(a_buf, b_buf);
+
+    // 作業バッファが足りない
+    let rgb = vec![0u8; 3 * spec.num_pixels()];
+    let mut scratch = vec![0i16; diffusion_scratch_len(spec.width) - 1];
+    let result = encode_error_diffused(&rgb, &mut a_buf, &spec, ColorType::Rgb888, &EncodeOptions::new(), &diffusion, &mut scratch);
+    assert!(matches!(result, Err(Error::ScratchBufferTooSmall)));
+}
+
+#[test]
+fn quantization_test() {
+    let quantizations = [Quantization::Truncate, Quantization::Round, Quantization::Nearest];
+
+    // チャンネルごとの量子化を総当たりで確認する
+    for bits in [5, 6] {
+        let max = (1u16 << bits) - 1;
+        let expand = |q: u8| pixel_to_rgb(if bits == 5 { (q as u16) << 11 } else { (q as u16) << 5 })[if bits == 5 { 0 } else { 1 }];
+
+        for value in 0..=255u8 {
+            let shift = 8 - bits;
+            assert_eq!(Quantization::Truncate.quantize(value, bits), value >> shift);
+            assert_eq!(Quantization::Round.quantize(value, bits) as u16, ((value as u16 + (1 << (shift - 1))) >> shift).min(max));
+
+            // 復元値が最も近く、等距離なら大きい値
+            let nearest = Quantization::Nearest.quantize(value, bits);
+            let best = (0..=max as u8).rev().min_by_key(|&q| expand(q).abs_diff(value)).unwrap();
+            assert_eq!(nearest, best, "{bits} {value}");
+        }
+    }
+
+    // 全2^24色で各チャンネルの量子化と一致する
+    for quantization in quantizations {
+        let rb: Vec<u16> = (0..=255).map(|v| quantization.quantize(v, 5) as u16).collect();
+        let g: Vec<u16> = (0..=255).map(|v| quantization.quantize(v, 6) as u16).collect();
+
+        for rgb in 0..1u32 << 24 {
+            let [_, r, g_value, b] = rgb.to_be_bytes();
+            let expected = (rb[r as usize] << 11) | (g[g_value as usize] << 5) | rb[b as usize];
+            assert_eq!(rgb_to_pixel_with_quantization([r, g_value, b], quantization), expected);
+        }
+    }
+
+    // 切り捨ては従来のrgb_to_pixelと同じ
+    for rgb in (0..1u32 << 24).step_by(7) {
+        let [_, r, g, b] = rgb.to_be_bytes();
+        assert_eq!(rgb_to_pixel_with_quantization([r, g, b], Quantization::Truncate), rgb_to_pixel([r, g, b]));
+    }
+
+    // エンコード結果も全2^24色で量子化方法に従う（端数の処理は幅が37の画像で確認する）
+    for spec in [ImageSpec::new(4096, 4096), ImageSpec::new(37, 5)] {
+        // 奇数倍は2^24を法とした全単射のため、4096x4096の画像には全色が1回ずつ現れる
+        let rgb: Vec<[u8; 3]> = (0..spec.num_pixels() as u32).map(|i| {
+            let [_, r, g, b] = (i.wrapping_mul(0x9E37_79B1) & 0xFF_FFFF).to_be_bytes();
+            [r, g, b]
+        }).collect();
+
+        for color_type in [ColorType::Rgb888, ColorType::Rgba8888] {
+            // 透明色が無いため、アルファ値は結果に影響しない
+            let input: Vec<u8> = match color_type {
+                ColorType::Rgb888 => rgb.concat(),
+                _ => rgb.iter().enumerate().flat_map(|(i, &[r, g, b])| [r, g, b, (i * 31) as u8]).collect(),
+            };
+
+            for quantization in quantizations {
+                let options = EncodeOptions { quantization, ..EncodeOptions::new() };
+
+                let mut data = vec![0u8; encoded_size(&spec)];
+                encode_with_options(&input, &mut data, &spec, color_type, &options).unwrap();
+
+                for (pixel, &rgb) in data[HEADER_SIZE..].chunks_exact(2).zip(&rgb) {
+                    assert_eq!(u16::from_le_bytes([pixel[0], pixel[1]]), rgb_to_pixel_with_quantization(rgb, quantization), "{color_type:?} {quantization:?} {rgb:?}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bgr_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(41, 7, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        // デコード結果はRGBのRとBを入れ替えたもの
+        for (rgb_type, bgr_type) in [(ColorType::Rgb888, ColorType::Bgr888), (ColorType::Rgba8888, ColorType::Bgra8888)] {
+            let bytes_per_pixel = rgb_type.bytes_per_pixel();
+            let mut rgb_buf = vec![0u8; decoded_size(&spec, rgb_type)];
+            let mut bgr_buf = vec![0u8; decoded_size(&spec, bgr_type)];
+            decode(&data, &mut rgb_buf, rgb_type).unwrap();
+            decode(&data, &mut bgr_buf, bgr_type).unwrap();
+
+            let mut swapped = rgb_buf.clone();
+            swapped.chunks_exact_mut(bytes_per_pixel).for_each(|pixel| pixel.swap(0, 2));
+            assert_eq!(bgr_buf, swapped);
+
+            // BGRからのエンコード結果はRGBからのものと同じ
+            let mut rgb_data = vec![0u8; encoded_size(&spec)];
+            let mut bgr_data = vec![0u8; encoded_size(&spec)];
+
+            for options in [
+                EncodeOptions::new(),
+                EncodeOptions { quantization: Quantization::Nearest, ..EncodeOptions::new() },
+                EncodeOptions { dither: Dither::Bayer8x8, ..EncodeOptions::new() },
+            ] {
+                encode_with_options(&rgb_buf, &mut rgb_data, &spec, rgb_type, &options).unwrap();
+                encode_with_options(&bgr_buf, &mut bgr_data, &spec, bgr_type, &options).unwrap();
+                assert_eq!(bgr_data, rgb_data);
+            }
+
+            if spec.transparent_color.is_some() && rgb_type == ColorType::Rgba8888 {
+                encode_collision_free(&rgb_buf, &mut rgb_data, &spec, rgb_type, &EncodeOptions::new()).unwrap();
+                encode_collision_free(&bgr_buf, &mut bgr_data, &spec, bgr_type, &EncodeOptions::new()).unwrap();
+                assert_eq!(bgr_data, rgb_data);
+            }
+        }
+    }
+}
+
+#[test]
+fn gray_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(37, 5, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        let mut rgba_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        decode(&data, &mut rgba_buf, ColorType::Rgba8888).unwrap();
+
+        // 既定はBT.601
+        let mut default_buf = vec![0u8; decoded_size(&spec, ColorType::L8)];
+        decode(&data, &mut default_buf, ColorType::L8).unwrap();
+
+        for luma in [LumaCoefficients::Bt601, LumaCoefficients::Bt709] {
+            let mut options = DecodeOptions::new();
+            options.luma = luma;
+
+            let mut l8_buf = vec![0u8; decoded_size(&spec, ColorType::L8)];
+            let mut la88_buf = vec![0u8; decoded_size(&spec, ColorType::La88)];
+            decode_with_options(&data, &mut l8_buf, ColorType::L8, &options).unwrap();
+            decode_data_with_options(&data[HEADER_SIZE..], &mut la88_buf, &spec, ColorType::La88, &options).unwrap();
+
+            if luma == LumaCoefficients::Bt601 {
+                assert_eq!(l8_buf, default_buf);
+            }
+
+            for ((&l, la), rgba) in l8_buf.iter().zip(la88_buf.chunks_exact(2)).zip(rgba_buf.chunks_exact(4)) {
+                assert_eq!(l, luma.luma([rgba[0], rgba[1], rgba[2]]));
+                assert_eq!(la, [l, rgba[3]]);
+            }
+
+            // グレースケールからのエンコード結果はR = G = Bからのものと同じ
+            let rgb: Vec<u8> = l8_buf.iter().flat_map(|&l| [l, l, l]).collect();
+            let rgba: Vec<u8> = la88_buf.chunks_exact(2).flat_map(|la| [la[0], la[0], la[0], la[1]]).collect();
+
+            let mut rgb_data = vec![0u8; encoded_size(&spec)];
+            let mut gray_data = vec![0u8; encoded_size(&spec)];
+
+            for quantization in [Quantization::Truncate, Quantization::Nearest] {
+                let options = EncodeOptions { quantization, ..EncodeOptions::new() };
+
+                encode_with_options(&rgb, &mut rgb_data, &spec, ColorType::Rgb888, &options).unwrap();
+                encode_with_options(&l8_buf, &mut gray_data, &spec, ColorType::L8, &options).unwrap();
+                assert_eq!(gray_data, rgb_data);
+
+                encode_with_options(&rgba, &mut rgb_data, &spec, ColorType::Rgba8888, &options).unwrap();
+                encode_with_options(&la88_buf, &mut gray_data, &spec, ColorType::La88, &options).unwrap();
+                assert_eq!(gray_data, rgb_data);
+            }
+
+            if spec.transparent_color.is_some() {
+                encode_collision_free(&rgba, &mut rgb_data, &spec, ColorType::Rgba8888, &EncodeOptions::new()).unwrap();
+                encode_collision_free(&la88_buf, &mut gray_data, &spec, ColorType::La88, &EncodeOptions::new()).unwrap();
+                assert_eq!(gray_data, rgb_data);
+            }
+        }
+    }
+}
+
+#[test]
+fn argb_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(43, 3, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        let mut rgba_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        let mut argb_buf = vec![0u8; decoded_size(&spec, ColorType::Argb8888)];
+        let mut xrgb_buf = vec![0u8; decoded_size(&spec, ColorType::Xrgb8888)];
+        decode(&data, &mut rgba_buf, ColorType::Rgba8888).unwrap();
+        decode(&data, &mut argb_buf, ColorType::Argb8888).unwrap();
+        decode_data(&data[HEADER_SIZE..], &mut xrgb_buf, &spec, ColorType::Xrgb8888).unwrap();
+
+        // ネイティブエンディアンのu32として0xAARRGGBB、XRGBのXは常に0xFF
+        for ((argb, xrgb), rgba) in argb_buf.chunks_exact(4).zip(xrgb_buf.chunks_exact(4)).zip(rgba_buf.chunks_exact(4)) {
+            let argb = u32::from_ne_bytes([argb[0], argb[1], argb[2], argb[3]]);
+            let xrgb = u32::from_ne_bytes([xrgb[0], xrgb[1], xrgb[2], xrgb[3]]);
+            assert_eq!(argb, u32::from_be_bytes([rgba[3], rgba[0], rgba[1], rgba[2]]));
+            assert_eq!(xrgb, argb | 0xFF00_0000);
+        }
+
+        // ARGBからのエンコード結果はRGBAからのものと同じ
+        let mut rgba_data = vec![0u8; encoded_size(&spec)];
+        let mut argb_data = vec![0u8; encoded_size(&spec)];
+
+        for options in [
+            EncodeOptions::new(),
+            EncodeOptions { quantization: Quantization::Nearest, ..EncodeOptions::new() },
+            EncodeOptions { dither: Dither::Bayer4x4, ..EncodeOptions::new() },
+        ] {
+            encode_with_options(&rgba_buf, &mut rgba_data, &spec, ColorType::Rgba8888, &options).unwrap();
+            encode_with_options(&argb_buf, &mut argb_data, &spec, ColorType::Argb8888, &options).unwrap();
+            assert_eq!(argb_data, rgba_data);
+
+            // XRGBのXは無視される
+            let rgb: Vec<u8> = rgba_buf.chunks_exact(4).flat_map(|rgba| [rgba[0], rgba[1], rgba[2]]).collect();
+            encode_with_options(&rgb, &mut rgba_data, &spec, ColorType::Rgb888, &options).unwrap();
+            encode_with_options(&argb_buf, &mut argb_data, &spec, ColorType::Xrgb8888, &options).unwrap();
+            assert_eq!(argb_data, rgba_data);
+        }
+
+        let diffusion = ErrorDiffusion { kernel: DiffusionKernel::FloydSteinberg, serpentine: true };
+        let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+        encode_error_diffused(&rgba_buf, &mut rgba_data, &spec, ColorType::Rgba8888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+        encode_error_diffused(&argb_buf, &mut argb_data, &spec, ColorType::Argb8888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+        assert_eq!(argb_data, rgba_data);
+
+        if spec.transparent_color.is_some() {
+            encode_collision_free(&rgba_buf, &mut rgba_data, &spec, ColorType::Rgba8888, &EncodeOptions::new()).unwrap();
+            encode_collision_free(&argb_buf, &mut argb_data, &spec, ColorType::Argb8888, &EncodeOptions::new()).unwrap();
+            assert_eq!(argb_data, rgba_data);
+        }
+    }
+}
+
+#[test]
+fn rgb555_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(39, 5, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        let mut rgba_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        let mut rgb555_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb555)];
+        let mut argb1555_buf = vec![0u8; decoded_size(&spec, ColorType::Argb1555)];
+        decode(&data, &mut rgba_buf, ColorType::Rgba8888).unwrap();
+        decode(&data, &mut rgb555_buf, ColorType::Rgb555).unwrap();
+        decode_data(&data[HEADER_SIZE..], &mut argb1555_buf, &spec, ColorType::Argb1555).unwrap();
+
+        // ネイティブエンディアンのu16として各チャンネルの上位5ビット、透明色のみAが 0
+        for ((rgb555, argb1555), rgba) in rgb555_buf.chunks_exact(2).zip(argb1555_buf.chunks_exact(2)).zip(rgba_buf.chunks_exact(4)) {
+            let rgb555 = u16::from_ne_bytes([rgb555[0], rgb555[1]]);
+            let argb1555 = u16::from_ne_bytes([argb1555[0], argb1555[1]]);
+            assert_eq!(rgb555, ((rgba[0] as u16 >> 3) << 10) | ((rgba[1] as u16 >> 3) << 5) | (rgba[2] as u16 >> 3));
+            assert_eq!(argb1555, rgb555 | if rgba[3] == 0 { 0 } else { 0x8000 });
+        }
+
+        // RGB555からのエンコード結果は各チャンネルをビット複製で8ビットに拡張したRGBAからのものと同じ
+        let expand = |value: u16| ((value << 3) | (value >> 2)) as u8;
+        let rgba: Vec<u8> = argb1555_buf.chunks_exact(2).flat_map(|argb| {
+            let argb = u16::from_ne_bytes([argb[0], argb[1]]);
+            [expand((argb >> 10) & 0x1F), expand((argb >> 5) & 0x1F), expand(argb & 0x1F), if argb & 0x8000 == 0 { 0 } else { 255 }]
+        }).collect();
+
+        let mut rgba_data = vec![0u8; encoded_size(&spec)];
+        let mut rgb555_data = vec![0u8; encoded_size(&spec)];
+
+        // 量子化方法とディザリングは適用されない
+        for options in [
+            EncodeOptions::new(),
+            EncodeOptions { quantization: Quantization::Nearest, ..EncodeOptions::new() },
+            EncodeOptions { dither: Dither::Bayer8x8, ..EncodeOptions::new() },
+        ] {
+            encode_with_options(&rgba, &mut rgba_data, &spec, ColorType::Rgba8888, &EncodeOptions::new()).unwrap();
+            encode_with_options(&argb1555_buf, &mut rgb555_data, &spec, ColorType::Argb1555, &options).unwrap();
+            assert_eq!(rgb555_data, rgba_data);
+
+            // Aは無視される
+            if spec.transparent_color.is_none() {
+                encode_with_options(&rgb555_buf, &mut rgb555_data, &spec, ColorType::Rgb555, &options).unwrap();
+                assert_eq!(rgb555_data, rgba_data);
+            }
+
+            if spec.transparent_color.is_some() {
+                encode_collision_free(&rgba, &mut rgba_data, &spec, ColorType::Rgba8888, &EncodeOptions::new()).unwrap();
+                encode_collision_free(&argb1555_buf, &mut rgb555_data, &spec, ColorType::Argb1555, &options).unwrap();
+                assert_eq!(rgb555_data, rgba_data);
+            }
+        }
+
+        // 誤差拡散しても変わらない
+        let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+        let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+        encode_with_options(&rgba, &mut rgba_data, &spec, ColorType::Rgba8888, &EncodeOptions::new()).unwrap();
+        encode_error_diffused(&argb1555_buf, &mut rgb555_data, &spec, ColorType::Argb1555, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+        assert_eq!(rgb555_data, rgba_data);
+    }
+}
+
+#[test]
+fn rgb444_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(35, 6, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        let mut rgba_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        let mut rgb444_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb444)];
+        let mut rgba4444_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba4444)];
+        decode(&data, &mut rgba_buf, ColorType::Rgba8888).unwrap();
+        decode(&data, &mut rgb444_buf, ColorType::Rgb444).unwrap();
+        decode_data(&data[HEADER_SIZE..], &mut rgba4444_buf, &spec, ColorType::Rgba4444).unwrap();
+
+        // ネイティブエンディアンのu16として各チャンネルの上位4ビット
+        for ((rgb444, rgba4444), rgba) in rgb444_buf.chunks_exact(2).zip(rgba4444_buf.chunks_exact(2)).zip(rgba_buf.chunks_exact(4)) {
+            let rgb444 = u16::from_ne_bytes([rgb444[0], rgb444[1]]);
+            let rgba4444 = u16::from_ne_bytes([rgba4444[0], rgba4444[1]]);
+            assert_eq!(rgb444, ((rgba[0] as u16 >> 4) << 8) | ((rgba[1] as u16 >> 4) << 4) | (rgba[2] as u16 >> 4));
+            assert_eq!(rgba4444, (rgb444 << 4) | (rgba[3] as u16 >> 4));
+        }
+
+        // RGBA4444からのエンコード結果は各チャンネルをビット複製で8ビットに拡張したRGBAからのものと同じ
+        let rgba: Vec<u8> = rgba4444_buf.chunks_exact(2).flat_map(|rgba| {
+            let rgba = u16::from_ne_bytes([rgba[0], rgba[1]]);
+            [12, 8, 4, 0].map(|shift| ((rgba >> shift) & 0x0F) as u8 * 0x11)
+        }).collect();
+
+        let mut rgba_data = vec![0u8; encoded_size(&spec)];
+        let mut rgba4444_data = vec![0u8; encoded_size(&spec)];
+
+        // 量子化方法とディザリングは適用されない
+        for options in [
+            EncodeOptions::new(),
+            EncodeOptions { quantization: Quantization::Round, ..EncodeOptions::new() },
+            EncodeOptions { dither: Dither::Bayer4x4, ..EncodeOptions::new() },
+            EncodeOptions { alpha_threshold: 0, ..EncodeOptions::new() },
+        ] {
+            let expected_options = EncodeOptions { alpha_threshold: options.alpha_threshold, ..EncodeOptions::new() };
+
+            encode_with_options(&rgba, &mut rgba_data, &spec, ColorType::Rgba8888, &expected_options).unwrap();
+            encode_with_options(&rgba4444_buf, &mut rgba4444_data, &spec, ColorType::Rgba4444, &options).unwrap();
+            assert_eq!(rgba4444_data, rgba_data);
+
+            if spec.transparent_color.is_some() {
+                encode_collision_free(&rgba, &mut rgba_data, &spec, ColorType::Rgba8888, &expected_options).unwrap();
+                encode_collision_free(&rgba4444_buf, &mut rgba4444_data, &spec, ColorType::Rgba4444, &options).unwrap();
+                assert_eq!(rgba4444_data, rgba_data);
+            } else {
+                // Xは無視される
+                encode_with_options(&rgb444_buf, &mut rgba4444_data, &spec, ColorType::Rgb444, &options).unwrap();
+                assert_eq!(rgba4444_data, rgba_data);
+            }
+        }
+    }
+}
+
+#[test]
+fn rgb332_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(35, 6, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    let channels = |rgb332: u8| [rgb332 >> 5, (rgb332 >> 2) & 0x07, rgb332 & 0x03];
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        let mut rgba_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        let mut rgb332_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb332)];
+        decode(&data, &mut rgba_buf, ColorType::Rgba8888).unwrap();
+        decode_data(&data[HEADER_SIZE..], &mut rgb332_buf, &spec, ColorType::Rgb332).unwrap();
+
+        // 各チャンネルの上位ビット
+        for (&rgb332, rgba) in rgb332_buf.iter().zip(rgba_buf.chunks_exact(4)) {
+            assert_eq!(rgb332, (rgba[0] & 0xE0) | ((rgba[1] >> 5) << 2) | (rgba[2] >> 6));
+        }
+
+        // ディザリングすると各チャンネルは切り捨てた値か1段階上の値になる
+        for dither in [Dither::Bayer4x4, Dither::Bayer8x8] {
+            let mut options = DecodeOptions::new();
+            options.dither = dither;
+
+            let mut dither_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb332)];
+            decode_with_options(&data, &mut dither_buf, ColorType::Rgb332, &options).unwrap();
+
+            for (&dithered, &truncated) in dither_buf.iter().zip(&rgb332_buf) {
+                for (dithered, truncated) in channels(dithered).into_iter().zip(channels(truncated)) {
+                    assert!(dithered == truncated || dithered == truncated + 1);
+                }
+            }
+        }
+
+        // RGB332からのエンコード結果は各チャンネルをビット複製で8ビットに拡張したRGBからのものと同じ
+        let expand = |value: u8, bits: usize| (0..8).step_by(bits).fold(0, |acc, shift| acc | (value >> shift));
+        let rgb: Vec<u8> = rgb332_buf.iter().flat_map(|&rgb332| {
+            let [r, g, b] = channels(rgb332);
+            [expand(r << 5, 3), expand(g << 5, 3), expand(b << 6, 2)]
+        }).collect();
+
+        let mut rgb_data = vec![0u8; encoded_size(&spec)];
+        let mut rgb332_data = vec![0u8; encoded_size(&spec)];
+
+        // 量子化方法とディザリングは適用されない
+        for options in [
+            EncodeOptions::new(),
+            EncodeOptions { quantization: Quantization::Round, ..EncodeOptions::new() },
+            EncodeOptions { dither: Dither::Bayer4x4, ..EncodeOptions::new() },
+        ] {
+            encode(&rgb, &mut rgb_data, &spec, ColorType::Rgb888).unwrap();
+            encode_with_options(&rgb332_buf, &mut rgb332_data, &spec, ColorType::Rgb332, &options).unwrap();
+            assert_eq!(rgb332_data, rgb_data);
+        }
+    }
+}
+
+#[test]
+fn rgb332_dither_test() {
+    // 単色の画像をディザリングすると、各チャンネルの段階の平均は元の値を段階の幅で割った値に近くなる
+    let spec = ImageSpec::new(16, 16);
+    let pixel = rgb_to_pixel([100, 150, 90]);
+    let data = pixel.to_le_bytes().repeat(spec.num_pixels());
+    let rgb = pixel_to_rgb(pixel);
+
+    for dither in [Dither::Bayer4x4, Dither::Bayer8x8] {
+        let mut options = DecodeOptions::new();
+        options.dither = dither;
+
+        let mut rgb332_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb332)];
+        decode_data_with_options(&data, &mut rgb332_buf, &spec, ColorType::Rgb332, &options).unwrap();
+
+        for (channel, (shift, mask, step)) in [(5, 0x07, 32.0), (2, 0x07, 32.0), (0, 0x03, 64.0)].into_iter().enumerate() {
+            let sum: f64 = rgb332_buf.iter().map(|&rgb332| ((rgb332 >> shift) & mask) as f64).sum();
+            let average = sum / spec.num_pixels() as f64;
+            assert!((average - rgb[channel] as f64 / step).abs() < 0.1, "{dither:?} {channel}: {average}");
+        }
+    }
+}
+
+#[test]
+fn bgr565_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(37, 6, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    let swap_rb = |pixel: u16| (pixel << 11) | (pixel & 0x07E0) | (pixel >> 11);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        let mut rgb565_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb565)];
+        let mut bgr565_buf = vec![0u8; decoded_size(&spec, ColorType::Bgr565)];
+        decode(&data, &mut rgb565_buf, ColorType::Rgb565).unwrap();
+        decode_data(&data[HEADER_SIZE..], &mut bgr565_buf, &spec, ColorType::Bgr565).unwrap();
+
+        // ネイティブエンディアンのu16としてRとBを入れ替えたRGB565
+        for (rgb565, bgr565) in rgb565_buf.chunks_exact(2).zip(bgr565_buf.chunks_exact(2)) {
+            let rgb565 = u16::from_ne_bytes([rgb565[0], rgb565[1]]);
+            let bgr565 = u16::from_ne_bytes([bgr565[0], bgr565[1]]);
+            assert_eq!(bgr565, swap_rb(rgb565));
+        }
+
+        // 透明色と一致するピクセルの置き換えもRGB565からのエンコードと同じ
+        let mut rgb565_data = vec![0u8; encoded_size(&spec)];
+        let mut bgr565_data = vec![0u8; encoded_size(&spec)];
+        encode_collision_free(&rgb565_buf, &mut rgb565_data, &spec, ColorType::Rgb565, &EncodeOptions::new()).unwrap();
+        encode_collision_free(&bgr565_buf, &mut bgr565_data, &spec, ColorType::Bgr565, &EncodeOptions::new()).unwrap();
+        assert_eq!(bgr565_data, rgb565_data);
+
+        // RGB565と同様に誤差拡散は適用されない
+        let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+        let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+        encode_error_diffused(&bgr565_buf, &mut bgr565_data, &spec, ColorType::Bgr565, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+        assert_eq!(bgr565_data, data);
+    }
+}
+
+#[test]
+fn rgb565be_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(37, 6, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        for (color_type, be_color_type) in [(ColorType::Rgb565, ColorType::Rgb565Be), (ColorType::Bgr565, ColorType::Bgr565Be)] {
+            let mut native_buf = vec![0u8; decoded_size(&spec, color_type)];
+            let mut be_buf = vec![0u8; decoded_size(&spec, be_color_type)];
+            decode(&data, &mut native_buf, color_type).unwrap();
+            decode_data(&data[HEADER_SIZE..], &mut be_buf, &spec, be_color_type).unwrap();
+
+            // CPUのエンディアンにかかわらず上位バイトが先になる
+            for (native, be) in native_buf.chunks_exact(2).zip(be_buf.chunks_exact(2)) {
+                assert_eq!(u16::from_ne_bytes([native[0], native[1]]).to_be_bytes(), [be[0], be[1]]);
+            }
+
+            // ビッグエンディアンのピクセルからのエンコードで元のデータに戻る
+            let mut be_data = vec![0u8; encoded_size(&spec)];
+            encode(&be_buf, &mut be_data, &spec, be_color_type).unwrap();
+            assert_eq!(be_data, data);
+
+            // 透明色と一致するピクセルの置き換えもネイティブエンディアンからのエンコードと同じ
+            let mut native_data = vec![0u8; encoded_size(&spec)];
+            encode_collision_free(&native_buf, &mut native_data, &spec, color_type, &EncodeOptions::new()).unwrap();
+            encode_collision_free(&be_buf, &mut be_data, &spec, be_color_type, &EncodeOptions::new()).unwrap();
+            assert_eq!(be_data, native_data);
+        }
+    }
+}
+
+/// sRGBの`value`を線形値に変換します。
+fn srgb_to_linear(value: u8) -> f64 {
+    let value = value as f64 / 255.0;
+    if value <= 0.04045 { value / 12.92 } else { ((value + 0.055) / 1.055).powf(2.4) }
+}
+
+#[test]
+fn wide_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(70, 5, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    let linear_options = DecodeOptions { srgb_transfer: false, ..DecodeOptions::new() };
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+
+        let mut rgba_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        let mut rgba16_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba16)];
+        let mut rgbaf32_buf = vec![0u8; decoded_size(&spec, ColorType::RgbaF32)];
+        let mut linear_buf = vec![0u8; decoded_size(&spec, ColorType::RgbaF32)];
+        decode(&data, &mut rgba_buf, ColorType::Rgba8888).unwrap();
+        decode(&data, &mut rgba16_buf, ColorType::Rgba16).unwrap();
+        decode(&data, &mut rgbaf32_buf, ColorType::RgbaF32).unwrap();
+        decode_with_options(&data, &mut linear_buf, ColorType::RgbaF32, &linear_options).unwrap();
+
+        let rgba16: Vec<u16> = rgba16_buf.chunks_exact(2).map(|v| u16::from_ne_bytes([v[0], v[1]])).collect();
+        let rgbaf32: Vec<f32> = rgbaf32_buf.chunks_exact(4).map(|v| f32::from_ne_bytes([v[0], v[1], v[2], v[3]])).collect();
+        let linear: Vec<f32> = linear_buf.chunks_exact(4).map(|v| f32::from_ne_bytes([v[0], v[1], v[2], v[3]])).collect();
+
+        for (i, &value) in rgba_buf.iter().enumerate() {
+            assert_eq!(rgba16[i], value as u16 * 257);
+
+            if i % 4 == 3 {
+                // アルファは透明色のピクセルのみ 0
+                assert_eq!(rgbaf32[i], if value == 0 { 0.0 } else { 1.0 });
+                assert_eq!(linear[i], rgbaf32[i]);
+            } else {
+                assert!((rgbaf32[i] as f64 - srgb_to_linear(value)).abs() < 1e-6, "{i}: {} {value}", rgbaf32[i]);
+                assert_eq!(linear[i], value as f32 / 255.0);
+            }
+        }
+
+        // 8ビットに変換してからのエンコードと同じ
+        let mut expected = vec![0u8; encoded_size(&spec)];
+        let mut expected_collision_free = vec![0u8; encoded_size(&spec)];
+        let mut buf = vec![0u8; encoded_size(&spec)];
+
+        for options in [EncodeOptions::new(), EncodeOptions { dither: Dither::Bayer8x8, ..EncodeOptions::new() }, EncodeOptions { quantization: Quantization::Nearest, ..EncodeOptions::new() }] {
+            encode_with_options(&rgba_buf, &mut expected, &spec, ColorType::Rgba8888, &options).unwrap();
+            let (_, expected_collisions) = encode_collision_free(&rgba_buf, &mut expected_collision_free, &spec, ColorType::Rgba8888, &options).unwrap();
+
+            for (wide, color_type) in [(&rgba16_buf, ColorType::Rgba16), (&rgbaf32_buf, ColorType::RgbaF32)] {
+                encode_with_options(wide, &mut buf, &spec, color_type, &options).unwrap();
+                assert_eq!(buf, expected);
+
+                let (_, collisions) = encode_collision_free(wide, &mut buf, &spec, color_type, &options).unwrap();
+                assert_eq!(collisions, expected_collisions);
+                assert_eq!(buf, expected_collision_free);
+            }
+        }
+
+        let linear_options = EncodeOptions { srgb_transfer: false, ..EncodeOptions::new() };
+        encode_with_options(&linear_buf, &mut buf, &spec, ColorType::RgbaF32, &linear_options).unwrap();
+        assert_eq!(buf, data);
+
+        let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+        let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+        encode_error_diffused(&rgba_buf, &mut expected, &spec, ColorType::Rgba8888, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+        encode_error_diffused(&rgbaf32_buf, &mut buf, &spec, ColorType::RgbaF32, &EncodeOptions::new(), &diffusion, &mut scratch).unwrap();
+        assert_eq!(buf, expected);
+    }
+}
+
+#[test]
+fn wide_clamp_test() {
+    let spec = ImageSpec::new(8, 1);
+
+    let rgb16: Vec<u8> = [0u16, 128, 129, 0x7F80, 0x8080, 0xFF7F, 0xFF80, 0xFFFF, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        .iter().flat_map(|v| v.to_ne_bytes()).collect();
+    let rgb16_expected = [0u8, 0, 1, 127, 128, 254, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+    let values = [-1.0f32, f32::NAN, 2.0, f32::INFINITY, f32::NEG_INFINITY, 0.5, 0.0, 1.0];
+    let rgbf32: Vec<u8> = values.iter().flat_map(|&v| [v, 0.0, 0.0]).flat_map(|v| v.to_ne_bytes()).collect();
+    // 範囲外の値は 0.0 - 1.0 に、NaN は 0.0 に丸める
+    let srgb_expected: Vec<u8> = [0u8, 0, 255, 255, 0, 188, 0, 255].iter().flat_map(|&v| [v, 0, 0]).collect();
+    let linear_expected: Vec<u8> = [0u8, 0, 255, 255, 0, 128, 0, 255].iter().flat_map(|&v| [v, 0, 0]).collect();
+
+    let mut buf = vec![0u8; encoded_size(&spec)];
+    let mut expected = vec![0u8; encoded_size(&spec)];
+    let options = EncodeOptions { quantization: Quantization::Nearest, ..EncodeOptions::new() };
+    let linear_options = EncodeOptions { srgb_transfer: false, ..options };
+
+    for (data, color_type, options, rgb) in [
+        (&rgb16, ColorType::Rgb16, &options, &rgb16_expected[..]),
+        (&rgbf32, ColorType::RgbF32, &options, &srgb_expected[..]),
+        (&rgbf32, ColorType::RgbF32, &linear_options, &linear_expected[..]),
+    ] {
+        encode_with_options(data, &mut buf, &spec, color_type, options).unwrap();
+        encode_with_options(rgb, &mut expected, &spec, ColorType::Rgb888, options).unwrap();
+        assert_eq!(buf, expected);
+    }
+
+    // アルファは伝達関数を適用せずに閾値と比較する
+    let spec = ImageSpec::with_transparent_color(4, 1, rgb_to_pixel([255, 0, 255]));
+    let rgbaf32: Vec<u8> = [0.49f32, 0.51, f32::NAN, 2.0].iter().flat_map(|&a| [1.0, 1.0, 1.0, a]).flat_map(|v| v.to_ne_bytes()).collect();
+
+    let mut buf = vec![0u8; encoded_size(&spec)];
+    encode(&rgbaf32, &mut buf, &spec, ColorType::RgbaF32).unwrap();
+
+    let mut rgba = [0u8; 4 * 4];
+    decode(&buf, &mut rgba, ColorType::Rgba8888).unwrap();
+    assert_eq!(rgba.iter().skip(3).step_by(4).copied().collect::<Vec<_>>(), [0, 255, 0, 255]);
+}
+
+/// YUYVの`(x, y)`ピクセルの`[Y, U, V]`を取得します。
+fn yuyv_at(data: &[u8], stride: usize, x: usize, y: usize) -> [u8; 3] {
+    let pair = stride * y + 4 * (x / 2);
+    [data[stride * y + 2 * x], data[pair + 1], data[pair + 3]]
+}
+
+#[test]
+fn yuyv_test() {
+    // 幅が奇数で各行に余白がある画像
+    let spec = ImageSpec::new(37, 5);
+    let stride = 4 * 19 + 6;
+    let data: Vec<u8> = (0..stride * spec.height as usize).map(|i| (i * 89 + 7) as u8).collect();
+
+    for (luma, (kr, kb)) in [(LumaCoefficients::Bt601, (0.299, 0.114)), (LumaCoefficients::Bt709, (0.2126, 0.0722))] {
+        for range in [YuvRange::Full, YuvRange::Limited] {
+            let conversion = YuvConversion { quantization: Quantization::Nearest, ..YuvConversion::new(luma, range) };
+
+            let mut buf = vec![0u8; encoded_size(&spec)];
+            encode_yuyv(&data, &mut buf, &spec, stride, &conversion).unwrap();
+
+            let mut rgb_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb888)];
+            decode(&buf, &mut rgb_buf, ColorType::Rgb888).unwrap();
+
+            // 実数で変換した値との差は量子化の誤差（RとBは約 4.1、Gは約 2.0）と固定小数点の誤差（1.0 以下）の範囲
+            let (y_scale, c_scale, y_offset) = match range {
+                YuvRange::Full => (1.0, 1.0, 0.0),
+                YuvRange::Limited => (255.0 / 219.0, 255.0 / 224.0, 16.0),
+            };
+
+            for (i, rgb) in rgb_buf.chunks_exact(3).enumerate() {
+                let [y, u, v] = yuyv_at(&data, stride, i % spec.width as usize, i / spec.width as usize);
+                let y = (y as f64 - y_offset) * y_scale;
+                let u = (u as f64 - 128.0) * c_scale;
+                let v = (v as f64 - 128.0) * c_scale;
+
+                let kg = 1.0 - kr - kb;
+                let expected = [
+                    y + 2.0 * (1.0 - kr) * v,
+                    y - 2.0 * kb * (1.0 - kb) / kg * u - 2.0 * kr * (1.0 - kr) / kg * v,
+                    y + 2.0 * (1.0 - kb) * u,
+                ];
+
+                for (channel, tolerance) in [(0, 5.0), (1, 3.0), (2, 5.0)] {
+                    let diff = (rgb[channel] as f64 - expected[channel].clamp(0.0, 255.0)).abs();
+                    assert!(diff <= tolerance, "{luma:?} {range:?} {i} {channel}: {rgb:?} {expected:?}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn yuv_gray_test() {
+    let spec = ImageSpec::with_pixel_endian(256, 1, PixelEndian::Big);
+
+    // フルレンジの無彩色はYがそのままRGBになる
+    let yuyv: Vec<u8> = (0..256).flat_map(|y| [y as u8, 128]).collect();
+    let rgb: Vec<u8> = (0..256).flat_map(|y| [y as u8; 3]).collect();
+
+    for quantization in [Quantization::Truncate, Quantization::Round, Quantization::Nearest] {
+        let conversion = YuvConversion { quantization, ..YuvConversion::new(LumaCoefficients::Bt709, YuvRange::Full) };
+
+        let mut yuyv_data = vec![0u8; encoded_size(&spec)];
+        encode_yuyv(&yuyv, &mut yuyv_data, &spec, yuyv.len(), &conversion).unwrap();
+
+        let mut rgb_data = vec![0u8; encoded_size(&spec)];
+        encode_with_options(&rgb, &mut rgb_data, &spec, ColorType::Rgb888, &EncodeOptions { quantization, ..EncodeOptions::new() }).unwrap();
+
+        assert_eq!(yuyv_data, rgb_data);
+    }
+
+    // リミテッドレンジは 16 - 235 が黒から白になる
+    let spec = ImageSpec::new(4, 1);
+    let yuyv = [0, 128, 16, 128, 235, 128, 255, 128];
+    let mut buf = [0u8; 4 * PIXEL_BYTES];
+    encode_data_yuyv(&yuyv, &mut buf, &spec, yuyv.len(), &YuvConversion::default()).unwrap();
+
+    let pixels: Vec<u16> = buf.chunks_exact(2).map(|pixel| u16::from_le_bytes([pixel[0], pixel[1]])).collect();
+    assert_eq!(pixels, [0x0000, 0x0000, 0xFFFF, 0xFFFF]);
+}
+
+#[test]
+fn nv12_test() {
+    // 幅と高さが奇数で各行に余白がある画像
+    let spec = ImageSpec::new(35, 7);
+    let (width, height) = (spec.width as usize, spec.height as usize);
+    let (y_stride, uv_stride) = (width + 5, 2 * width.div_ceil(2) + 3);
+
+    let y_plane: Vec<u8> = (0..y_stride * height).map(|i| (i * 89 + 7) as u8).collect();
+    let uv_plane: Vec<u8> = (0..uv_stride * height.div_ceil(2)).map(|i| (i * 53 + 101) as u8).collect();
+
+    // UV平面の行を2行ずつ共有するYUYVに並べ替える
+    let yuyv_stride = 4 * width.div_ceil(2);
+    let mut yuyv = vec![0u8; yuyv_stride * height];
+    for y in 0..height {
+        for x in 0..width.div_ceil(2) * 2 {
+            yuyv[yuyv_stride * y + 2 * x] = y_plane[y_stride * y + x.min(width - 1)];
+            yuyv[yuyv_stride * y + 2 * x + 1] = uv_plane[uv_stride * (y / 2) + x];
+        }
+    }
+
+    for conversion in [
+        YuvConversion::default(),
+        YuvConversion { quantization: Quantization::Round, ..YuvConversion::new(LumaCoefficients::Bt709, YuvRange::Full) },
+    ] {
+        let mut nv12_data = vec![0u8; encoded_size(&spec)];
+        let mut yuyv_data = vec![0u8; encoded_size(&spec)];
+        encode_nv12(&y_plane, &uv_plane, &mut nv12_data, &spec, y_stride, uv_stride, &conversion).unwrap();
+        encode_yuyv(&yuyv, &mut yuyv_data, &spec, yuyv_stride, &conversion).unwrap();
+        assert_eq!(nv12_data, yuyv_data);
+
+        // 最終行は余白なしでもよい
+        let y_len = y_stride * (height - 1) + width;
+        let uv_len = uv_stride * (height.div_ceil(2) - 1) + 2 * width.div_ceil(2);
+        encode_data_nv12(&y_plane[..y_len], &uv_plane[..uv_len], &mut nv12_data, &spec, y_stride, uv_stride, &conversion).unwrap();
+        assert_eq!(nv12_data[..PIXEL_BYTES * spec.num_pixels()], yuyv_data[HEADER_SIZE..]);
+    }
+}
+
+#[test]
+fn yuv_error_test() {
+    let spec = ImageSpec::new(5, 3);
+    let conversion = YuvConversion::default();
+
+    let yuyv = [0u8; 12 * 3];
+    let mut buf = [0u8; HEADER_SIZE + PIXEL_BYTES * 15];
+
+    assert!(matches!(encode_yuyv(&yuyv, &mut buf, &ImageSpec::new(0, 3), 12, &conversion), Err(Error::ZeroImageDimensions)));
+    assert!(matches!(encode_yuyv(&yuyv, &mut buf, &spec, 11, &conversion), Err(Error::InvalidStride)));
+    assert!(matches!(encode_yuyv(&yuyv[..35], &mut buf, &spec, 12, &conversion), Err(Error::InputBufferTooSmall)));
+    assert!(matches!(encode_yuyv(&yuyv, &mut buf[..HEADER_SIZE + PIXEL_BYTES * 15 - 1], &spec, 12, &conversion), Err(Error::OutputBufferTooSmall)));
+    assert!(matches!(encode_yuyv(&yuyv, &mut buf, &spec, 12, &conversion), Ok(size) if size == HEADER_SIZE + PIXEL_BYTES * 15));
+
+    let y_plane = [0u8; 5 * 3];
+    let uv_plane = [0u8; 6 * 2];
+
+    assert!(matches!(encode_nv12(&y_plane, &uv_plane, &mut buf, &spec, 4, 6, &conversion), Err(Error::InvalidStride)));
+    assert!(matches!(encode_nv12(&y_plane, &uv_plane, &mut buf, &spec, 5, 5, &conversion), Err(Error::InvalidStride)));
+    assert!(matches!(encode_nv12(&y_plane[..14], &uv_plane, &mut buf, &spec, 5, 6, &conversion), Err(Error::InputBufferTooSmall)));
+    assert!(matches!(encode_nv12(&y_plane, &uv_plane[..11], &mut buf, &spec, 5, 6, &conversion), Err(Error::InputBufferTooSmall)));
+    assert!(matches!(encode_data_nv12(&y_plane, &uv_plane, &mut buf[..PIXEL_BYTES * 15 - 1], &spec, 5, 6, &conversion), Err(Error::OutputBufferTooSmall)));
+    assert!(matches!(encode_data_nv12(&y_plane, &uv_plane, &mut buf, &spec, 5, 6, &conversion), Ok(size) if size == PIXEL_BYTES * 15));
+}
+
+#[test]
+fn tensor_test() {
+    let mut images = test_images();
+
+    // 透明色を含む画像
+    let spec = ImageSpec::with_transparent_color(70, 5, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [i as u8, (i * 7) as u8, (i * 13) as u8] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+        let num_pixels = spec.num_pixels();
+        assert_eq!(tensor_len(&spec), 3 * num_pixels);
+
+        let mut rgb_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb888)];
+        decode(&data, &mut rgb_buf, ColorType::Rgb888).unwrap();
+
+        // HWCはRGB888と同じで、CHWはその転置
+        let mut hwc = vec![0u8; tensor_len(&spec)];
+        let mut chw = vec![0u8; tensor_len(&spec)];
+        assert_eq!(decode_tensor(&data, &mut hwc, TensorLayout::Hwc).unwrap(), (spec, tensor_len(&spec)));
+        assert_eq!(decode_data_tensor(&data[HEADER_SIZE..], &mut chw, &spec, TensorLayout::Chw).unwrap(), tensor_len(&spec));
+        assert_eq!(hwc, rgb_buf);
+
+        for i in 0..num_pixels {
+            for channel in 0..3 {
+                assert_eq!(chw[num_pixels * channel + i], rgb_buf[3 * i + channel]);
+            }
+        }
+
+        for normalization in [None, Some(TensorNormalization::IMAGENET)] {
+            let mut hwc_f32 = vec![0f32; tensor_len(&spec)];
+            let mut chw_f32 = vec![0f32; tensor_len(&spec)];
+            decode_tensor_f32(&data, &mut hwc_f32, TensorLayout::Hwc, normalization.as_ref()).unwrap();
+            decode_data_tensor_f32(&data[HEADER_SIZE..], &mut chw_f32, &spec, TensorLayout::Chw, normalization.as_ref()).unwrap();
+
+            for i in 0..num_pixels {
+                for channel in 0..3 {
+                    let value = rgb_buf[3 * i + channel] as f32 / 255.0;
+                    let expected = match normalization {
+                        Some(normalization) => (value - normalization.mean[channel]) / normalization.std[channel],
+                        None => value,
+                    };
+
+                    assert_eq!(hwc_f32[3 * i + channel], expected);
+                    assert_eq!(chw_f32[num_pixels * channel + i], expected);
+                }
+            }
+        }
+    }
+
+    // バッファの長さが足りない
+    let data = &test_images()[0];
+    let spec = decode_header(data).unwrap();
+    let mut buf = vec![0u8; tensor_len(&spec) - 1];
+    let mut buf_f32 = vec![0f32; tensor_len(&spec) - 1];
+
+    assert!(matches!(decode_tensor(data, &mut buf, TensorLayout::Chw), Err(Error::OutputBufferTooSmall)));
+    assert!(matches!(decode_tensor_f32(data, &mut buf_f32, TensorLayout::Chw, None), Err(Error::OutputBufferTooSmall)));
+    assert!(matches!(decode_data_tensor(&data[HEADER_SIZE..data.len() - 1], &mut buf, &spec, TensorLayout::Hwc), Err(Error::InputBufferTooSmall)));
+    assert!(matches!(decode_data_tensor_f32(&data[HEADER_SIZE..data.len() - 1], &mut buf_f32, &spec, TensorLayout::Hwc, None), Err(Error::InputBufferTooSmall)));
+}
+
+/// 1行あたり`row_size`バイトのMono1データから`(x, y)`のビットを取得します。
+fn mono1_bit(buf: &[u8], row_size: usize, x: usize, y: usize) -> bool {
+    buf[row_size * y + x / 8] & (0x80 >> (x % 8)) != 0
+}
+
+#[test]
+fn mono1_test() {
+    let mut images = test_images();
+
+    // 透明色を含む幅が8の倍数ではない画像
+    let spec = ImageSpec::with_transparent_color(35, 6, rgb_to_pixel([255, 0, 255]));
+    let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % 4 == 0 { [255, 0, 255] } else { [(i * 5) as u8; 3] }).collect();
+    let mut data = vec![0u8; encoded_size(&spec)];
+    encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+    images.push(data);
+
+    for data in images {
+        let spec = decode_header(&data).unwrap();
+        let width = spec.width as usize;
+        let row_size = width.div_ceil(8);
+
+        assert_eq!(decoded_size(&spec, ColorType::Mono1), row_size * spec.height as usize);
+
+        let mut rgba_buf = vec![0u8; decoded_size(&spec, ColorType::Rgba8888)];
+        let mut l8_buf = vec![0u8; decoded_size(&spec, ColorType::L8)];
+        decode(&data, &mut rgba_buf, ColorType::Rgba8888).unwrap();
+        decode(&data, &mut l8_buf, ColorType::L8).unwrap();
+
+        for mono_background in [true, false] {
+            for mono_dither in [Dither::None, Dither::Bayer4x4, Dither::Bayer8x8] {
+                let mut options = DecodeOptions::new();
+                options.mono_background = mono_background;
+                options.mono_dither = mono_dither;
+
+                // パディングも書き込まれることを確認するため 0xFF で埋めておく
+                let mut mono_buf = vec![0xFFu8; decoded_size(&spec, ColorType::Mono1)];
+                let (_, written_size) = decode_with_options(&data, &mut mono_buf, ColorType::Mono1, &options).unwrap();
+                assert_eq!(written_size, mono_buf.len());
+
+                for y in 0..spec.height as usize {
+                    for x in 0..width {
+                        let i = width * y + x;
+                        let bit = mono1_bit(&mono_buf, row_size, x, y);
+
+                        if rgba_buf[4 * i + 3] == 0 {
+                            assert_eq!(bit, mono_background);
+                        } else if mono_dither == Dither::None {
+                            assert_eq!(bit, l8_buf[i] >= DecodeOptions::DEFAULT_MONO_THRESHOLD);
+                        } else if l8_buf[i] == 0 || l8_buf[i] == 255 {
+                            // 黒と白はディザリングしても変わらない
+                            assert_eq!(bit, l8_buf[i] == 255);
+                        }
+                    }
+
+                    for x in width..8 * row_size {
+                        assert!(!mono1_bit(&mono_buf, row_size, x, y));
+                    }
+                }
+            }
+        }
+
+        // 白黒のRGB565に展開される
+        let mut mono_buf = vec![0u8; decoded_size(&spec, ColorType::Mono1)];
+        decode(&data, &mut mono_buf, ColorType::Mono1).unwrap();
+
+        let mut mono_data = vec![0u8; encoded_size(&spec)];
+        encode(&mono_buf, &mut mono_data, &spec, ColorType::Mono1).unwrap();
+
+        let mut rgb565_buf = vec![0u8; decoded_size(&spec, ColorType::Rgb565)];
+        decode(&mono_data, &mut rgb565_buf, ColorType::Rgb565).unwrap();
+
+        for (i, pixel) in rgb565_buf.chunks_exact(2).enumerate() {
+            let expected = if mono1_bit(&mono_buf, row_size, i % width, i / width) { 0xFFFF } else { 0x0000 };
+            assert_eq!(u16::from_ne_bytes([pixel[0], pixel[1]]), expected);
+        }
+
+        // 白が透明色と衝突する場合は補正される
+        let white_spec = ImageSpec { transparent_color: Some(0xFFFF), ..spec };
+        let (_, adjusted_pixels) = encode_data_collision_free(&mono_buf, &mut mono_data[HEADER_SIZE..], &white_spec, ColorType::Mono1, &EncodeOptions::new()).unwrap();
+        assert_eq!(adjusted_pixels, (0..spec.num_pixels()).filter(|&i| mono1_bit(&mono_buf, row_size, i % width, i / width)).count());
+    }
+}
+
+#[test]
+fn mono1_dither_test() {
+    // 単色の画像では、周期内で輝度以下のしきい値の数だけ白になる
+    let spec = ImageSpec::new(16, 16);
+
+    for gray in [0u8, 60, 128, 200, 255] {
+        let data = rgb_to_pixel([gray; 3]).to_le_bytes().repeat(spec.num_pixels());
+        let luma = LumaCoefficients::Bt601.luma(pixel_to_rgb(rgb_to_pixel([gray; 3]))) as usize;
+
+        for (mono_dither, n) in [(Dither::Bayer4x4, 16), (Dither::Bayer8x8, 64)] {
+            let mut options = DecodeOptions::new();
+            options.mono_dither = mono_dither;
+
+            let mut mono_buf = vec![0u8; decoded_size(&spec, ColorType::Mono1)];
+            decode_data_with_options(&data, &mut mono_buf, &spec, ColorType::Mono1, &options).unwrap();
+
+            let whites = (0..n).filter(|b| (2 * b + 1) * 128 / n <= luma).count() * (spec.num_pixels() / n);
+            assert_eq!(mono_buf.iter().map(|byte| byte.count_ones() as usize).sum::<usize>(), whites);
+        }
+    }
+}
+
+#[test]
+fn mono1_error_diffusion_test() {
+    let spec = ImageSpec::with_transparent_color(61, 40, rgb_to_pixel([255, 0, 255]));
+    let row_size = ColorType::Mono1.bytes_per_row(spec.width as usize);
+    let mut scratch = vec![0i16; diffusion_scratch_len(spec.width)];
+
+    for kernel in [DiffusionKernel::FloydSteinberg, DiffusionKernel::Atkinson] {
+        for serpentine in [true, false] {
+            let diffusion = ErrorDiffusion { kernel, serpentine };
+
+            for gray in [0u8, 64, 128, 192, 255] {
+                // 左端の列は透明色
+                let rgb: Vec<u8> = (0..spec.num_pixels()).flat_map(|i| if i % spec.width as usize == 0 { [255, 0, 255] } else { [gray; 3] }).collect();
+                let mut data = vec![0u8; encoded_size(&spec)];
+                encode(&rgb, &mut data, &spec, ColorType::Rgb888).unwrap();
+
+                let luma = LumaCoefficients::Bt601.luma(pixel_to_rgb(rgb_to_pixel([gray; 3])));
+
+                let mut mono_buf = vec![0xFFu8; decoded_size(&spec, ColorType::Mono1)];
+                let (_, written_size) = decode_mono_error_diffused(&data, &mut mono_buf, &DecodeOptions::new(), &diffusion, &mut scratch).unwrap();
+                assert_eq!(written_size, mono_buf.len());
+
+                let mut whites = 0;
+
+                for y in 0..spec.height as usize {
+                    assert!(mono1_bit(&mono_buf, row_size, 0, y));
+
+                    for x in 1..spec.width as usize {
+                        whites += mono1_bit(&mono_buf, row_size, x, y) as usize;
+                    }
+
+                    for x in spec.width as usize..8 * row_size {
+                        assert!(!mono1_bit(&mono_buf, row_size, x, y));
+                    }
+                }
+
+                // 白の割合は輝度に近くなる（Atkinsonは誤差の一部を捨てるため中間調が離れやすい）
+                let num_pixels = (spec.width as usize - 1) * spec.height as usize;
+                let ratio = whites as f64 / num_pixels as f64;
+                let tolerance = if kernel == DiffusionKernel::Atkinson { 0.1 } else { 0.03 };
+                assert!((ratio - luma as f64 / 255.0).abs() < tolerance, "{kernel:?} {gray}: {ratio}");
+
+                if gray == 0 || gray == 255 {
+                    assert_eq!(whites, if gray == 0 { 0 } else { num_pixels });
+                }
+            }
+        }
+    }
+
+    // 作業バッファが足りない
+    let data = vec![0u8; PIXEL_BYTES * spec.num_pixels()];
+    let mut mono_buf = vec![0u8; decoded_size(&spec, ColorType::Mono1)];
+    let diffusion = ErrorDiffusion::new(DiffusionKernel::FloydSteinberg);
+    let result = decode_data_mono_error_diffused(&data, &mut mono_buf, &spec, &DecodeOptions::new(), &diffusion, &mut scratch[..1]);
+    assert!(matches!(result, Err(Error::ScratchBufferTooSmall)));
+}
+
+#[test]
+fn mono1_unsupported_test() {
+    let data = &test_images()[0];
+    let spec = decode_header(data).unwrap();
+    let mut buf = vec![0u8; 4 * spec.num_pixels()];
+
+    assert!(matches!(decode_rect(data, &mut buf, 0, 0, 1, 1, ColorType::Mono1), Err(Error::UnsupportedColorType)));
+    assert!(matches!(decode_strided(data, &mut buf, 64, 0, 0, ColorType::Mono1), Err(Error::UnsupportedColorType)));
+    assert!(matches!(decode_oriented(data, &mut buf, ColorType::Mono1, Orientation::Rotate90), Err(Error::UnsupportedColorType)));
+    assert!(matches!(blit(data, &mut buf, 64, 0, 0, ColorType::Mono1), Err(Error::UnsupportedColorType)));
+    assert!(matches!(ChunkedDecoder::new(data, ColorType::Mono1), Err(Error::UnsupportedColorType)));
+    assert!(matches!(IncrementalDecoder::new(&mut buf, ColorType::Mono1).feed(data, |_, _| {}), Err(Error::UnsupportedColorType)));
+    assert!(matches!(encode_data_strided(&buf, &mut vec![0u8; encoded_size(&spec)], &spec, 64, 0, 0, ColorType::Mono1), Err(Error::UnsupportedColorType)));
+}